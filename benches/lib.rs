@@ -4,14 +4,15 @@ extern crate mco_redis;
 
 use test::Bencher;
 use mco_redis::cmd;
-use mco_redis::connector::RedisConnector;
+use mco_redis::testing::MockServer;
 
 #[bench]
 fn bench_get(b: &mut Bencher) {
-    let redis = RedisConnector::new("127.0.0.1:6379").connect().unwrap();
+    let server = MockServer::start().unwrap();
+    let redis = server.connector().connect().unwrap();
     redis.exec(cmd::Set("test", "value")).unwrap();
 
     b.iter(|| {
-        redis.exec(cmd::Get("test"));
+        redis.exec(cmd::Get("test")).unwrap();
     });
 }
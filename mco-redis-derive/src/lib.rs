@@ -0,0 +1,363 @@
+//! Derive macros for mco-redis
+//!
+//! Use `derive` feature of `mco-redis` crate instead of depending on this
+//! crate directly.
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, Attribute, Data, DeriveInput, Fields, LitByteStr, LitStr, Path, Type,
+};
+
+/// Derive `mco_redis::FromResponse` for a struct
+///
+/// See `FromResponse` trait documentation for supported attributes.
+#[proc_macro_derive(FromResponse, attributes(redis))]
+pub fn derive_from_response(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Derive `mco_redis::ToArgs` for a struct
+///
+/// See `ToArgs` trait documentation for supported attributes.
+#[proc_macro_derive(ToArgs, attributes(redis))]
+pub fn derive_to_args(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand_to_args(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+#[derive(Default)]
+struct FieldAttrs {
+    rename: Option<String>,
+    default: bool,
+    skip: bool,
+    with: Option<Path>,
+}
+
+fn field_attrs(attrs: &[Attribute]) -> syn::Result<FieldAttrs> {
+    let mut result = FieldAttrs::default();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("redis")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                let name: LitStr = meta.value()?.parse()?;
+                result.rename = Some(name.value());
+            } else if meta.path.is_ident("default") {
+                result.default = true;
+            } else if meta.path.is_ident("skip") {
+                result.skip = true;
+            } else if meta.path.is_ident("with") {
+                let path: LitStr = meta.value()?.parse()?;
+                result.with = Some(path.parse()?);
+            } else {
+                return Err(meta.error("unsupported redis field attribute"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(result)
+}
+
+fn is_positional(attrs: &[Attribute]) -> syn::Result<bool> {
+    let mut positional = false;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("redis")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("positional") {
+                positional = true;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported redis container attribute"))
+            }
+        })?;
+    }
+    Ok(positional)
+}
+
+struct Field {
+    /// Struct field name, `None` for tuple structs
+    ident: Option<syn::Ident>,
+    /// Local variable for the decoded value
+    var: syn::Ident,
+    /// Redis field name
+    name: String,
+    ty: Type,
+    attrs: FieldAttrs,
+}
+
+impl Field {
+    fn decode(&self) -> TokenStream2 {
+        let ty = &self.ty;
+        match self.attrs.with {
+            Some(ref path) => quote!(#path(value)?),
+            None => quote!(<#ty as ::mco_redis::FromResponse>::from_response(value)?),
+        }
+    }
+
+    /// Missing `Option` fields are decoded as `None`
+    fn is_option(&self) -> bool {
+        match self.ty {
+            Type::Path(ref tp) => tp
+                .path
+                .segments
+                .last()
+                .map(|seg| seg.ident == "Option")
+                .unwrap_or(false),
+            _ => false,
+        }
+    }
+
+    fn missing(&self) -> TokenStream2 {
+        if self.attrs.default || self.attrs.skip {
+            quote!(::std::default::Default::default())
+        } else if self.is_option() {
+            quote!(::std::option::Option::None)
+        } else {
+            let msg = LitStr::new(&format!("Missing field `{}`", self.name), Span::call_site());
+            quote!(return Err(::mco_redis::errors::CommandError::Output(
+                #msg,
+                ::mco_redis::codec_redis::Response::Nil,
+            )))
+        }
+    }
+
+    /// Nil values are replaced with default for fields with `default` attribute
+    fn is_present(&self) -> TokenStream2 {
+        if self.attrs.default {
+            quote!(!matches!(value, ::mco_redis::codec_redis::Response::Nil))
+        } else {
+            quote!(true)
+        }
+    }
+}
+
+struct Struct<'a> {
+    input: &'a DeriveInput,
+    named: bool,
+    positional: bool,
+    fields: Vec<Field>,
+}
+
+fn parse_struct<'a>(input: &'a DeriveInput, derive: &str) -> syn::Result<Struct<'a>> {
+    let data = match input.data {
+        Data::Struct(ref data) => data,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                format!("{} can be derived for structs only", derive),
+            ))
+        }
+    };
+    let named = match data.fields {
+        Fields::Named(_) => true,
+        Fields::Unnamed(_) => false,
+        Fields::Unit => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                format!("{} cannot be derived for unit structs", derive),
+            ))
+        }
+    };
+
+    let mut fields = Vec::new();
+    for (idx, field) in data.fields.iter().enumerate() {
+        let attrs = field_attrs(&field.attrs)?;
+        let name = match (&attrs.rename, &field.ident) {
+            (Some(name), _) => name.clone(),
+            (None, Some(ident)) => ident.to_string().trim_start_matches("r#").to_string(),
+            (None, None) => idx.to_string(),
+        };
+        fields.push(Field {
+            ident: field.ident.clone(),
+            var: format_ident!("__field{}", idx),
+            name,
+            ty: field.ty.clone(),
+            attrs,
+        });
+    }
+
+    Ok(Struct {
+        input,
+        named,
+        positional: !named || is_positional(&input.attrs)?,
+        fields,
+    })
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let Struct {
+        input,
+        named,
+        positional,
+        fields,
+    } = parse_struct(&input, "FromResponse")?;
+
+    let body = if !positional {
+        expand_map(&fields)
+    } else {
+        expand_positional(&fields)
+    };
+
+    let vars = fields.iter().map(|f| &f.var);
+    let construct = if named {
+        let idents = fields.iter().map(|f| &f.ident);
+        quote!(Self { #(#idents: #vars),* })
+    } else {
+        quote!(Self(#(#vars),*))
+    };
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::mco_redis::FromResponse for #ident #ty_generics #where_clause {
+            fn from_response(
+                val: ::mco_redis::codec_redis::Response,
+            ) -> ::std::result::Result<Self, ::mco_redis::errors::CommandError> {
+                #body
+                Ok(#construct)
+            }
+        }
+    })
+}
+
+fn expand_to_args(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let Struct {
+        input,
+        positional,
+        fields,
+        ..
+    } = parse_struct(&input, "ToArgs")?;
+
+    let write = fields
+        .iter()
+        .enumerate()
+        .filter(|(_, f)| !f.attrs.skip)
+        .map(|(idx, f)| {
+            let member = match f.ident {
+                Some(ref ident) => quote!(#ident),
+                None => {
+                    let idx = syn::Index::from(idx);
+                    quote!(#idx)
+                }
+            };
+            if positional {
+                quote!(::mco_redis::ToArgs::write_args(self.#member, args);)
+            } else {
+                let name = LitByteStr::new(f.name.as_bytes(), Span::call_site());
+                let push = quote! {
+                    args.push(::mco_redis::codec_redis::Request::BulkStatic(#name));
+                    ::mco_redis::ToArgs::write_args(value, args);
+                };
+                if f.is_option() {
+                    // field/value pair is not written for `None` values
+                    quote! {
+                        if let Some(value) = self.#member {
+                            #push
+                        }
+                    }
+                } else {
+                    quote! {
+                        let value = self.#member;
+                        #push
+                    }
+                }
+            }
+        });
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::mco_redis::ToArgs for #ident #ty_generics #where_clause {
+            fn write_args(
+                self,
+                args: &mut ::std::vec::Vec<::mco_redis::codec_redis::Request>,
+            ) {
+                #(#write)*
+            }
+        }
+    })
+}
+
+/// Decode field/value array
+fn expand_map(fields: &[Field]) -> TokenStream2 {
+    // skipped fields are never assigned
+    let declare = fields.iter().map(|f| {
+        let var = &f.var;
+        let ty = &f.ty;
+        if f.attrs.skip {
+            quote!(let #var: ::std::option::Option<#ty> = None;)
+        } else {
+            quote!(let mut #var: ::std::option::Option<#ty> = None;)
+        }
+    });
+    let arms = fields.iter().filter(|f| !f.attrs.skip).map(|f| {
+        let var = &f.var;
+        let name = LitByteStr::new(f.name.as_bytes(), Span::call_site());
+        let present = f.is_present();
+        let decode = f.decode();
+        quote! {
+            #name => if #present {
+                #var = Some(#decode);
+            }
+        }
+    });
+    let unwrap = fields.iter().map(|f| {
+        let var = &f.var;
+        let missing = f.missing();
+        quote! {
+            let #var = match #var {
+                Some(value) => value,
+                None => #missing,
+            };
+        }
+    });
+
+    quote! {
+        #(#declare)*
+        for (key, value) in ::mco_redis::response::entries(val)? {
+            let key = <::mco_redis::bytes::Bytes as ::mco_redis::FromResponse>::from_response(key)?;
+            match key.as_ref() {
+                #(#arms)*
+                _ => (),
+            }
+        }
+        #(#unwrap)*
+    }
+}
+
+/// Decode array of values
+fn expand_positional(fields: &[Field]) -> TokenStream2 {
+    let decode = fields.iter().map(|f| {
+        let var = &f.var;
+        let present = f.is_present();
+        let decode = f.decode();
+        let missing = f.missing();
+        if f.attrs.skip {
+            return quote!(let #var = #missing;);
+        }
+        quote! {
+            let #var = match items.next() {
+                Some(value) if #present => #decode,
+                _ => #missing,
+            };
+        }
+    });
+
+    quote! {
+        let mut items = match val {
+            ::mco_redis::codec_redis::Response::Array(items) => items.into_iter(),
+            val => {
+                return Err(::mco_redis::errors::CommandError::Output(
+                    "Cannot be converted into a struct",
+                    val,
+                ))
+            }
+        };
+        #(#decode)*
+    }
+}
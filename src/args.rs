@@ -0,0 +1,267 @@
+//! Encoding of rust types into redis command arguments
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::convert::TryFrom;
+
+use crate::bytes::{ByteString, Bytes, BytesMut};
+use crate::codec_redis::{BulkString, Request};
+
+/// Trait implemented by types that can be written as redis command arguments
+///
+/// Commands with variable number of arguments, like `HSET`, `RPUSH`,
+/// `MSET` or `EVAL`, accept values implementing this trait. Integers,
+/// floats and booleans are written in their redis representation, `None`
+/// writes nothing, tuples, slices and collections write all of their
+/// items, maps write keys and values one after another.
+///
+/// Single value arguments, like value of `SET` command, accept `ToArg`
+/// values instead.
+///
+/// User structs could implement it with `#[derive(ToArgs)]`, available
+/// with `derive` feature. Derived implementation writes field/value pairs,
+/// fields with `None` value are skipped. Structs marked with
+/// `#[redis(positional)]` and tuple structs write values only.
+/// Field attributes:
+///
+/// * `#[redis(rename = "name")]` - use different field name
+/// * `#[redis(skip)]` - do not write field
+///
+/// ```rust
+/// use mco_redis::{cmd, FromResponse, ToArgs};
+/// use mco_redis::testing::MockServer;
+///
+/// #[derive(ToArgs, FromResponse)]
+/// struct User {
+///     name: String,
+///     #[redis(rename = "login_count")]
+///     logins: u64,
+///     email: Option<String>,
+/// }
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let server = MockServer::start()?;
+///     let redis = server.connector().connect()?;
+///
+///     let user = User { name: "alice".to_string(), logins: 3, email: None };
+///     redis.exec(cmd::HSetEntries("user:1", user))?;
+///
+///     let user = redis.exec(cmd::HGetAll("user:1").output::<User>())?;
+///     assert_eq!(user.logins, 3);
+///     assert_eq!(user.email, None);
+///     Ok(())
+/// }
+/// ```
+pub trait ToArgs {
+    /// Write value as redis command arguments
+    fn write_args(self, args: &mut Vec<Request>);
+}
+
+/// Trait implemented by types that are written as exactly one redis
+/// command argument
+///
+/// Field and value positions, like value of `SET` command or field and
+/// value of `HSetCommand::entry()`, accept values implementing this trait,
+/// so arguments that follow cannot be shifted by `None` or by a collection.
+pub trait ToArg {
+    /// Convert value to a redis command argument
+    fn to_arg(self) -> Request;
+}
+
+macro_rules! impl_to_arg_bulk {
+    ($($tp:ty),* $(,)*) => {
+        $(
+            impl ToArg for $tp {
+                fn to_arg(self) -> Request {
+                    Request::BulkString(self.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_to_arg_bulk!(BulkString, ByteString, String, Bytes, BytesMut, Vec<u8>);
+
+macro_rules! impl_to_arg_bulk_ref {
+    ($($tp:ty),* $(,)*) => {
+        $(
+            impl<'a> ToArg for &'a $tp {
+                fn to_arg(self) -> Request {
+                    Request::BulkString(self.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_to_arg_bulk_ref!(ByteString, String, str, Bytes, [u8]);
+
+impl ToArg for &&str {
+    fn to_arg(self) -> Request {
+        Request::BulkString(self.into())
+    }
+}
+
+macro_rules! impl_to_arg_int {
+    ($($tp:ty),* $(,)*) => {
+        $(
+            impl ToArg for $tp {
+                fn to_arg(self) -> Request {
+                    Request::BulkInteger(self as i64)
+                }
+            }
+        )*
+    };
+}
+
+// `u8` is not implemented, `Vec<u8>` and `&[u8]` are written as bytes
+impl_to_arg_int!(i8, i16, i32, i64, isize, u16, u32);
+
+macro_rules! impl_to_arg_uint {
+    ($($tp:ty),* $(,)*) => {
+        $(
+            impl ToArg for $tp {
+                fn to_arg(self) -> Request {
+                    match i64::try_from(self) {
+                        Ok(val) => Request::BulkInteger(val),
+                        Err(_) => Request::BulkString(self.to_string().into()),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_to_arg_uint!(u64, usize);
+
+macro_rules! impl_to_arg_float {
+    ($($tp:ty),* $(,)*) => {
+        $(
+            impl ToArg for $tp {
+                fn to_arg(self) -> Request {
+                    // redis parses `inf` and `-inf`
+                    Request::BulkString(self.to_string().into())
+                }
+            }
+        )*
+    };
+}
+
+impl_to_arg_float!(f32, f64);
+
+impl ToArg for bool {
+    fn to_arg(self) -> Request {
+        Request::BulkStatic(if self { b"1" } else { b"0" })
+    }
+}
+
+/// Single value types write one argument
+macro_rules! impl_to_args_single {
+    ($($tp:ty),* $(,)*) => {
+        $(
+            impl ToArgs for $tp {
+                fn write_args(self, args: &mut Vec<Request>) {
+                    args.push(self.to_arg());
+                }
+            }
+        )*
+    };
+}
+
+impl_to_args_single!(
+    BulkString,
+    ByteString,
+    String,
+    Bytes,
+    BytesMut,
+    Vec<u8>,
+    &ByteString,
+    &String,
+    &str,
+    &&str,
+    &Bytes,
+    &[u8],
+    i8,
+    i16,
+    i32,
+    i64,
+    isize,
+    u16,
+    u32,
+    u64,
+    usize,
+    f32,
+    f64,
+    bool,
+);
+
+impl<T: ToArgs> ToArgs for Option<T> {
+    fn write_args(self, args: &mut Vec<Request>) {
+        if let Some(val) = self {
+            val.write_args(args);
+        }
+    }
+}
+
+impl<T: ToArgs + Clone> ToArgs for &[T] {
+    fn write_args(self, args: &mut Vec<Request>) {
+        for item in self {
+            item.clone().write_args(args);
+        }
+    }
+}
+
+impl<T: ToArgs> ToArgs for Vec<T> {
+    fn write_args(self, args: &mut Vec<Request>) {
+        for item in self {
+            item.write_args(args);
+        }
+    }
+}
+
+impl<T: ToArgs, S> ToArgs for HashSet<T, S> {
+    fn write_args(self, args: &mut Vec<Request>) {
+        for item in self {
+            item.write_args(args);
+        }
+    }
+}
+
+impl<T: ToArgs> ToArgs for BTreeSet<T> {
+    fn write_args(self, args: &mut Vec<Request>) {
+        for item in self {
+            item.write_args(args);
+        }
+    }
+}
+
+impl<K: ToArgs, V: ToArgs, S> ToArgs for HashMap<K, V, S> {
+    fn write_args(self, args: &mut Vec<Request>) {
+        for (key, value) in self {
+            key.write_args(args);
+            value.write_args(args);
+        }
+    }
+}
+
+impl<K: ToArgs, V: ToArgs> ToArgs for BTreeMap<K, V> {
+    fn write_args(self, args: &mut Vec<Request>) {
+        for (key, value) in self {
+            key.write_args(args);
+            value.write_args(args);
+        }
+    }
+}
+
+impl<A: ToArgs, B: ToArgs> ToArgs for (A, B) {
+    fn write_args(self, args: &mut Vec<Request>) {
+        self.0.write_args(args);
+        self.1.write_args(args);
+    }
+}
+
+impl<A: ToArgs, B: ToArgs, C: ToArgs> ToArgs for (A, B, C) {
+    fn write_args(self, args: &mut Vec<Request>) {
+        self.0.write_args(args);
+        self.1.write_args(args);
+        self.2.write_args(args);
+    }
+}
@@ -1,26 +1,44 @@
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use std::sync::mpsc::RecvError;
 use mco::chan;
 use mco::coroutine::spawn;
 use either::Either;
-use crate::codec_redis::{Codec, Request, Response};
-use super::cmd::Command;
-use super::errors::{CommandError, Error};
+use crate::codec_redis::{Codec, Request, RequestChunks, Response};
+use super::cmd::{self, BlockingCommand, Command};
+use super::cmd::commands::{CursorCommand, EvalCommand, FlushMode, Library, ScanIter, ScriptCall, WaitCommand};
+use super::errors::{CommandError, Error, ServerErrorKind};
 use mco::std::sync::{Receiver, Sender};
-use crate::bytes::BytesMut;
+use crate::bytes::ByteString;
 use crate::simple::SimpleClient;
+use crate::response::FromResponse;
 
 pub type CommandResult = Result<Response, Error>;
 
 /// Shared redis client
+///
+/// All callers share single connection, so blocking commands (`BLPOP`,
+/// `BZPOPMIN`, etc) are not supported. Use `SimpleClient` on a dedicated
+/// connection for them.
 #[derive(Clone)]
 pub struct Client {
     io: Arc<SimpleClient>,
-    queue: Arc<(Sender<(BytesMut, Sender<Result<Response, Error>>)>, Receiver<(BytesMut, Sender<Result<Response, Error>>)>)>,
+    queue: Arc<(Sender<(RequestChunks, Sender<Result<Response, Error>>)>, Receiver<(RequestChunks, Sender<Result<Response, Error>>)>)>,
+    id: Option<i64>,
+    name: Arc<Mutex<Option<ByteString>>>,
+    read_timeout: Option<Duration>,
 }
 
 impl Client {
-    pub(crate) fn new(io: SimpleClient) -> Self {
+    pub(crate) fn new(io: SimpleClient, name: Option<ByteString>) -> Self {
+        let read_timeout = io
+            .io
+            .borrow()
+            .as_ref()
+            .and_then(|io| io.read_timeout().ok())
+            .flatten();
+        // connection id does not change, request it before io is shared
+        let id = io.exec(cmd::ClientId()).ok();
         let io = Arc::new(io);
         let queue = Arc::new(chan!());
         // read redis response task
@@ -31,7 +49,7 @@ impl Client {
                 match queue_clone.1.recv() {
                     Ok((data,s)) => {
                         let s:Sender<Result<Response, Error>> = s;
-                        s.send(match io_clone.send(&data){
+                        s.send(match io_clone.send_chunks(data){
                             Ok(d)=>{
                                 Ok(d)
                             }
@@ -50,6 +68,9 @@ impl Client {
         Client {
             queue: queue,
             io: io,
+            id,
+            name: Arc::new(Mutex::new(name)),
+            read_timeout,
         }
     }
 
@@ -58,8 +79,12 @@ impl Client {
         where
             T: Command,
     {
+        T::to_output(self.exec_request(cmd.to_request())?)
+    }
+
+    fn exec_request(&self, req: Request) -> Result<Response, CommandError> {
         let is_open = !self.io.is_closed();
-        let result = self.call(cmd.to_request());
+        let result = self.call(req);
         if !is_open {
             Err(CommandError::Protocol(Error::PeerGone(None)))
         } else {
@@ -67,7 +92,7 @@ impl Client {
                 Either::Left(v) => {
                     match v{
                         Ok(res) => {
-                            T::to_output(res.into_result().map_err(CommandError::Error)?)
+                            res.into_result().map_err(|err| CommandError::Error(err.into()))
                         }
                         Err(e) => {
                           Err(CommandError::Protocol(e))
@@ -76,22 +101,124 @@ impl Client {
                 }
                 Either::Right(v) => {
                     v.map_err(CommandError::Protocol)
-                        .and_then(|res| T::to_output(res.into_result().map_err(CommandError::Error)?))
+                        .and_then(|res| res.into_result().map_err(|err| CommandError::Error(err.into())))
                 }
             }
         }
     }
 
-    /// Delete all the keys of the currently selected DB.
-    pub fn flushdb(&self) -> Result<(), Error> {
-        match self.call("FLUSHDB".into()){
-            Either::Left(v) => {
-                v?;
+    /// Execute write command and wait for replicas acknowledgement
+    ///
+    /// Returns command output and the number of replicas that acknowledged
+    /// the write, it could be less than requested if timeout expires.
+    /// `WAIT` holds shared connection, so timeout must be non-zero and less
+    /// than connection read timeout. Use `SimpleClient::write_and_wait()`
+    /// on a dedicated connection for longer timeouts.
+    pub fn write_and_wait<T>(&self, cmd: T, replicas: usize, timeout: Duration) -> Result<(T::Output, usize), CommandError>
+        where
+            T: Command,
+    {
+        if timeout.is_zero() {
+            return Err(CommandError::Protocol(Error::Command(
+                "WAIT timeout must be non-zero on shared connection".to_string(),
+            )));
+        }
+        if let Some(read_timeout) = self.read_timeout {
+            if cmd::utils::round_timeout(timeout) >= read_timeout {
+                return Err(CommandError::Protocol(Error::Command(
+                    "WAIT timeout must be less than connection read timeout".to_string(),
+                )));
             }
-            Either::Right(v) => {
-                v?;
+        }
+        let output = self.exec(cmd)?;
+        let acked = WaitCommand::to_output(
+            self.exec_request(cmd::Wait(replicas, timeout).to_request())?,
+        )?;
+        Ok((output, acked))
+    }
+
+    /// Execute lua script
+    ///
+    /// Script is executed with `EVALSHA` command, if script is not loaded
+    /// yet, it is executed with `EVAL` command, which loads it to the cache.
+    pub fn exec_script<T>(&self, call: ScriptCall<T>) -> Result<T, CommandError>
+        where
+            T: FromResponse,
+    {
+        call.exec_with(|cmd| self.exec(cmd))
+    }
+
+    /// Load functions library
+    ///
+    /// Library is loaded if it is not loaded yet, or replaced if loaded
+    /// code differs.
+    pub fn ensure_library(&self, lib: &Library) -> Result<(), CommandError> {
+        if let Some(cmd) = lib.load(&self.exec(lib.list())?) {
+            self.exec(cmd)?;
+        }
+        Ok(())
+    }
+
+    /// Execute `FCALL` or `FCALL_RO` command
+    ///
+    /// If function is not found, library is loaded and command is retried.
+    pub fn exec_function<T>(&self, lib: &Library, cmd: EvalCommand<T>) -> Result<T, CommandError>
+        where
+            T: FromResponse,
+    {
+        let retry = cmd.clone();
+        match self.exec(cmd) {
+            Err(CommandError::Error(ref err))
+                if err.kind == ServerErrorKind::Err && err.message.starts_with("Function not found") =>
+            {
+                self.ensure_library(lib)?;
+                self.exec(retry)
             }
+            res => res,
         }
+    }
+
+    /// Iterate over all elements of the cursor based command
+    ///
+    /// Cursor is driven until redis returns 0, next batch of elements is
+    /// requested only when previous one is consumed.
+    pub fn scan<T>(&self, cmd: T) -> ScanIter<T, impl FnMut(T) -> Result<T::Output, CommandError> + '_>
+        where
+            T: CursorCommand,
+    {
+        ScanIter::new(cmd, move |cmd| self.exec(cmd))
+    }
+
+    /// Delete all the keys of the currently selected DB.
+    pub fn flushdb(&self) -> Result<(), Error> {
+        self.exec(cmd::FlushDb())?;
+        Ok(())
+    }
+
+    /// Delete all the keys of the currently selected DB with the given mode.
+    pub fn flushdb_mode(&self, mode: FlushMode) -> Result<(), CommandError> {
+        self.exec(cmd::FlushDb().mode(mode))
+    }
+
+    /// Server-side id of the connection
+    ///
+    /// Id is requested on connect. Returns `None` if server does not
+    /// support `CLIENT ID` command.
+    pub fn id(&self) -> Option<i64> {
+        self.id
+    }
+
+    /// Name of the connection, requested from the server
+    pub fn name(&self) -> Result<Option<ByteString>, CommandError> {
+        self.exec(cmd::ClientGetName().output::<ByteString>())
+    }
+
+    /// Set name of the connection, it is displayed in the output of
+    /// `CLIENT LIST`.
+    pub fn set_name<T: Into<ByteString>>(&self, name: T) -> Result<(), CommandError> {
+        let name = name.into();
+        self.exec(cmd::ClientSetName(name.clone()))?;
+        *self.name.lock().unwrap() = Some(name);
         Ok(())
     }
 
@@ -102,9 +229,8 @@ impl Client {
 
     /// call and return Either
     pub fn call(&self, req: Request) -> Either<CommandResult, Result<Response, Error>> {
-        let mut buf = BytesMut::new();
-        match self.io.encode_req(req,&mut buf) {
-            Ok(_) => {
+        match self.io.encode_chunks(req) {
+            Ok(buf) => {
                 let (tx, rx) = chan!();
                 self.queue.0.send((buf,tx));
                 match rx.recv() {
@@ -133,6 +259,8 @@ impl Client {
 impl std::fmt::Debug for Client {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Client")
+            .field("id", &self.id)
+            .field("name", &*self.name.lock().unwrap())
             .field("connected", &!self.io.is_closed())
             .finish()
     }
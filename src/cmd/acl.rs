@@ -0,0 +1,690 @@
+use std::convert::TryFrom;
+
+use super::{utils, Command, CommandError};
+use crate::bytes::ByteString;
+use crate::codec_redis::{BulkString, Request, Response};
+
+#[derive(Debug, Clone, Default)]
+/// Access rules of the user or of the selector
+///
+/// `T` is username for `ACL SETUSER` command and unit for selectors.
+pub struct AclRules<T> {
+    target: T,
+    rules: Vec<String>,
+}
+
+impl<T> AclRules<T> {
+    fn push(&mut self, rule: String) {
+        self.rules.push(rule);
+    }
+
+    /// Add raw rule.
+    pub fn rule<U: AsRef<str>>(mut self, rule: U) -> Self {
+        self.push(rule.as_ref().to_string());
+        self
+    }
+
+    /// Allow access to keys matching the pattern.
+    pub fn key_pattern<U: AsRef<str>>(mut self, pattern: U) -> Self {
+        self.push(format!("~{}", pattern.as_ref()));
+        self
+    }
+
+    /// Allow read access to keys matching the pattern.
+    pub fn read_key_pattern<U: AsRef<str>>(mut self, pattern: U) -> Self {
+        self.push(format!("%R~{}", pattern.as_ref()));
+        self
+    }
+
+    /// Allow write access to keys matching the pattern.
+    pub fn write_key_pattern<U: AsRef<str>>(mut self, pattern: U) -> Self {
+        self.push(format!("%W~{}", pattern.as_ref()));
+        self
+    }
+
+    /// Allow access to all keys.
+    pub fn all_keys(mut self) -> Self {
+        self.push("allkeys".to_string());
+        self
+    }
+
+    /// Allow access to pub/sub channels matching the pattern.
+    pub fn channel_pattern<U: AsRef<str>>(mut self, pattern: U) -> Self {
+        self.push(format!("&{}", pattern.as_ref()));
+        self
+    }
+
+    /// Allow access to all pub/sub channels.
+    pub fn all_channels(mut self) -> Self {
+        self.push("allchannels".to_string());
+        self
+    }
+
+    /// Allow commands of the category, i.e. `read` or `keyspace`.
+    pub fn allow_category<U: AsRef<str>>(mut self, category: U) -> Self {
+        self.push(format!("+@{}", category.as_ref()));
+        self
+    }
+
+    /// Deny commands of the category.
+    pub fn deny_category<U: AsRef<str>>(mut self, category: U) -> Self {
+        self.push(format!("-@{}", category.as_ref()));
+        self
+    }
+
+    /// Allow the command or subcommand, i.e. `get` or `config|get`.
+    pub fn allow_command<U: AsRef<str>>(mut self, command: U) -> Self {
+        self.push(format!("+{}", command.as_ref()));
+        self
+    }
+
+    /// Deny the command or subcommand.
+    pub fn deny_command<U: AsRef<str>>(mut self, command: U) -> Self {
+        self.push(format!("-{}", command.as_ref()));
+        self
+    }
+
+    /// Allow all commands.
+    pub fn all_commands(mut self) -> Self {
+        self.push("allcommands".to_string());
+        self
+    }
+
+    /// Deny all commands.
+    pub fn no_commands(mut self) -> Self {
+        self.push("nocommands".to_string());
+        self
+    }
+}
+
+/// ACL SETUSER redis command
+///
+/// Creates the user or modifies rules of the existing user.
+///
+/// ```rust,no_run
+/// use mco_redis::cmd;
+/// use mco_redis::connector::RedisConnector;
+/// use mco_redis::cmd::commands::AclSelector;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // requires real redis server, ACL commands are not implemented by `MockServer`
+///     let redis = RedisConnector::new("127.0.0.1:6379").connect()?;
+///
+///     // tenant user with access to own keys only
+///     redis.exec(
+///         cmd::AclSetUser("tenant-1")
+///             .reset()
+///             .on()
+///             .password("secret")
+///             .key_pattern("tenant-1:*")
+///             .allow_category("read")
+///             .allow_category("write")
+///             .deny_category("dangerous")
+///             .selector(AclSelector::new().read_key_pattern("shared:*").allow_command("get"))
+///     )?;
+///
+///     let user = redis.exec(cmd::AclGetUser("tenant-1"))?.unwrap();
+///     assert!(user.flags.iter().any(|f| f == "on"));
+///     assert_eq!(user.keys, "~tenant-1:*");
+///
+///     redis.exec(cmd::AclDelUser("tenant-1"))?;
+///     Ok(())
+/// }
+/// ```
+pub fn AclSetUser<T>(username: T) -> AclSetUserCommand
+where
+    BulkString: From<T>,
+{
+    AclRules {
+        target: username.into(),
+        rules: Vec::new(),
+    }
+}
+
+pub type AclSetUserCommand = AclRules<BulkString>;
+
+impl AclSetUserCommand {
+    /// Reset user to the initial state, with no permissions.
+    pub fn reset(mut self) -> Self {
+        self.push("reset".to_string());
+        self
+    }
+
+    /// Enable the user.
+    pub fn on(mut self) -> Self {
+        self.push("on".to_string());
+        self
+    }
+
+    /// Disable the user.
+    pub fn off(mut self) -> Self {
+        self.push("off".to_string());
+        self
+    }
+
+    /// Add the password.
+    pub fn password<T: AsRef<str>>(mut self, password: T) -> Self {
+        self.push(format!(">{}", password.as_ref()));
+        self
+    }
+
+    /// Remove the password.
+    pub fn remove_password<T: AsRef<str>>(mut self, password: T) -> Self {
+        self.push(format!("<{}", password.as_ref()));
+        self
+    }
+
+    /// Add SHA-256 hash of the password, in hex.
+    pub fn password_hash<T: AsRef<str>>(mut self, hash: T) -> Self {
+        self.push(format!("#{}", hash.as_ref()));
+        self
+    }
+
+    /// Remove SHA-256 hash of the password.
+    pub fn remove_password_hash<T: AsRef<str>>(mut self, hash: T) -> Self {
+        self.push(format!("!{}", hash.as_ref()));
+        self
+    }
+
+    /// Allow any password.
+    pub fn no_pass(mut self) -> Self {
+        self.push("nopass".to_string());
+        self
+    }
+
+    /// Remove all passwords.
+    pub fn reset_pass(mut self) -> Self {
+        self.push("resetpass".to_string());
+        self
+    }
+
+    /// Remove all key patterns.
+    pub fn reset_keys(mut self) -> Self {
+        self.push("resetkeys".to_string());
+        self
+    }
+
+    /// Remove all channel patterns.
+    pub fn reset_channels(mut self) -> Self {
+        self.push("resetchannels".to_string());
+        self
+    }
+
+    /// Add the selector, access is granted if root permissions or any
+    /// of the selectors match the command.
+    pub fn selector(mut self, selector: AclSelector) -> Self {
+        let rules: Vec<_> = selector.rules.iter().map(|rule| quote_rule(rule)).collect();
+        self.push(format!("({})", rules.join(" ")));
+        self
+    }
+
+    /// Remove all selectors.
+    pub fn clear_selectors(mut self) -> Self {
+        self.push("clearselectors".to_string());
+        self
+    }
+}
+
+impl Command for AclSetUserCommand {
+    type Output = ();
+
+    fn to_request(self) -> Request {
+        let mut req = vec![
+            Request::from_static("ACL"),
+            Request::from_static("SETUSER"),
+            Request::BulkString(self.target),
+        ];
+        req.extend(self.rules.into_iter().map(|rule| Request::BulkString(rule.into())));
+        Request::Array(req)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        utils::OkOutputCommand::to_output(val)
+    }
+}
+
+/// Additional set of permissions of the user
+pub type AclSelector = AclRules<()>;
+
+impl AclSelector {
+    /// Create empty selector
+    pub fn new() -> Self {
+        AclSelector::default()
+    }
+}
+
+/// Quote selector rule, server splits selector into rules the same way
+/// as `redis-cli` splits command arguments
+fn quote_rule(rule: &str) -> String {
+    let mut quoted = String::with_capacity(rule.len() + 2);
+    quoted.push('"');
+    for ch in rule.chars() {
+        if ch == '"' || ch == '\\' {
+            quoted.push('\\');
+        }
+        quoted.push(ch);
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// Selector of the `ACL GETUSER` command output
+pub struct AclSelectorInfo {
+    pub commands: ByteString,
+    pub keys: ByteString,
+    pub channels: ByteString,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// Output of the `ACL GETUSER` command
+pub struct AclUser {
+    /// User flags, i.e. `on`, `off` or `nopass`
+    pub flags: Vec<ByteString>,
+    /// SHA-256 hashes of the passwords
+    pub passwords: Vec<ByteString>,
+    /// Command rules, i.e. `+@all -debug`
+    pub commands: ByteString,
+    /// Key patterns, i.e. `~app:* %R~shared:*`
+    pub keys: ByteString,
+    /// Channel patterns
+    pub channels: ByteString,
+    pub selectors: Vec<AclSelectorInfo>,
+}
+
+/// Rules are returned as a string since redis 7.0 and as an array before
+fn rules(val: Response) -> Result<ByteString, CommandError> {
+    match val {
+        Response::Array(_) => {
+            let items = Vec::<ByteString>::try_from(val)?;
+            Ok(items
+                .iter()
+                .map(|s| s.as_ref())
+                .collect::<Vec<_>>()
+                .join(" ")
+                .into())
+        }
+        val => Ok(ByteString::try_from(val)?),
+    }
+}
+
+fn parse_selector(val: Response) -> Result<AclSelectorInfo, CommandError> {
+    let mut selector = AclSelectorInfo::default();
+    for (name, value) in utils::map_entries(val)? {
+        match name.as_ref() {
+            "commands" => selector.commands = rules(value)?,
+            "keys" => selector.keys = rules(value)?,
+            "channels" => selector.channels = rules(value)?,
+            _ => (),
+        }
+    }
+    Ok(selector)
+}
+
+/// ACL GETUSER redis command
+///
+/// Returns rules of the user, `None` is returned if user does not exist.
+pub fn AclGetUser<T>(username: T) -> AclGetUserCommand
+where
+    BulkString: From<T>,
+{
+    AclGetUserCommand(Request::Array(vec![
+        Request::from_static("ACL"),
+        Request::from_static("GETUSER"),
+        Request::BulkString(username.into()),
+    ]))
+}
+
+pub struct AclGetUserCommand(Request);
+
+impl Command for AclGetUserCommand {
+    type Output = Option<AclUser>;
+
+    fn to_request(self) -> Request {
+        self.0
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        if let Response::Nil = val {
+            return Ok(None);
+        }
+
+        let mut user = AclUser::default();
+        for (name, value) in utils::map_entries(val)? {
+            match name.as_ref() {
+                "flags" => user.flags = Vec::try_from(value)?,
+                "passwords" => user.passwords = Vec::try_from(value)?,
+                "commands" => user.commands = rules(value)?,
+                "keys" => user.keys = rules(value)?,
+                "channels" => user.channels = rules(value)?,
+                "selectors" => match value {
+                    Response::Array(items) => {
+                        for item in items {
+                            user.selectors.push(parse_selector(item)?);
+                        }
+                    }
+                    _ => return Err(CommandError::Output("Cannot parse selectors", value)),
+                },
+                _ => (),
+            }
+        }
+        Ok(Some(user))
+    }
+}
+
+/// ACL DELUSER redis command
+///
+/// Deletes the users and terminates their connections. Returns the number
+/// of deleted users.
+pub fn AclDelUser<T>(username: T) -> AclDelUserCommand
+where
+    BulkString: From<T>,
+{
+    AclDelUserCommand(vec![
+        Request::from_static("ACL"),
+        Request::from_static("DELUSER"),
+        Request::BulkString(username.into()),
+    ])
+}
+
+pub struct AclDelUserCommand(Vec<Request>);
+
+impl AclDelUserCommand {
+    /// Add a user to this command.
+    pub fn user<T>(mut self, username: T) -> Self
+    where
+        BulkString: From<T>,
+    {
+        self.0.push(username.into());
+        self
+    }
+
+    /// Add more users to this command.
+    pub fn users<T>(mut self, other: impl IntoIterator<Item = T>) -> Self
+    where
+        BulkString: From<T>,
+    {
+        self.0.extend(other.into_iter().map(|t| t.into()));
+        self
+    }
+}
+
+impl Command for AclDelUserCommand {
+    type Output = usize;
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(usize::try_from(val)?)
+    }
+}
+
+pub struct AclStringsCommand(Vec<Request>);
+
+impl Command for AclStringsCommand {
+    type Output = Vec<ByteString>;
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(Vec::try_from(val)?)
+    }
+}
+
+/// ACL LIST redis command
+///
+/// Returns rules of all users in the acl file format.
+pub fn AclList() -> AclStringsCommand {
+    AclStringsCommand(vec![
+        Request::from_static("ACL"),
+        Request::from_static("LIST"),
+    ])
+}
+
+/// ACL USERS redis command
+///
+/// Returns names of all users.
+pub fn AclUsers() -> AclStringsCommand {
+    AclStringsCommand(vec![
+        Request::from_static("ACL"),
+        Request::from_static("USERS"),
+    ])
+}
+
+/// ACL CAT redis command
+///
+/// Returns command categories, or commands of the category if it is
+/// specified.
+pub fn AclCat() -> AclCatCommand {
+    AclCatCommand(vec![
+        Request::from_static("ACL"),
+        Request::from_static("CAT"),
+    ])
+}
+
+pub struct AclCatCommand(Vec<Request>);
+
+impl AclCatCommand {
+    /// Return commands of the category.
+    pub fn category<T>(mut self, category: T) -> AclStringsCommand
+    where
+        BulkString: From<T>,
+    {
+        self.0.push(category.into());
+        AclStringsCommand(self.0)
+    }
+}
+
+impl Command for AclCatCommand {
+    type Output = Vec<ByteString>;
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(Vec::try_from(val)?)
+    }
+}
+
+pub struct AclStringCommand(Vec<Request>);
+
+impl Command for AclStringCommand {
+    type Output = ByteString;
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(ByteString::try_from(val)?)
+    }
+}
+
+/// ACL WHOAMI redis command
+///
+/// Returns the user of the current connection.
+pub fn AclWhoAmI() -> AclStringCommand {
+    AclStringCommand(vec![
+        Request::from_static("ACL"),
+        Request::from_static("WHOAMI"),
+    ])
+}
+
+/// ACL GENPASS redis command
+///
+/// Returns random hex string generated by server, 256 bits by default.
+pub fn AclGenPass() -> AclGenPassCommand {
+    AclGenPassCommand(vec![
+        Request::from_static("ACL"),
+        Request::from_static("GENPASS"),
+    ])
+}
+
+pub struct AclGenPassCommand(Vec<Request>);
+
+impl AclGenPassCommand {
+    /// Number of random bits.
+    pub fn bits(mut self, bits: u32) -> AclStringCommand {
+        self.0.push(Request::BulkInteger(bits as i64));
+        AclStringCommand(self.0)
+    }
+}
+
+impl Command for AclGenPassCommand {
+    type Output = ByteString;
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(ByteString::try_from(val)?)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+/// Entry of the `ACL LOG` command output
+pub struct AclLogEntry {
+    /// Number of similar events within 60 seconds
+    pub count: u64,
+    /// `command`, `key`, `channel` or `auth`
+    pub reason: ByteString,
+    /// `toplevel`, `multi`, `lua` or `module`
+    pub context: ByteString,
+    /// Denied command, key or channel
+    pub object: ByteString,
+    pub username: ByteString,
+    pub age_seconds: f64,
+    /// Client connection details in `CLIENT LIST` format
+    pub client_info: ByteString,
+    pub entry_id: u64,
+    /// Unix timestamp in milliseconds
+    pub timestamp_created: u64,
+    /// Unix timestamp in milliseconds
+    pub timestamp_last_updated: u64,
+}
+
+/// ACL LOG redis command
+///
+/// Returns recent security events, most recent events first.
+pub fn AclLog() -> AclLogCommand {
+    AclLogCommand(vec![
+        Request::from_static("ACL"),
+        Request::from_static("LOG"),
+    ])
+}
+
+pub struct AclLogCommand(Vec<Request>);
+
+impl AclLogCommand {
+    /// Number of returned entries, default is 10.
+    pub fn count(mut self, count: usize) -> Self {
+        self.0.truncate(2);
+        self.0.push(Request::BulkInteger(count as i64));
+        self
+    }
+
+    /// Clear the log.
+    pub fn reset(mut self) -> utils::OkOutputCommand {
+        self.0.truncate(2);
+        self.0.push(Request::from_bstatic(b"RESET"));
+        utils::OkOutputCommand(Request::Array(self.0))
+    }
+}
+
+impl Command for AclLogCommand {
+    type Output = Vec<AclLogEntry>;
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        let items = match val {
+            Response::Array(items) => items,
+            _ => return Err(CommandError::Output("Cannot parse acl log", val)),
+        };
+
+        let mut entries = Vec::with_capacity(items.len());
+        for item in items {
+            let mut entry = AclLogEntry::default();
+            for (name, value) in utils::map_entries(item)? {
+                match name.as_ref() {
+                    "count" => entry.count = u64::try_from(value)?,
+                    "reason" => entry.reason = ByteString::try_from(value)?,
+                    "context" => entry.context = ByteString::try_from(value)?,
+                    "object" => entry.object = ByteString::try_from(value)?,
+                    "username" => entry.username = ByteString::try_from(value)?,
+                    "age-seconds" => entry.age_seconds = f64::try_from(value)?,
+                    "client-info" => entry.client_info = ByteString::try_from(value)?,
+                    "entry-id" => entry.entry_id = u64::try_from(value)?,
+                    "timestamp-created" => entry.timestamp_created = u64::try_from(value)?,
+                    "timestamp-last-updated" => {
+                        entry.timestamp_last_updated = u64::try_from(value)?
+                    }
+                    _ => (),
+                }
+            }
+            entries.push(entry);
+        }
+        Ok(entries)
+    }
+}
+
+/// ACL DRYRUN redis command
+///
+/// Simulates execution of the command by the user without executing it.
+/// Returns `Err` with the reason if the command is not permitted.
+pub fn AclDryRun<T, C>(username: T, command: C) -> AclDryRunCommand
+where
+    BulkString: From<T> + From<C>,
+{
+    AclDryRunCommand(vec![
+        Request::from_static("ACL"),
+        Request::from_static("DRYRUN"),
+        Request::BulkString(username.into()),
+        Request::BulkString(command.into()),
+    ])
+}
+
+pub struct AclDryRunCommand(Vec<Request>);
+
+impl AclDryRunCommand {
+    /// Add an argument of the command.
+    pub fn arg<T>(mut self, arg: T) -> Self
+    where
+        BulkString: From<T>,
+    {
+        self.0.push(arg.into());
+        self
+    }
+
+    /// Add more arguments of the command.
+    pub fn args<T>(mut self, other: impl IntoIterator<Item = T>) -> Self
+    where
+        BulkString: From<T>,
+    {
+        self.0.extend(other.into_iter().map(|t| t.into()));
+        self
+    }
+}
+
+impl Command for AclDryRunCommand {
+    type Output = Result<(), ByteString>;
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        match val {
+            Response::String(ref s) if s == "OK" => Ok(Ok(())),
+            val => Ok(Err(ByteString::try_from(val)?)),
+        }
+    }
+}
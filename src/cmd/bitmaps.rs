@@ -0,0 +1,399 @@
+use std::convert::TryFrom;
+
+use super::{utils, Command, CommandError};
+use crate::codec_redis::{BulkString, Request, Response};
+
+/// SETBIT redis command
+///
+/// Sets or clears the bit at offset in the string value stored at key.
+/// Returns the original bit value.
+///
+/// ```rust,no_run
+/// use mco_redis::cmd;
+/// use mco_redis::connector::RedisConnector;
+/// # use rand::{thread_rng, Rng, distributions::Alphanumeric};
+/// # fn gen_random_key() -> String {
+/// #    thread_rng().sample_iter(&Alphanumeric).take(12).map(char::from).collect::<String>()
+/// # }
+///
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // requires real redis server, bitmap commands are not implemented by `MockServer`
+///     let redis = RedisConnector::new("127.0.0.1:6379").connect()?;
+///     let key = gen_random_key();
+///
+///     // enable feature flags #3 and #10
+///     redis.exec(cmd::SetBit(&key, 3, true))?;
+///     redis.exec(cmd::SetBit(&key, 10, true))?;
+///
+///     assert!(redis.exec(cmd::GetBit(&key, 10))?);
+///     assert_eq!(redis.exec(cmd::BitCount(&key))?, 2);
+///     assert_eq!(redis.exec(cmd::BitPos(&key, true))?, Some(3));
+///     Ok(())
+/// }
+/// ```
+pub fn SetBit<T>(key: T, offset: u64, value: bool) -> utils::BoolOutputCommand
+where
+    BulkString: From<T>,
+{
+    utils::BoolOutputCommand(Request::Array(vec![
+        Request::from_static("SETBIT"),
+        Request::BulkString(key.into()),
+        Request::BulkInteger(offset as i64),
+        Request::BulkInteger(value as i64),
+    ]))
+}
+
+/// GETBIT redis command
+///
+/// Returns the bit value at offset in the string value stored at key.
+pub fn GetBit<T>(key: T, offset: u64) -> utils::BoolOutputCommand
+where
+    BulkString: From<T>,
+{
+    utils::BoolOutputCommand(Request::Array(vec![
+        Request::from_static("GETBIT"),
+        Request::BulkString(key.into()),
+        Request::BulkInteger(offset as i64),
+    ]))
+}
+
+/// BITCOUNT redis command
+///
+/// Counts the number of set bits in the string value stored at key.
+pub fn BitCount<T>(key: T) -> BitCountCommand
+where
+    BulkString: From<T>,
+{
+    BitCountCommand(vec![
+        Request::from_static("BITCOUNT"),
+        Request::BulkString(key.into()),
+    ])
+}
+
+pub struct BitCountCommand(Vec<Request>);
+
+impl BitCountCommand {
+    /// Count bits within the byte range, negative values are offsets
+    /// from the end of the string.
+    pub fn range(mut self, start: i64, end: i64) -> Self {
+        self.0.push(Request::BulkInteger(start));
+        self.0.push(Request::BulkInteger(end));
+        self.0.push(Request::from_bstatic(b"BYTE"));
+        self
+    }
+
+    /// Count bits within the bit range, negative values are offsets
+    /// from the end of the string.
+    pub fn range_bits(mut self, start: i64, end: i64) -> Self {
+        self.0.push(Request::BulkInteger(start));
+        self.0.push(Request::BulkInteger(end));
+        self.0.push(Request::from_bstatic(b"BIT"));
+        self
+    }
+}
+
+impl Command for BitCountCommand {
+    type Output = usize;
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(usize::try_from(val)?)
+    }
+}
+
+/// BITPOS redis command
+///
+/// Returns the position of the first bit set to 1 or 0 in a string,
+/// `None` is returned if there is no such bit.
+pub fn BitPos<T>(key: T, bit: bool) -> BitPosCommand
+where
+    BulkString: From<T>,
+{
+    BitPosCommand(vec![
+        Request::from_static("BITPOS"),
+        Request::BulkString(key.into()),
+        Request::BulkInteger(bit as i64),
+    ])
+}
+
+pub struct BitPosCommand(Vec<Request>);
+
+impl BitPosCommand {
+    /// Search starting from the byte offset.
+    pub fn start(mut self, start: i64) -> Self {
+        self.0.truncate(3);
+        self.0.push(Request::BulkInteger(start));
+        self
+    }
+
+    /// Search within the byte range, negative values are offsets
+    /// from the end of the string.
+    pub fn range(mut self, start: i64, end: i64) -> Self {
+        self.0.truncate(3);
+        self.0.push(Request::BulkInteger(start));
+        self.0.push(Request::BulkInteger(end));
+        self.0.push(Request::from_bstatic(b"BYTE"));
+        self
+    }
+
+    /// Search within the bit range, negative values are offsets
+    /// from the end of the string.
+    pub fn range_bits(mut self, start: i64, end: i64) -> Self {
+        self.0.truncate(3);
+        self.0.push(Request::BulkInteger(start));
+        self.0.push(Request::BulkInteger(end));
+        self.0.push(Request::from_bstatic(b"BIT"));
+        self
+    }
+}
+
+impl Command for BitPosCommand {
+    type Output = Option<u64>;
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        match val {
+            Response::Integer(-1) => Ok(None),
+            Response::Integer(pos) if pos >= 0 => Ok(Some(pos as u64)),
+            _ => Err(CommandError::Output("Cannot parse response", val)),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// Bitwise operation of `BITOP` command
+pub enum BitOperation {
+    And,
+    Or,
+    Xor,
+    /// Inverts bits of the single source key
+    Not,
+}
+
+/// BITOP redis command
+///
+/// Performs a bitwise operation between source keys and stores the result
+/// in the `destination` key. Returns the size of the stored string.
+pub fn BitOp<T, S>(op: BitOperation, destination: T, source: S) -> BitOpCommand
+where
+    BulkString: From<T> + From<S>,
+{
+    let op = match op {
+        BitOperation::And => "AND",
+        BitOperation::Or => "OR",
+        BitOperation::Xor => "XOR",
+        BitOperation::Not => "NOT",
+    };
+    BitOpCommand(vec![
+        Request::from_static("BITOP"),
+        Request::from_static(op),
+        Request::BulkString(destination.into()),
+        Request::BulkString(source.into()),
+    ])
+}
+
+pub struct BitOpCommand(Vec<Request>);
+
+impl BitOpCommand {
+    /// Add a source key to this command.
+    pub fn key<T>(mut self, other: T) -> Self
+    where
+        BulkString: From<T>,
+    {
+        self.0.push(other.into());
+        self
+    }
+
+    /// Add more source keys to this command.
+    pub fn keys<T>(mut self, other: impl IntoIterator<Item = T>) -> Self
+    where
+        BulkString: From<T>,
+    {
+        self.0.extend(other.into_iter().map(|t| t.into()));
+        self
+    }
+}
+
+impl Command for BitOpCommand {
+    type Output = usize;
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(usize::try_from(val)?)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// Integer type of the `BITFIELD` sub-command
+pub enum BitFieldType {
+    /// Signed integer of the given width, up to 64 bits
+    Signed(u8),
+    /// Unsigned integer of the given width, up to 63 bits
+    Unsigned(u8),
+}
+
+impl BitFieldType {
+    fn to_request(self) -> Request {
+        let tp = match self {
+            BitFieldType::Signed(bits) => format!("i{}", bits),
+            BitFieldType::Unsigned(bits) => format!("u{}", bits),
+        };
+        Request::BulkString(tp.into())
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// Overflow behavior of `SET` and `INCRBY` sub-commands of `BITFIELD`
+pub enum BitFieldOverflow {
+    /// Wrap around, default behavior
+    Wrap,
+    /// Saturate to the minimum or maximum value
+    Sat,
+    /// Do nothing and return `None`
+    Fail,
+}
+
+/// BITFIELD redis command
+///
+/// Treats string value stored at key as an array of bits and performs
+/// sequence of integer operations on it. Returns a value for each
+/// `GET`, `SET` and `INCRBY` sub-command, `None` is returned for operations
+/// failed with `BitFieldOverflow::Fail` policy.
+///
+/// ```rust,no_run
+/// use mco_redis::cmd;
+/// use mco_redis::connector::RedisConnector;
+/// use mco_redis::cmd::commands::{BitFieldOverflow, BitFieldType};
+/// # use rand::{thread_rng, Rng, distributions::Alphanumeric};
+/// # fn gen_random_key() -> String {
+/// #    thread_rng().sample_iter(&Alphanumeric).take(12).map(char::from).collect::<String>()
+/// # }
+///
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // requires real redis server, BITFIELD is not implemented by `MockServer`
+///     let redis = RedisConnector::new("127.0.0.1:6379").connect()?;
+///     let key = gen_random_key();
+///
+///     let result = redis.exec(
+///         cmd::BitField(&key)
+///             .set(BitFieldType::Unsigned(8), 0, 200)
+///             .overflow(BitFieldOverflow::Fail)
+///             .incr_by(BitFieldType::Unsigned(8), 0, 100)
+///             .get(BitFieldType::Unsigned(8), 0)
+///     )?;
+///
+///     assert_eq!(result, vec![Some(0), None, Some(200)]);
+///     Ok(())
+/// }
+/// ```
+pub fn BitField<T>(key: T) -> BitFieldCommand
+where
+    BulkString: From<T>,
+{
+    BitFieldCommand(vec![
+        Request::from_static("BITFIELD"),
+        Request::BulkString(key.into()),
+    ])
+}
+
+pub struct BitFieldCommand(Vec<Request>);
+
+impl BitFieldCommand {
+    /// Get the integer at the bit offset.
+    pub fn get(mut self, tp: BitFieldType, offset: u64) -> Self {
+        self.0.push(Request::from_bstatic(b"GET"));
+        self.0.push(tp.to_request());
+        self.0.push(Request::BulkInteger(offset as i64));
+        self
+    }
+
+    /// Set the integer at the bit offset, old value is returned.
+    pub fn set(mut self, tp: BitFieldType, offset: u64, value: i64) -> Self {
+        self.0.push(Request::from_bstatic(b"SET"));
+        self.0.push(tp.to_request());
+        self.0.push(Request::BulkInteger(offset as i64));
+        self.0.push(Request::BulkInteger(value));
+        self
+    }
+
+    /// Increment the integer at the bit offset, new value is returned.
+    pub fn incr_by(mut self, tp: BitFieldType, offset: u64, increment: i64) -> Self {
+        self.0.push(Request::from_bstatic(b"INCRBY"));
+        self.0.push(tp.to_request());
+        self.0.push(Request::BulkInteger(offset as i64));
+        self.0.push(Request::BulkInteger(increment));
+        self
+    }
+
+    /// Set overflow behavior for following `set` and `incr_by` operations.
+    pub fn overflow(mut self, overflow: BitFieldOverflow) -> Self {
+        self.0.push(Request::from_bstatic(b"OVERFLOW"));
+        self.0.push(match overflow {
+            BitFieldOverflow::Wrap => Request::from_bstatic(b"WRAP"),
+            BitFieldOverflow::Sat => Request::from_bstatic(b"SAT"),
+            BitFieldOverflow::Fail => Request::from_bstatic(b"FAIL"),
+        });
+        self
+    }
+}
+
+impl Command for BitFieldCommand {
+    type Output = Vec<Option<i64>>;
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(Vec::try_from(val)?)
+    }
+}
+
+/// BITFIELD_RO redis command
+///
+/// Read-only variant of the `BITFIELD` command, only `GET` sub-command
+/// is supported.
+pub fn BitFieldRo<T>(key: T) -> BitFieldRoCommand
+where
+    BulkString: From<T>,
+{
+    BitFieldRoCommand(vec![
+        Request::from_static("BITFIELD_RO"),
+        Request::BulkString(key.into()),
+    ])
+}
+
+pub struct BitFieldRoCommand(Vec<Request>);
+
+impl BitFieldRoCommand {
+    /// Get the integer at the bit offset.
+    pub fn get(mut self, tp: BitFieldType, offset: u64) -> Self {
+        self.0.push(Request::from_bstatic(b"GET"));
+        self.0.push(tp.to_request());
+        self.0.push(Request::BulkInteger(offset as i64));
+        self
+    }
+}
+
+impl Command for BitFieldRoCommand {
+    type Output = Vec<i64>;
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(Vec::try_from(val)?)
+    }
+}
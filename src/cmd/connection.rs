@@ -1,6 +1,11 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::str;
+use std::time::Duration;
 
-use super::{Command, CommandError};
-use crate::codec_redis::{Request, Response};
+use super::server::parse_field;
+use super::{utils, Command, CommandError, NoReplyCommand};
+use crate::codec_redis::{BulkString, Request, Response};
 
 /// SELECT redis command
 ///
@@ -8,10 +13,12 @@ use crate::codec_redis::{Request, Response};
 /// numeric index.
 ///
 /// ```rust
-/// use mco_redis::{cmd, RedisConnector};
+/// use mco_redis::cmd;
+/// use mco_redis::testing::MockServer;
 ///
 ///  fn main() -> Result<(), Box<dyn std::error::Error>> {
-///     let redis = RedisConnector::new("127.0.0.1:6379").connect()?;
+///     let server = MockServer::start()?;
+///     let redis = server.connector().connect()?;
 ///
 ///     // select db for current connection
 ///     let success = redis.exec(cmd::Select(1))?;
@@ -51,10 +58,12 @@ impl Command for SelectCommand {
 /// or to measure latency.
 ///
 /// ```rust
-/// use mco_redis::{cmd, RedisConnector};
+/// use mco_redis::cmd;
+/// use mco_redis::testing::MockServer;
 ///
 /// fn main() -> Result<(), Box<dyn std::error::Error>> {
-///     let redis = RedisConnector::new("127.0.0.1:6379").connect()?;
+///     let server = MockServer::start()?;
+///     let redis = server.connector().connect()?;
 ///
 ///     // ping connection
 ///     let response = redis.exec(cmd::Ping())?;
@@ -80,8 +89,531 @@ impl Command for PingCommand {
     fn to_output(val: Response) -> Result<Self::Output, CommandError> {
         match val {
             Response::String(val) => Ok(val.to_string()),
-            Response::Error(val) => Err(CommandError::Error(val)),
+            Response::Error(val) => Err(CommandError::Error(val.into())),
             _ => Err(CommandError::Output("Unknown response", val)),
         }
     }
 }
+
+/// CLIENT ID redis command
+///
+/// Returns the id of the current connection.
+pub fn ClientId() -> utils::IntOutputCommand {
+    utils::IntOutputCommand(Request::Array(vec![
+        Request::from_static("CLIENT"),
+        Request::from_static("ID"),
+    ]))
+}
+
+/// CLIENT SETNAME redis command
+///
+/// Assigns a name to the current connection, the name is displayed in
+/// the output of `CLIENT LIST`.
+///
+/// ```rust,no_run
+/// use mco_redis::cmd;
+/// use mco_redis::connector::RedisConnector;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // requires real redis server, CLIENT INFO and CLIENT LIST are not implemented by `MockServer`
+///     let redis = RedisConnector::new("127.0.0.1:6379").connect_simple()?;
+///
+///     redis.exec(cmd::ClientSetName("billing-worker"))?;
+///
+///     let info = redis.exec(cmd::ClientInfo())?;
+///     assert_eq!(info.name, "billing-worker");
+///     assert_eq!(info.id, redis.exec(cmd::ClientId())?);
+///
+///     // find all connections of the service
+///     let clients = redis.exec(cmd::ClientList())?;
+///     assert!(clients.iter().any(|c| c.name == "billing-worker"));
+///     Ok(())
+/// }
+/// ```
+pub fn ClientSetName<T>(name: T) -> utils::OkOutputCommand
+where
+    BulkString: From<T>,
+{
+    utils::OkOutputCommand(Request::Array(vec![
+        Request::from_static("CLIENT"),
+        Request::from_static("SETNAME"),
+        Request::BulkString(name.into()),
+    ]))
+}
+
+/// CLIENT GETNAME redis command
+///
+/// Returns the name of the current connection, `None` is returned if
+/// name is not set.
+pub fn ClientGetName() -> utils::BulkOutputCommand {
+    utils::BulkOutputCommand(Request::Array(vec![
+        Request::from_static("CLIENT"),
+        Request::from_static("GETNAME"),
+    ]))
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// Type of the client connection
+pub enum ClientType {
+    Normal,
+    Master,
+    Replica,
+    PubSub,
+}
+
+impl ClientType {
+    fn to_request(self) -> Request {
+        match self {
+            ClientType::Normal => Request::from_static("NORMAL"),
+            ClientType::Master => Request::from_static("MASTER"),
+            ClientType::Replica => Request::from_static("REPLICA"),
+            ClientType::PubSub => Request::from_static("PUBSUB"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// Client connection details of `CLIENT LIST` and `CLIENT INFO` commands
+///
+/// Fields that are not covered by the struct are available with
+/// `ConnectionInfo::get()`.
+pub struct ConnectionInfo {
+    pub id: i64,
+    /// Address and port of the client
+    pub addr: String,
+    /// Address and port of the server the client connected to
+    pub laddr: String,
+    pub fd: i64,
+    pub name: String,
+    /// Connection age in seconds
+    pub age: u64,
+    /// Idle time in seconds
+    pub idle: u64,
+    pub flags: String,
+    pub db: u32,
+    /// Number of channel subscriptions
+    pub sub: u64,
+    /// Number of pattern subscriptions
+    pub psub: u64,
+    /// Number of commands in `MULTI` context, -1 if not in transaction
+    pub multi: i64,
+    /// Last executed command
+    pub cmd: String,
+    pub user: String,
+    /// Protocol version
+    pub resp: u32,
+    pub lib_name: String,
+    pub lib_ver: String,
+    fields: HashMap<String, String>,
+}
+
+impl ConnectionInfo {
+    /// Parse single line of `CLIENT LIST` command output
+    ///
+    /// Malformed values of typed fields are reported as
+    /// `CommandError::Decode` error.
+    pub fn parse(line: &str) -> Result<Self, CommandError> {
+        ConnectionInfo::parse_line(line)
+            .map_err(|msg| CommandError::Decode(msg, Response::String(line.into())))
+    }
+
+    fn parse_line(line: &str) -> Result<Self, String> {
+        let mut info = ConnectionInfo::default();
+        for item in line.split_whitespace() {
+            let (name, value) = match item.split_once('=') {
+                Some(item) => item,
+                None => continue,
+            };
+            match name {
+                "id" => info.id = parse_field(name, value)?,
+                "addr" => info.addr = parse_field(name, value)?,
+                "laddr" => info.laddr = parse_field(name, value)?,
+                "fd" => info.fd = parse_field(name, value)?,
+                "name" => info.name = parse_field(name, value)?,
+                "age" => info.age = parse_field(name, value)?,
+                "idle" => info.idle = parse_field(name, value)?,
+                "flags" => info.flags = parse_field(name, value)?,
+                "db" => info.db = parse_field(name, value)?,
+                "sub" => info.sub = parse_field(name, value)?,
+                "psub" => info.psub = parse_field(name, value)?,
+                "multi" => info.multi = parse_field(name, value)?,
+                "cmd" => info.cmd = parse_field(name, value)?,
+                "user" => info.user = parse_field(name, value)?,
+                "resp" => info.resp = parse_field(name, value)?,
+                "lib-name" => info.lib_name = parse_field(name, value)?,
+                "lib-ver" => info.lib_ver = parse_field(name, value)?,
+                _ => (),
+            }
+            info.fields.insert(name.to_string(), value.to_string());
+        }
+        Ok(info)
+    }
+
+    /// Get raw value of the field
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.fields.get(name).map(|s| s.as_str())
+    }
+}
+
+fn parse_client_list(val: Response) -> Result<Vec<ConnectionInfo>, CommandError> {
+    let text = match val {
+        Response::Bytes(ref bytes) => match str::from_utf8(bytes) {
+            Ok(text) => text,
+            Err(_) => {
+                return Err(CommandError::Output(
+                    "Client list is not a valid utf-8",
+                    val,
+                ))
+            }
+        },
+        Response::String(ref text) => text.as_ref(),
+        _ => return Err(CommandError::Output("Cannot parse client list", val)),
+    };
+    let result: Result<Vec<_>, _> = text
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(ConnectionInfo::parse_line)
+        .collect();
+    result.map_err(|msg| CommandError::Decode(msg, val))
+}
+
+/// CLIENT LIST redis command
+///
+/// Returns information about client connections.
+pub fn ClientList() -> ClientListCommand {
+    ClientListCommand(vec![
+        Request::from_static("CLIENT"),
+        Request::from_static("LIST"),
+    ])
+}
+
+pub struct ClientListCommand(Vec<Request>);
+
+impl ClientListCommand {
+    /// Return connections of the specific type only.
+    pub fn client_type(mut self, tp: ClientType) -> Self {
+        self.0.push(Request::from_bstatic(b"TYPE"));
+        self.0.push(tp.to_request());
+        self
+    }
+
+    /// Return connections with specified ids only.
+    pub fn ids(mut self, ids: impl IntoIterator<Item = i64>) -> Self {
+        self.0.push(Request::from_bstatic(b"ID"));
+        self.0.extend(ids.into_iter().map(Request::BulkInteger));
+        self
+    }
+}
+
+impl Command for ClientListCommand {
+    type Output = Vec<ConnectionInfo>;
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        parse_client_list(val)
+    }
+}
+
+/// CLIENT INFO redis command
+///
+/// Returns information about the current connection.
+pub fn ClientInfo() -> ClientInfoCommand {
+    ClientInfoCommand(Request::Array(vec![
+        Request::from_static("CLIENT"),
+        Request::from_static("INFO"),
+    ]))
+}
+
+pub struct ClientInfoCommand(Request);
+
+impl Command for ClientInfoCommand {
+    type Output = ConnectionInfo;
+
+    fn to_request(self) -> Request {
+        self.0
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(parse_client_list(val)?.pop().unwrap_or_default())
+    }
+}
+
+/// CLIENT KILL redis command
+///
+/// Closes client connections that match all specified filters. Returns
+/// the number of killed connections.
+pub fn ClientKill() -> ClientKillCommand {
+    ClientKillCommand(vec![
+        Request::from_static("CLIENT"),
+        Request::from_static("KILL"),
+    ])
+}
+
+pub struct ClientKillCommand(Vec<Request>);
+
+impl ClientKillCommand {
+    /// Kill connection with the id.
+    pub fn id(mut self, id: i64) -> Self {
+        self.0.push(Request::from_bstatic(b"ID"));
+        self.0.push(Request::BulkInteger(id));
+        self
+    }
+
+    /// Kill connection with the client address, `ip:port`.
+    pub fn addr<T>(mut self, addr: T) -> Self
+    where
+        BulkString: From<T>,
+    {
+        self.0.push(Request::from_bstatic(b"ADDR"));
+        self.0.push(addr.into());
+        self
+    }
+
+    /// Kill connections to the local address, `ip:port`.
+    pub fn laddr<T>(mut self, addr: T) -> Self
+    where
+        BulkString: From<T>,
+    {
+        self.0.push(Request::from_bstatic(b"LADDR"));
+        self.0.push(addr.into());
+        self
+    }
+
+    /// Kill connections authenticated as the user.
+    pub fn user<T>(mut self, user: T) -> Self
+    where
+        BulkString: From<T>,
+    {
+        self.0.push(Request::from_bstatic(b"USER"));
+        self.0.push(user.into());
+        self
+    }
+
+    /// Kill connections of the specific type.
+    pub fn client_type(mut self, tp: ClientType) -> Self {
+        self.0.push(Request::from_bstatic(b"TYPE"));
+        self.0.push(tp.to_request());
+        self
+    }
+
+    /// Kill connections older than specified age.
+    pub fn max_age(mut self, age: Duration) -> Self {
+        self.0.push(Request::from_bstatic(b"MAXAGE"));
+        self.0.push(utils::secs_request(age));
+        self
+    }
+
+    /// Skip current connection, enabled by default.
+    pub fn skip_me(mut self, skip: bool) -> Self {
+        self.0.push(Request::from_bstatic(b"SKIPME"));
+        self.0.push(if skip {
+            Request::from_bstatic(b"YES")
+        } else {
+            Request::from_bstatic(b"NO")
+        });
+        self
+    }
+}
+
+impl Command for ClientKillCommand {
+    type Output = usize;
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(usize::try_from(val)?)
+    }
+}
+
+/// CLIENT PAUSE redis command
+///
+/// Suspends all the clients for the specified amount of time.
+pub fn ClientPause(timeout: Duration) -> ClientPauseCommand {
+    ClientPauseCommand(vec![
+        Request::from_static("CLIENT"),
+        Request::from_static("PAUSE"),
+        utils::millis_request(timeout),
+    ])
+}
+
+pub struct ClientPauseCommand(Vec<Request>);
+
+impl ClientPauseCommand {
+    /// Suspend write commands only.
+    pub fn write_only(mut self) -> Self {
+        self.0.push(Request::from_bstatic(b"WRITE"));
+        self
+    }
+}
+
+impl Command for ClientPauseCommand {
+    type Output = ();
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        utils::OkOutputCommand::to_output(val)
+    }
+}
+
+/// CLIENT UNPAUSE redis command
+///
+/// Resumes clients suspended with `CLIENT PAUSE`.
+pub fn ClientUnpause() -> utils::OkOutputCommand {
+    utils::OkOutputCommand(Request::Array(vec![
+        Request::from_static("CLIENT"),
+        Request::from_static("UNPAUSE"),
+    ]))
+}
+
+fn on_off(enabled: bool) -> Request {
+    if enabled {
+        Request::from_static("ON")
+    } else {
+        Request::from_static("OFF")
+    }
+}
+
+/// CLIENT NO-EVICT redis command
+///
+/// Excludes current connection from the client eviction.
+pub fn ClientNoEvict(enabled: bool) -> utils::OkOutputCommand {
+    utils::OkOutputCommand(Request::Array(vec![
+        Request::from_static("CLIENT"),
+        Request::from_static("NO-EVICT"),
+        on_off(enabled),
+    ]))
+}
+
+/// CLIENT NO-TOUCH redis command
+///
+/// Commands of current connection do not alter LRU/LFU of the keys.
+pub fn ClientNoTouch(enabled: bool) -> utils::OkOutputCommand {
+    utils::OkOutputCommand(Request::Array(vec![
+        Request::from_static("CLIENT"),
+        Request::from_static("NO-TOUCH"),
+        on_off(enabled),
+    ]))
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// Reply mode of the `CLIENT REPLY` command
+pub enum ReplyMode {
+    /// Server does not reply to commands
+    Off,
+    /// Server does not reply to the next command
+    Skip,
+}
+
+/// CLIENT REPLY OFF|SKIP redis command
+///
+/// Disables server replies to the current connection. Server does not
+/// acknowledge this command, so it could be sent only with
+/// `SimpleClient::exec_no_reply()`. Use `ClientReplyOn` to enable
+/// replies again.
+pub fn ClientReply(mode: ReplyMode) -> ClientReplyCommand {
+    let mode = match mode {
+        ReplyMode::Off => Request::from_static("OFF"),
+        ReplyMode::Skip => Request::from_static("SKIP"),
+    };
+    ClientReplyCommand(Request::Array(vec![
+        Request::from_static("CLIENT"),
+        Request::from_static("REPLY"),
+        mode,
+    ]))
+}
+
+/// Unacknowledged `CLIENT REPLY` command
+pub struct ClientReplyCommand(Request);
+
+impl NoReplyCommand for ClientReplyCommand {
+    fn into_request(self) -> Request {
+        self.0
+    }
+}
+
+/// CLIENT REPLY ON redis command
+///
+/// Enables server replies to the current connection, default mode.
+pub fn ClientReplyOn() -> utils::OkOutputCommand {
+    utils::OkOutputCommand(Request::Array(vec![
+        Request::from_static("CLIENT"),
+        Request::from_static("REPLY"),
+        Request::from_static("ON"),
+    ]))
+}
+
+/// CLIENT TRACKING redis command
+///
+/// Enables or disables server assisted client side caching.
+pub fn ClientTracking(enabled: bool) -> ClientTrackingCommand {
+    ClientTrackingCommand(vec![
+        Request::from_static("CLIENT"),
+        Request::from_static("TRACKING"),
+        on_off(enabled),
+    ])
+}
+
+pub struct ClientTrackingCommand(Vec<Request>);
+
+impl ClientTrackingCommand {
+    /// Send invalidation messages to the connection with the id.
+    pub fn redirect(mut self, id: i64) -> Self {
+        self.0.push(Request::from_bstatic(b"REDIRECT"));
+        self.0.push(Request::BulkInteger(id));
+        self
+    }
+
+    /// Track key prefix in broadcasting mode.
+    pub fn prefix<T>(mut self, prefix: T) -> Self
+    where
+        BulkString: From<T>,
+    {
+        self.0.push(Request::from_bstatic(b"PREFIX"));
+        self.0.push(prefix.into());
+        self
+    }
+
+    /// Enable broadcasting mode.
+    pub fn bcast(mut self) -> Self {
+        self.0.push(Request::from_bstatic(b"BCAST"));
+        self
+    }
+
+    /// Track keys of the next read command only, after `CLIENT CACHING yes`.
+    pub fn opt_in(mut self) -> Self {
+        self.0.push(Request::from_bstatic(b"OPTIN"));
+        self
+    }
+
+    /// Do not track keys of the next read command, after `CLIENT CACHING no`.
+    pub fn opt_out(mut self) -> Self {
+        self.0.push(Request::from_bstatic(b"OPTOUT"));
+        self
+    }
+
+    /// Do not send invalidation messages for keys modified by this connection.
+    pub fn no_loop(mut self) -> Self {
+        self.0.push(Request::from_bstatic(b"NOLOOP"));
+        self
+    }
+}
+
+impl Command for ClientTrackingCommand {
+    type Output = ();
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        utils::OkOutputCommand::to_output(val)
+    }
+}
@@ -0,0 +1,374 @@
+use std::convert::TryFrom;
+
+use super::scripting::EvalCommand;
+use super::utils::{self, FlushMode};
+use super::{Command, CommandError};
+use crate::bytes::{ByteString, Bytes};
+use crate::codec_redis::{BulkString, Request, Response};
+
+/// FCALL redis command
+///
+/// Invoke a function loaded with `FUNCTION LOAD` command. Command output
+/// is decoded into any type that implements `FromResponse`.
+pub fn FCall<S, T>(function: S) -> EvalCommand<T>
+where
+    BulkString: From<S>,
+{
+    EvalCommand::new("FCALL", Request::BulkString(function.into()))
+}
+
+/// FCALL_RO redis command
+///
+/// Read-only variant of the `FCALL` command, it can be executed on
+/// replicas. Function must be declared with the `no-writes` flag.
+pub fn FCallRo<S, T>(function: S) -> EvalCommand<T>
+where
+    BulkString: From<S>,
+{
+    EvalCommand::new("FCALL_RO", Request::BulkString(function.into()))
+}
+
+/// FUNCTION LOAD redis command
+///
+/// Load a library to redis. Returns the library name.
+pub fn FunctionLoad<S>(code: S) -> FunctionLoadCommand
+where
+    BulkString: From<S>,
+{
+    FunctionLoadCommand {
+        code: Request::BulkString(code.into()),
+        replace: false,
+    }
+}
+
+pub struct FunctionLoadCommand {
+    code: Request,
+    replace: bool,
+}
+
+impl FunctionLoadCommand {
+    /// Overwrite the existing library with the same name.
+    pub fn replace(mut self) -> Self {
+        self.replace = true;
+        self
+    }
+}
+
+impl Command for FunctionLoadCommand {
+    type Output = ByteString;
+
+    fn to_request(self) -> Request {
+        let mut req = vec![
+            Request::from_static("FUNCTION"),
+            Request::from_static("LOAD"),
+        ];
+        if self.replace {
+            req.push(Request::from_static("REPLACE"));
+        }
+        req.push(self.code);
+        Request::Array(req)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(ByteString::try_from(val)?)
+    }
+}
+
+/// FUNCTION DELETE redis command
+///
+/// Delete a library and all its functions.
+pub fn FunctionDelete<S>(library: S) -> utils::OkOutputCommand
+where
+    BulkString: From<S>,
+{
+    utils::OkOutputCommand(Request::Array(vec![
+        Request::from_static("FUNCTION"),
+        Request::from_static("DELETE"),
+        Request::BulkString(library.into()),
+    ]))
+}
+
+/// FUNCTION FLUSH redis command
+///
+/// Delete all the libraries.
+pub fn FunctionFlush(mode: FlushMode) -> utils::OkOutputCommand {
+    utils::OkOutputCommand(Request::Array(vec![
+        Request::from_static("FUNCTION"),
+        Request::from_static("FLUSH"),
+        mode.to_request(),
+    ]))
+}
+
+/// FUNCTION DUMP redis command
+///
+/// Return the serialized payload of loaded libraries.
+pub fn FunctionDump() -> utils::BulkOutputCommand {
+    utils::BulkOutputCommand(Request::Array(vec![
+        Request::from_static("FUNCTION"),
+        Request::from_static("DUMP"),
+    ]))
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// Policy for handling existing libraries by `FUNCTION RESTORE` command
+pub enum RestorePolicy {
+    /// Delete all existing libraries before restoring the payload
+    Flush,
+    /// Append the restored libraries, abort on name collision
+    Append,
+    /// Append the restored libraries, replace existing ones on collision
+    Replace,
+}
+
+/// FUNCTION RESTORE redis command
+///
+/// Restore libraries from the serialized payload produced by `FUNCTION DUMP`.
+pub fn FunctionRestore<S>(payload: S) -> FunctionRestoreCommand
+where
+    BulkString: From<S>,
+{
+    FunctionRestoreCommand(vec![
+        Request::from_static("FUNCTION"),
+        Request::from_static("RESTORE"),
+        Request::BulkString(payload.into()),
+    ])
+}
+
+pub struct FunctionRestoreCommand(Vec<Request>);
+
+impl FunctionRestoreCommand {
+    /// Set policy for existing libraries, default is `Append`.
+    pub fn policy(mut self, policy: RestorePolicy) -> Self {
+        self.0.push(match policy {
+            RestorePolicy::Flush => Request::from_static("FLUSH"),
+            RestorePolicy::Append => Request::from_static("APPEND"),
+            RestorePolicy::Replace => Request::from_static("REPLACE"),
+        });
+        self
+    }
+}
+
+impl Command for FunctionRestoreCommand {
+    type Output = ();
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        utils::OkOutputCommand::to_output(val)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Function descriptor returned by `FUNCTION LIST` command
+pub struct FunctionInfo {
+    /// Name of the function
+    pub name: ByteString,
+    /// Function description
+    pub description: Option<ByteString>,
+    /// Function flags, i.e. `no-writes`
+    pub flags: Vec<ByteString>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Library descriptor returned by `FUNCTION LIST` command
+pub struct LibraryInfo {
+    /// Name of the library
+    pub name: ByteString,
+    /// Engine of the library, i.e. `LUA`
+    pub engine: ByteString,
+    /// Functions of the library
+    pub functions: Vec<FunctionInfo>,
+    /// Library source code, available only with `WITHCODE` option
+    pub code: Option<Bytes>,
+}
+
+/// FUNCTION LIST redis command
+///
+/// Return information about the functions and libraries.
+///
+/// ```rust,no_run
+/// use mco_redis::cmd;
+/// use mco_redis::connector::RedisConnector;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // requires real redis server, FUNCTION LOAD and FUNCTION LIST are not implemented by `MockServer`
+///     let redis = RedisConnector::new("127.0.0.1:6379").connect()?;
+///
+///     redis.exec(cmd::FunctionLoad(
+///         "#!lua name=listlib\nredis.register_function('listfn', function() return 1 end)"
+///     ).replace())?;
+///
+///     let libs = redis.exec(cmd::FunctionList().library("listlib"))?;
+///
+///     assert_eq!(libs[0].name, "listlib");
+///     assert_eq!(libs[0].functions[0].name, "listfn");
+///     Ok(())
+/// }
+/// ```
+pub fn FunctionList() -> FunctionListCommand {
+    FunctionListCommand(vec![
+        Request::from_static("FUNCTION"),
+        Request::from_static("LIST"),
+    ])
+}
+
+pub struct FunctionListCommand(Vec<Request>);
+
+impl FunctionListCommand {
+    /// Only return libraries with names matching glob-style `pattern`.
+    pub fn library<S>(mut self, pattern: S) -> Self
+    where
+        BulkString: From<S>,
+    {
+        self.0.push(Request::from_bstatic(b"LIBRARYNAME"));
+        self.0.push(pattern.into());
+        self
+    }
+
+    /// Include library source code into the result.
+    pub fn with_code(mut self) -> Self {
+        self.0.push(Request::from_bstatic(b"WITHCODE"));
+        self
+    }
+}
+
+impl Command for FunctionListCommand {
+    type Output = Vec<LibraryInfo>;
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        let libs = match val {
+            Response::Array(libs) => libs,
+            _ => return Err(CommandError::Output("Cannot parse response", val)),
+        };
+        libs.into_iter().map(parse_library).collect()
+    }
+}
+
+fn parse_library(val: Response) -> Result<LibraryInfo, CommandError> {
+    let mut lib = LibraryInfo {
+        name: ByteString::new(),
+        engine: ByteString::new(),
+        functions: Vec::new(),
+        code: None,
+    };
+    for (name, value) in utils::map_entries(val)? {
+        match name.as_ref() {
+            "library_name" => lib.name = ByteString::try_from(value)?,
+            "engine" => lib.engine = ByteString::try_from(value)?,
+            "library_code" => lib.code = Option::try_from(value)?,
+            "functions" => match value {
+                Response::Array(items) => {
+                    for item in items {
+                        lib.functions.push(parse_function(item)?);
+                    }
+                }
+                _ => return Err(CommandError::Output("Cannot parse functions", value)),
+            },
+            _ => (),
+        }
+    }
+    Ok(lib)
+}
+
+fn parse_function(val: Response) -> Result<FunctionInfo, CommandError> {
+    let mut func = FunctionInfo {
+        name: ByteString::new(),
+        description: None,
+        flags: Vec::new(),
+    };
+    for (name, value) in utils::map_entries(val)? {
+        match name.as_ref() {
+            "name" => func.name = ByteString::try_from(value)?,
+            "description" => func.description = Option::try_from(value)?,
+            "flags" => func.flags = Vec::try_from(value)?,
+            _ => (),
+        }
+    }
+    Ok(func)
+}
+
+/// Redis functions library
+///
+/// Library name is parsed from the shebang line of the code, i.e.
+/// `#!lua name=mylib`. Use `Client::ensure_library()` to load library
+/// or replace it if loaded code differs, and `Client::exec_function()` to
+/// call a function and load library on demand.
+///
+/// ```rust,no_run
+/// use mco_redis::cmd;
+/// use mco_redis::connector::RedisConnector;
+/// use mco_redis::cmd::commands::Library;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // requires real redis server, FUNCTION and FCALL are not implemented by `MockServer`
+///     let redis = RedisConnector::new("127.0.0.1:6379").connect()?;
+///
+///     let lib = Library::new(
+///         "#!lua name=mathlib\n\
+///          redis.register_function('double', function(keys, args) return args[1] * 2 end)"
+///     ).unwrap();
+///
+///     let value: i64 = redis.exec_function(&lib, cmd::FCall("double").arg("21"))?;
+///
+///     assert_eq!(value, 42);
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Library {
+    name: ByteString,
+    code: Bytes,
+}
+
+impl Library {
+    /// Create library from its code, returns `None` if code does not
+    /// contain shebang line with library name.
+    pub fn new<S: AsRef<str>>(code: S) -> Option<Self> {
+        let code = code.as_ref();
+        let shebang = code.lines().next()?;
+        if !shebang.starts_with("#!") {
+            return None;
+        }
+        let name = shebang
+            .split_whitespace()
+            .find_map(|part| part.strip_prefix("name="))?;
+
+        Some(Library {
+            name: ByteString::from(name.to_string()),
+            code: Bytes::copy_from_slice(code.as_bytes()),
+        })
+    }
+
+    /// Library name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Library code
+    pub fn code(&self) -> &[u8] {
+        &self.code
+    }
+
+    /// Create `FUNCTION LIST` command that checks loaded library.
+    pub fn list(&self) -> FunctionListCommand {
+        FunctionList().library(&self.name).with_code()
+    }
+
+    /// Create `FUNCTION LOAD` command if library is not loaded or loaded
+    /// code differs.
+    pub fn load(&self, loaded: &[LibraryInfo]) -> Option<FunctionLoadCommand> {
+        match loaded.iter().find(|lib| lib.name == self.name) {
+            None => Some(FunctionLoad(&self.code)),
+            Some(lib) if lib.code.as_ref() != Some(&self.code) => {
+                Some(FunctionLoad(&self.code).replace())
+            }
+            Some(_) => None,
+        }
+    }
+}
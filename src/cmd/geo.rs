@@ -0,0 +1,573 @@
+use std::convert::TryFrom;
+
+use super::{utils, Command, CommandError};
+use crate::bytes::Bytes;
+use crate::codec_redis::{BulkString, Request, Response};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// Distance unit of geospatial commands
+pub enum GeoUnit {
+    /// Meters
+    M,
+    /// Kilometers
+    Km,
+    /// Miles
+    Mi,
+    /// Feet
+    Ft,
+}
+
+impl GeoUnit {
+    fn to_request(self) -> Request {
+        match self {
+            GeoUnit::M => Request::from_static("m"),
+            GeoUnit::Km => Request::from_static("km"),
+            GeoUnit::Mi => Request::from_static("mi"),
+            GeoUnit::Ft => Request::from_static("ft"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Center point of the `GEOSEARCH` area
+pub enum GeoFrom {
+    /// Position of the existing member
+    Member(BulkString),
+    /// Longitude and latitude
+    LonLat(f64, f64),
+}
+
+impl GeoFrom {
+    /// Use position of the existing member.
+    pub fn member<T>(member: T) -> Self
+    where
+        BulkString: From<T>,
+    {
+        GeoFrom::Member(member.into())
+    }
+
+    fn push_to(self, req: &mut Vec<Request>) {
+        match self {
+            GeoFrom::Member(member) => {
+                req.push(Request::from_bstatic(b"FROMMEMBER"));
+                req.push(Request::BulkString(member));
+            }
+            GeoFrom::LonLat(lon, lat) => {
+                req.push(Request::from_bstatic(b"FROMLONLAT"));
+                req.push(float_request(lon));
+                req.push(float_request(lat));
+            }
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+/// Shape of the `GEOSEARCH` area
+pub enum GeoBy {
+    /// Circular area with the given radius
+    Radius(f64, GeoUnit),
+    /// Axis-aligned rectangle with the given width and height
+    Box(f64, f64, GeoUnit),
+}
+
+impl GeoBy {
+    fn push_to(self, req: &mut Vec<Request>) {
+        match self {
+            GeoBy::Radius(radius, unit) => {
+                req.push(Request::from_bstatic(b"BYRADIUS"));
+                req.push(float_request(radius));
+                req.push(unit.to_request());
+            }
+            GeoBy::Box(width, height, unit) => {
+                req.push(Request::from_bstatic(b"BYBOX"));
+                req.push(float_request(width));
+                req.push(float_request(height));
+                req.push(unit.to_request());
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Single item of the `GEOSEARCH` result
+pub struct GeoResult {
+    /// Member name
+    pub member: Bytes,
+    /// Distance from the center, available with `WITHDIST` option
+    pub dist: Option<f64>,
+    /// Raw geohash-encoded sorted set score, available with `WITHHASH` option
+    pub hash: Option<i64>,
+    /// Longitude and latitude, available with `WITHCOORD` option
+    pub coord: Option<(f64, f64)>,
+}
+
+impl TryFrom<Response> for GeoResult {
+    type Error = (&'static str, Response);
+
+    fn try_from(val: Response) -> Result<Self, Self::Error> {
+        match val {
+            Response::Bytes(member) => Ok(GeoResult {
+                member,
+                dist: None,
+                hash: None,
+                coord: None,
+            }),
+            Response::Array(items) => {
+                let mut items = items.into_iter();
+                let member = Bytes::try_from(items.next().unwrap_or(Response::Nil))?;
+                let mut result = GeoResult {
+                    member,
+                    dist: None,
+                    hash: None,
+                    coord: None,
+                };
+                // optional values are returned in order: dist, hash, coord
+                for item in items {
+                    match item {
+                        Response::Integer(hash) => result.hash = Some(hash),
+                        Response::Array(_) => result.coord = Some(<(f64, f64)>::try_from(item)?),
+                        item => result.dist = Some(f64::try_from(item)?),
+                    }
+                }
+                Ok(result)
+            }
+            _ => Err(("Cannot be converted into a geo result", val)),
+        }
+    }
+}
+
+fn float_request(val: f64) -> Request {
+    Request::BulkString(val.to_string().into())
+}
+
+/// GEOADD redis command
+///
+/// Adds the specified geospatial items (longitude, latitude, name) to the
+/// specified key. Returns the number of added elements.
+///
+/// ```rust,no_run
+/// use mco_redis::cmd;
+/// use mco_redis::connector::RedisConnector;
+/// use mco_redis::cmd::commands::{GeoBy, GeoFrom, GeoUnit};
+/// # use rand::{thread_rng, Rng, distributions::Alphanumeric};
+/// # fn gen_random_key() -> String {
+/// #    thread_rng().sample_iter(&Alphanumeric).take(12).map(char::from).collect::<String>()
+/// # }
+///
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // requires real redis server, GEOADD and GEOSEARCH are not implemented by `MockServer`
+///     let redis = RedisConnector::new("127.0.0.1:6379").connect()?;
+///     let key = gen_random_key();
+///
+///     redis.exec(
+///         cmd::GeoAdd(&key, 13.361389, 38.115556, "Palermo")
+///             .member(15.087269, 37.502669, "Catania")
+///     )?;
+///
+///     // find stores within 200 km of the point
+///     let stores = redis.exec(
+///         cmd::GeoSearch(&key, GeoFrom::LonLat(15.0, 37.0), GeoBy::Radius(200.0, GeoUnit::Km))
+///             .asc()
+///             .with_dist()
+///     )?;
+///
+///     assert_eq!(stores[0].member, "Catania");
+///     assert!(stores[0].dist.unwrap() < 100.0);
+///     Ok(())
+/// }
+/// ```
+pub fn GeoAdd<T, M>(key: T, longitude: f64, latitude: f64, member: M) -> GeoAddCommand
+where
+    BulkString: From<T> + From<M>,
+{
+    GeoAddCommand {
+        key: Request::BulkString(key.into()),
+        opts: Vec::new(),
+        members: vec![
+            float_request(longitude),
+            float_request(latitude),
+            Request::BulkString(member.into()),
+        ],
+    }
+}
+
+pub struct GeoAddCommand {
+    key: Request,
+    opts: Vec<Request>,
+    members: Vec<Request>,
+}
+
+impl GeoAddCommand {
+    /// Add a member to this command.
+    pub fn member<M>(mut self, longitude: f64, latitude: f64, member: M) -> Self
+    where
+        BulkString: From<M>,
+    {
+        self.members.push(float_request(longitude));
+        self.members.push(float_request(latitude));
+        self.members.push(member.into());
+        self
+    }
+
+    /// Only update elements that already exist.
+    pub fn if_exists(mut self) -> Self {
+        self.opts.push(Request::from_bstatic(b"XX"));
+        self
+    }
+
+    /// Only add new elements.
+    pub fn if_not_exists(mut self) -> Self {
+        self.opts.push(Request::from_bstatic(b"NX"));
+        self
+    }
+
+    /// Return the number of changed elements, added and updated.
+    pub fn changed(mut self) -> Self {
+        self.opts.push(Request::from_bstatic(b"CH"));
+        self
+    }
+}
+
+impl Command for GeoAddCommand {
+    type Output = usize;
+
+    fn to_request(self) -> Request {
+        let mut req = Vec::with_capacity(self.opts.len() + self.members.len() + 2);
+        req.push(Request::from_static("GEOADD"));
+        req.push(self.key);
+        req.extend(self.opts);
+        req.extend(self.members);
+        Request::Array(req)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(usize::try_from(val)?)
+    }
+}
+
+/// GEOPOS redis command
+///
+/// Returns the positions (longitude, latitude) of the specified members,
+/// `None` is returned for missing members.
+pub fn GeoPos<T, M>(key: T, member: M) -> GeoPosCommand
+where
+    BulkString: From<T> + From<M>,
+{
+    GeoPosCommand(vec![
+        Request::from_static("GEOPOS"),
+        Request::BulkString(key.into()),
+        Request::BulkString(member.into()),
+    ])
+}
+
+pub struct GeoPosCommand(Vec<Request>);
+
+impl GeoPosCommand {
+    /// Add a member to this command.
+    pub fn member<M>(mut self, member: M) -> Self
+    where
+        BulkString: From<M>,
+    {
+        self.0.push(member.into());
+        self
+    }
+
+    /// Add more members to this command.
+    pub fn members<M>(mut self, other: impl IntoIterator<Item = M>) -> Self
+    where
+        BulkString: From<M>,
+    {
+        self.0.extend(other.into_iter().map(|t| t.into()));
+        self
+    }
+}
+
+impl Command for GeoPosCommand {
+    type Output = Vec<Option<(f64, f64)>>;
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(Vec::try_from(val)?)
+    }
+}
+
+/// GEODIST redis command
+///
+/// Returns the distance between two members, `None` is returned if one
+/// or both members are missing.
+pub fn GeoDist<T, M1, M2>(key: T, member1: M1, member2: M2, unit: GeoUnit) -> GeoDistCommand
+where
+    BulkString: From<T> + From<M1> + From<M2>,
+{
+    GeoDistCommand(Request::Array(vec![
+        Request::from_static("GEODIST"),
+        Request::BulkString(key.into()),
+        Request::BulkString(member1.into()),
+        Request::BulkString(member2.into()),
+        unit.to_request(),
+    ]))
+}
+
+pub struct GeoDistCommand(Request);
+
+impl Command for GeoDistCommand {
+    type Output = Option<f64>;
+
+    fn to_request(self) -> Request {
+        self.0
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(Option::try_from(val)?)
+    }
+}
+
+/// GEOHASH redis command
+///
+/// Returns Geohash strings representing the positions of the specified
+/// members, `None` is returned for missing members.
+pub fn GeoHash<T, M>(key: T, member: M) -> GeoHashCommand
+where
+    BulkString: From<T> + From<M>,
+{
+    GeoHashCommand(vec![
+        Request::from_static("GEOHASH"),
+        Request::BulkString(key.into()),
+        Request::BulkString(member.into()),
+    ])
+}
+
+pub struct GeoHashCommand(Vec<Request>);
+
+impl GeoHashCommand {
+    /// Add a member to this command.
+    pub fn member<M>(mut self, member: M) -> Self
+    where
+        BulkString: From<M>,
+    {
+        self.0.push(member.into());
+        self
+    }
+
+    /// Add more members to this command.
+    pub fn members<M>(mut self, other: impl IntoIterator<Item = M>) -> Self
+    where
+        BulkString: From<M>,
+    {
+        self.0.extend(other.into_iter().map(|t| t.into()));
+        self
+    }
+}
+
+impl Command for GeoHashCommand {
+    type Output = Vec<Option<Bytes>>;
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        utils::MultiBulkOutputCommand::to_output(val)
+    }
+}
+
+/// Shared options of `GEOSEARCH` and `GEOSEARCHSTORE` commands
+struct GeoSearchOpts {
+    order: Option<&'static str>,
+    count: Option<(usize, bool)>,
+}
+
+impl GeoSearchOpts {
+    fn push_to(self, req: &mut Vec<Request>) {
+        if let Some(order) = self.order {
+            req.push(Request::from_static(order));
+        }
+        if let Some((count, any)) = self.count {
+            req.push(Request::from_bstatic(b"COUNT"));
+            req.push(Request::BulkInteger(count as i64));
+            if any {
+                req.push(Request::from_bstatic(b"ANY"));
+            }
+        }
+    }
+}
+
+/// GEOSEARCH redis command
+///
+/// Returns the members within the borders of the area specified by a
+/// given shape.
+pub fn GeoSearch<T>(key: T, from: GeoFrom, by: GeoBy) -> GeoSearchCommand
+where
+    BulkString: From<T>,
+{
+    let mut req = vec![
+        Request::from_static("GEOSEARCH"),
+        Request::BulkString(key.into()),
+    ];
+    from.push_to(&mut req);
+    by.push_to(&mut req);
+
+    GeoSearchCommand {
+        req,
+        opts: GeoSearchOpts {
+            order: None,
+            count: None,
+        },
+        with: Vec::new(),
+    }
+}
+
+pub struct GeoSearchCommand {
+    req: Vec<Request>,
+    opts: GeoSearchOpts,
+    with: Vec<Request>,
+}
+
+impl GeoSearchCommand {
+    /// Sort returned items from the nearest to the farthest.
+    pub fn asc(mut self) -> Self {
+        self.opts.order = Some("ASC");
+        self
+    }
+
+    /// Sort returned items from the farthest to the nearest.
+    pub fn desc(mut self) -> Self {
+        self.opts.order = Some("DESC");
+        self
+    }
+
+    /// Limit the number of returned items.
+    pub fn count(mut self, count: usize) -> Self {
+        self.opts.count = Some((count, false));
+        self
+    }
+
+    /// Limit the number of returned items, return as soon as enough
+    /// matches are found.
+    pub fn count_any(mut self, count: usize) -> Self {
+        self.opts.count = Some((count, true));
+        self
+    }
+
+    /// Return the longitude and latitude of matching items.
+    pub fn with_coord(mut self) -> Self {
+        self.with.push(Request::from_bstatic(b"WITHCOORD"));
+        self
+    }
+
+    /// Return the distance of matching items from the center.
+    pub fn with_dist(mut self) -> Self {
+        self.with.push(Request::from_bstatic(b"WITHDIST"));
+        self
+    }
+
+    /// Return the raw geohash-encoded sorted set score of matching items.
+    pub fn with_hash(mut self) -> Self {
+        self.with.push(Request::from_bstatic(b"WITHHASH"));
+        self
+    }
+}
+
+impl Command for GeoSearchCommand {
+    type Output = Vec<GeoResult>;
+
+    fn to_request(mut self) -> Request {
+        self.opts.push_to(&mut self.req);
+        self.req.extend(self.with);
+        Request::Array(self.req)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(Vec::try_from(val)?)
+    }
+}
+
+/// GEOSEARCHSTORE redis command
+///
+/// Stores the members within the borders of the area specified by a given
+/// shape to the `destination` key. Returns the number of stored members.
+pub fn GeoSearchStore<T, S>(
+    destination: T,
+    source: S,
+    from: GeoFrom,
+    by: GeoBy,
+) -> GeoSearchStoreCommand
+where
+    BulkString: From<T> + From<S>,
+{
+    let mut req = vec![
+        Request::from_static("GEOSEARCHSTORE"),
+        Request::BulkString(destination.into()),
+        Request::BulkString(source.into()),
+    ];
+    from.push_to(&mut req);
+    by.push_to(&mut req);
+
+    GeoSearchStoreCommand {
+        req,
+        opts: GeoSearchOpts {
+            order: None,
+            count: None,
+        },
+        storedist: false,
+    }
+}
+
+pub struct GeoSearchStoreCommand {
+    req: Vec<Request>,
+    opts: GeoSearchOpts,
+    storedist: bool,
+}
+
+impl GeoSearchStoreCommand {
+    /// Sort items from the nearest to the farthest.
+    pub fn asc(mut self) -> Self {
+        self.opts.order = Some("ASC");
+        self
+    }
+
+    /// Sort items from the farthest to the nearest.
+    pub fn desc(mut self) -> Self {
+        self.opts.order = Some("DESC");
+        self
+    }
+
+    /// Limit the number of stored items.
+    pub fn count(mut self, count: usize) -> Self {
+        self.opts.count = Some((count, false));
+        self
+    }
+
+    /// Limit the number of stored items, stop as soon as enough matches
+    /// are found.
+    pub fn count_any(mut self, count: usize) -> Self {
+        self.opts.count = Some((count, true));
+        self
+    }
+
+    /// Store distances from the center as scores instead of positions.
+    pub fn store_dist(mut self) -> Self {
+        self.storedist = true;
+        self
+    }
+}
+
+impl Command for GeoSearchStoreCommand {
+    type Output = usize;
+
+    fn to_request(mut self) -> Request {
+        self.opts.push_to(&mut self.req);
+        if self.storedist {
+            self.req.push(Request::from_bstatic(b"STOREDIST"));
+        }
+        Request::Array(self.req)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(usize::try_from(val)?)
+    }
+}
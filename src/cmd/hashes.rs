@@ -1,16 +1,22 @@
 use std::collections::HashMap;
 use std::convert::TryFrom;
+use std::marker::PhantomData;
+use std::time::Duration;
 use crate::bytes::Bytes;
 
+use super::keys::{Millis, PTtlResult, Seconds, TimeRangeError, TtlResult};
 use super::{utils, Command, CommandError};
+use crate::args::{ToArg, ToArgs};
 use crate::codec_redis::{BulkString, Request, Response};
+use crate::response::FromResponse;
 
 /// HGET redis command
 ///
 /// Returns the value associated with field in the hash stored at key.
 ///
 /// ```rust
-/// use mco_redis::{cmd, RedisConnector};
+/// use mco_redis::cmd;
+/// use mco_redis::testing::MockServer;
 /// # use rand::{thread_rng, Rng, distributions::Alphanumeric};
 /// # fn gen_random_key() -> String {
 /// #    thread_rng().sample_iter(&Alphanumeric).take(12).map(char::from).collect::<String>()
@@ -18,7 +24,8 @@ use crate::codec_redis::{BulkString, Request, Response};
 ///
 ///
 /// fn main() -> Result<(), Box<dyn std::error::Error>> {
-///     let redis = RedisConnector::new("127.0.0.1:6379").connect()?;
+///     let server = MockServer::start()?;
+///     let redis = server.connector().connect()?;
 ///     let key = gen_random_key();
 ///
 ///     // create hashmap
@@ -45,27 +52,72 @@ where
 /// HGETALL redis command
 ///
 /// Returns all fields and values of the hash stored at `key`
+///
+/// By default result is decoded into `HashMap<Bytes, Bytes>`, use
+/// `HGetAllCommand::output()` to choose another map type.
+///
+/// ```rust
+/// use std::collections::BTreeMap;
+/// use mco_redis::cmd;
+/// use mco_redis::testing::MockServer;
+/// # use rand::{thread_rng, Rng, distributions::Alphanumeric};
+/// # fn gen_random_key() -> String {
+/// #    thread_rng().sample_iter(&Alphanumeric).take(12).map(char::from).collect::<String>()
+/// # }
+///
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let server = MockServer::start()?;
+///     let redis = server.connector().connect()?;
+///     let key = gen_random_key();
+///
+///     redis.exec(cmd::HSet(&key, "field1", "value1").entry("field2", "value2"))?;
+///
+///     // decode hash into a sorted map of strings
+///     let map = redis.exec(cmd::HGetAll(&key).output::<BTreeMap<String, String>>())?;
+///
+///     assert_eq!(map["field1"], "value1");
+///     assert_eq!(map["field2"], "value2");
+///     Ok(())
+/// }
+/// ```
 pub fn HGetAll<T>(key: T) -> HGetAllCommand
 where
     BulkString: From<T>,
 {
-    HGetAllCommand(vec![
-        Request::from_static("HGETALL"),
-        Request::BulkString(key.into()),
-    ])
+    HGetAllCommand(
+        vec![
+            Request::from_static("HGETALL"),
+            Request::BulkString(key.into()),
+        ],
+        PhantomData,
+    )
 }
 
-pub struct HGetAllCommand(Vec<Request>);
+pub struct HGetAllCommand<M = HashMap<Bytes, Bytes>>(Vec<Request>, PhantomData<M>);
 
-impl Command for HGetAllCommand {
-    type Output = HashMap<Bytes, Bytes>;
+impl<M> HGetAllCommand<M> {
+    /// Decode result into a different map type
+    pub fn output<U>(self) -> HGetAllCommand<U>
+    where
+        U: FromResponse,
+    {
+        HGetAllCommand(self.0, PhantomData)
+    }
+}
+
+impl<M> Command for HGetAllCommand<M>
+where
+    M: FromResponse,
+{
+    type Output = M;
 
     fn to_request(self) -> Request {
         Request::Array(self.0)
     }
 
     fn to_output(val: Response) -> Result<Self::Output, CommandError> {
-        Ok(HashMap::try_from(val)?)
+        M::from_response(val)
     }
 }
 
@@ -74,7 +126,8 @@ impl Command for HGetAllCommand {
 /// Sets field in the hash stored at key to value.
 ///
 /// ```rust
-/// use mco_redis::{cmd, RedisConnector};
+/// use mco_redis::cmd;
+/// use mco_redis::testing::MockServer;
 /// # use rand::{thread_rng, Rng, distributions::Alphanumeric};
 /// # fn gen_random_key() -> String {
 /// #    thread_rng().sample_iter(&Alphanumeric).take(12).map(char::from).collect::<String>()
@@ -82,7 +135,8 @@ impl Command for HGetAllCommand {
 ///
 ///
 /// fn main() -> Result<(), Box<dyn std::error::Error>> {
-///     let redis = RedisConnector::new("127.0.0.1:6379").connect()?;
+///     let server = MockServer::start()?;
+///     let redis = server.connector().connect()?;
 ///     let key = gen_random_key();
 ///
 ///     // create hashmap and set field
@@ -97,36 +151,54 @@ impl Command for HGetAllCommand {
 /// ```
 pub fn HSet<T, K, V>(key: T, field: K, value: V) -> HSetCommand
 where
-    BulkString: From<T> + From<K> + From<V>,
+    BulkString: From<T>,
+    K: ToArg,
+    V: ToArg,
 {
     HSetCommand(vec![
         Request::from_static("HSET"),
         Request::BulkString(key.into()),
-        Request::BulkString(field.into()),
-        Request::BulkString(value.into()),
     ])
+    .entry(field, value)
+}
+
+/// HSET redis command
+///
+/// Sets fields in the hash stored at key to field/value pairs written by
+/// `entries`, i.e. a map, a list of tuples or a struct with derived
+/// `ToArgs` implementation.
+pub fn HSetEntries<T, E>(key: T, entries: E) -> HSetCommand
+where
+    BulkString: From<T>,
+    E: ToArgs,
+{
+    let mut req = vec![
+        Request::from_static("HSET"),
+        Request::BulkString(key.into()),
+    ];
+    entries.write_args(&mut req);
+    HSetCommand(req)
 }
 
 pub struct HSetCommand(Vec<Request>);
 
 impl HSetCommand {
     /// Insert new entry to a redis hashmap
-    pub fn entry<K, V>(mut self, field: K, value: V) -> Self
-    where
-        BulkString: From<K> + From<V>,
-    {
-        self.0.push(field.into());
-        self.0.push(value.into());
+    pub fn entry<K: ToArg, V: ToArg>(mut self, field: K, value: V) -> Self {
+        self.add_entry(field, value);
         self
     }
 
     /// Insert new entry to a redis hashmap
-    pub fn add_entry<K, V>(&mut self, field: K, value: V)
-    where
-        BulkString: From<K> + From<V>,
-    {
-        self.0.push(field.into());
-        self.0.push(value.into());
+    pub fn add_entry<K: ToArg, V: ToArg>(&mut self, field: K, value: V) {
+        self.0.push(field.to_arg());
+        self.0.push(value.to_arg());
+    }
+
+    /// Insert field/value pairs to a redis hashmap
+    pub fn entries<E: ToArgs>(mut self, entries: E) -> Self {
+        entries.write_args(&mut self.0);
+        self
     }
 }
 
@@ -150,7 +222,8 @@ impl Command for HSetCommand {
 /// Removes the specified fields from the hash stored at key.
 ///
 /// ```rust
-/// use mco_redis::{cmd, RedisConnector};
+/// use mco_redis::cmd;
+/// use mco_redis::testing::MockServer;
 /// # use rand::{thread_rng, Rng, distributions::Alphanumeric};
 /// # fn gen_random_key() -> String {
 /// #    thread_rng().sample_iter(&Alphanumeric).take(12).map(char::from).collect::<String>()
@@ -158,7 +231,8 @@ impl Command for HSetCommand {
 ///
 ///
 /// fn main() -> Result<(), Box<dyn std::error::Error>> {
-///     let redis = RedisConnector::new("127.0.0.1:6379").connect()?;
+///     let server = MockServer::start()?;
+///     let redis = server.connector().connect()?;
 ///     let key = gen_random_key();
 ///
 ///     // create hashmap and set field
@@ -224,7 +298,8 @@ impl Command for HDelCommand {
 /// Returns the number of fields contained in the hash stored at key.
 ///
 /// ```rust
-/// use mco_redis::{cmd, RedisConnector};
+/// use mco_redis::cmd;
+/// use mco_redis::testing::MockServer;
 /// # use rand::{thread_rng, Rng, distributions::Alphanumeric};
 /// # fn gen_random_key() -> String {
 /// #    thread_rng().sample_iter(&Alphanumeric).take(12).map(char::from).collect::<String>()
@@ -232,7 +307,8 @@ impl Command for HDelCommand {
 ///
 ///
 /// fn main() -> Result<(), Box<dyn std::error::Error>> {
-///     let redis = RedisConnector::new("127.0.0.1:6379").connect()?;
+///     let server = MockServer::start()?;
+///     let redis = server.connector().connect()?;
 ///     let key = gen_random_key();
 ///
 ///     // create hashmap and set field
@@ -270,3 +346,557 @@ where
         Request::BulkString(i64::from(increment).to_string().into()),
     ]))
 }
+
+/// HINCRBYFLOAT redis command
+///
+/// Increment the floating point number stored at `field` in the hash
+/// stored at `key` by `increment`.
+pub fn HIncrByFloat<T, K>(key: T, field: K, increment: f64) -> utils::FloatOutputCommand
+where
+    BulkString: From<T> + From<K>,
+{
+    utils::FloatOutputCommand(Request::Array(vec![
+        Request::from_static("HINCRBYFLOAT"),
+        Request::BulkString(key.into()),
+        Request::BulkString(field.into()),
+        Request::BulkString(increment.to_string().into()),
+    ]))
+}
+
+/// HMGET redis command
+///
+/// Returns the values associated with the specified fields in the hash
+/// stored at `key`. For every field that does not exist `None` is returned.
+///
+/// ```rust
+/// use mco_redis::cmd;
+/// use mco_redis::testing::MockServer;
+/// # use rand::{thread_rng, Rng, distributions::Alphanumeric};
+/// # fn gen_random_key() -> String {
+/// #    thread_rng().sample_iter(&Alphanumeric).take(12).map(char::from).collect::<String>()
+/// # }
+///
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let server = MockServer::start()?;
+///     let redis = server.connector().connect()?;
+///     let key = gen_random_key();
+///
+///     redis.exec(cmd::HSet(&key, "field1", "value1"))?;
+///
+///     let values = redis.exec(cmd::HMGet(&key, "field1").field("field2"))?;
+///
+///     assert_eq!(values[0].as_ref().unwrap(), "value1");
+///     assert!(values[1].is_none());
+///     Ok(())
+/// }
+/// ```
+pub fn HMGet<T, K>(key: T, field: K) -> HMGetCommand
+where
+    BulkString: From<T> + From<K>,
+{
+    HMGetCommand(vec![
+        Request::from_static("HMGET"),
+        Request::BulkString(key.into()),
+        Request::BulkString(field.into()),
+    ])
+}
+
+pub struct HMGetCommand(Vec<Request>);
+
+impl HMGetCommand {
+    /// Add a field to this command.
+    pub fn field<K>(mut self, field: K) -> Self
+    where
+        BulkString: From<K>,
+    {
+        self.0.push(field.into());
+        self
+    }
+
+    /// Add more fields to this command.
+    pub fn fields<K>(mut self, other: impl IntoIterator<Item = K>) -> Self
+    where
+        BulkString: From<K>,
+    {
+        self.0.extend(other.into_iter().map(|t| t.into()));
+        self
+    }
+
+    /// Decode values into a different type, i.e. tuple or struct with
+    /// `#[redis(positional)]` attribute
+    pub fn output<U: FromResponse>(self) -> utils::OutputCommand<U> {
+        utils::OutputCommand(Request::Array(self.0), PhantomData)
+    }
+}
+
+impl Command for HMGetCommand {
+    type Output = Vec<Option<Bytes>>;
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        utils::MultiBulkOutputCommand::to_output(val)
+    }
+}
+
+/// HEXISTS redis command
+///
+/// Returns if `field` is an existing field in the hash stored at `key`.
+pub fn HExists<T, K>(key: T, field: K) -> utils::BoolOutputCommand
+where
+    BulkString: From<T> + From<K>,
+{
+    utils::BoolOutputCommand(Request::Array(vec![
+        Request::from_static("HEXISTS"),
+        Request::BulkString(key.into()),
+        Request::BulkString(field.into()),
+    ]))
+}
+
+/// HKEYS redis command
+///
+/// Returns all field names in the hash stored at `key`.
+pub fn HKeys<T>(key: T) -> utils::BulkArrayOutputCommand
+where
+    BulkString: From<T>,
+{
+    utils::BulkArrayOutputCommand(Request::Array(vec![
+        Request::from_static("HKEYS"),
+        Request::BulkString(key.into()),
+    ]))
+}
+
+/// HVALS redis command
+///
+/// Returns all values in the hash stored at `key`.
+pub fn HVals<T>(key: T) -> utils::BulkArrayOutputCommand
+where
+    BulkString: From<T>,
+{
+    utils::BulkArrayOutputCommand(Request::Array(vec![
+        Request::from_static("HVALS"),
+        Request::BulkString(key.into()),
+    ]))
+}
+
+/// HSETNX redis command
+///
+/// Sets `field` in the hash stored at `key` to `value`, only if `field`
+/// does not yet exist. Command returns true if field was set.
+pub fn HSetNx<T, K, V>(key: T, field: K, value: V) -> utils::BoolOutputCommand
+where
+    BulkString: From<T> + From<K> + From<V>,
+{
+    utils::BoolOutputCommand(Request::Array(vec![
+        Request::from_static("HSETNX"),
+        Request::BulkString(key.into()),
+        Request::BulkString(field.into()),
+        Request::BulkString(value.into()),
+    ]))
+}
+
+/// HSTRLEN redis command
+///
+/// Returns the string length of the value associated with `field` in the
+/// hash stored at `key`.
+pub fn HStrLen<T, K>(key: T, field: K) -> utils::IntOutputCommand
+where
+    BulkString: From<T> + From<K>,
+{
+    utils::IntOutputCommand(Request::Array(vec![
+        Request::from_static("HSTRLEN"),
+        Request::BulkString(key.into()),
+        Request::BulkString(field.into()),
+    ]))
+}
+
+/// HRANDFIELD redis command
+///
+/// Returns a random field from the hash stored at `key`.
+pub fn HRandField<T>(key: T) -> HRandFieldCommand
+where
+    BulkString: From<T>,
+{
+    HRandFieldCommand(vec![
+        Request::from_static("HRANDFIELD"),
+        Request::BulkString(key.into()),
+    ])
+}
+
+pub struct HRandFieldCommand(Vec<Request>);
+
+impl HRandFieldCommand {
+    /// Return up to `count` distinct fields, negative `count` allows the
+    /// same field to be returned multiple times.
+    pub fn count(mut self, count: i64) -> utils::BulkArrayOutputCommand {
+        self.0.push(Request::BulkInteger(count));
+        utils::BulkArrayOutputCommand(Request::Array(self.0))
+    }
+
+    /// Return up to `count` fields with their values, negative `count`
+    /// allows the same field to be returned multiple times.
+    pub fn count_with_values(mut self, count: i64) -> HRandFieldWithValuesCommand {
+        self.0.push(Request::BulkInteger(count));
+        self.0.push(Request::from_bstatic(b"WITHVALUES"));
+        HRandFieldWithValuesCommand(self.0)
+    }
+}
+
+impl Command for HRandFieldCommand {
+    type Output = Option<Bytes>;
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        utils::BulkOutputCommand::to_output(val)
+    }
+}
+
+pub struct HRandFieldWithValuesCommand(Vec<Request>);
+
+impl Command for HRandFieldWithValuesCommand {
+    type Output = Vec<(Bytes, Bytes)>;
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        let items = match val {
+            Response::Nil => return Ok(Vec::new()),
+            Response::Array(items) => items,
+            _ => return Err(CommandError::Output("Cannot parse response", val)),
+        };
+        let mut result = Vec::with_capacity(items.len() / 2);
+        let mut items = items.into_iter();
+        while let Some(field) = items.next() {
+            let value = items.next().ok_or((
+                "Cannot convert an odd number of elements into pairs",
+                Response::Nil,
+            ))?;
+            result.push((Bytes::try_from(field)?, Bytes::try_from(value)?));
+        }
+        Ok(result)
+    }
+}
+
+/// Result of the `HEXPIRE` and `HPEXPIRE` commands for a single field
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum HExpireResult {
+    /// Field does not exist
+    NotFound,
+    /// Expiration time was not set, condition is not met
+    NotSet,
+    /// Expiration time was set
+    Set,
+    /// Field was deleted, expiration time is in the past
+    Deleted,
+}
+
+/// Result of the `HPERSIST` command for a single field
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum HPersistResult {
+    /// Field does not exist
+    NotFound,
+    /// Field has no associated expiration time
+    NoExpire,
+    /// Expiration time was removed
+    Persisted,
+}
+
+/// HEXPIRE redis command
+///
+/// Set an expiration time on hash fields, available since redis 7.4.
+/// Returns a result for each of the provided fields.
+///
+/// ```rust,no_run
+/// use mco_redis::cmd;
+/// use mco_redis::connector::RedisConnector;
+/// use mco_redis::cmd::commands::HExpireResult;
+/// # use rand::{thread_rng, Rng, distributions::Alphanumeric};
+/// # fn gen_random_key() -> String {
+/// #    thread_rng().sample_iter(&Alphanumeric).take(12).map(char::from).collect::<String>()
+/// # }
+///
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // requires real redis server, HEXPIRE is not implemented by `MockServer`
+///     let redis = RedisConnector::new("127.0.0.1:6379").connect()?;
+///     let key = gen_random_key();
+///
+///     redis.exec(cmd::HSet(&key, "field1", "value1"))?;
+///
+///     let result = redis.exec(cmd::HExpire(&key, 100, "field1").field("field2"))?;
+///
+///     assert_eq!(result, vec![HExpireResult::Set, HExpireResult::NotFound]);
+///     Ok(())
+/// }
+/// ```
+pub fn HExpire<T, S, K>(key: T, seconds: S, field: K) -> HExpireCommand
+where
+    BulkString: From<T> + From<K>,
+    Seconds: From<S>,
+{
+    HFieldsCommand::new(
+        vec![
+            Request::from_static("HEXPIRE"),
+            Request::BulkString(key.into()),
+            Request::BulkInteger(Seconds::from(seconds).0),
+        ],
+        field,
+    )
+}
+
+/// HPEXPIRE redis command
+///
+/// Set an expiration time on hash fields in milliseconds, available
+/// since redis 7.4. Returns a result for each of the provided fields.
+pub fn HPExpire<T, S, K>(key: T, millis: S, field: K) -> HExpireCommand
+where
+    BulkString: From<T> + From<K>,
+    Millis: From<S>,
+{
+    HFieldsCommand::new(
+        vec![
+            Request::from_static("HPEXPIRE"),
+            Request::BulkString(key.into()),
+            Request::BulkInteger(Millis::from(millis).0),
+        ],
+        field,
+    )
+}
+
+/// HPEXPIRE redis command with `Duration` timeout
+///
+/// Set an expiration time on hash fields, fractions of a millisecond are
+/// rounded up. Returns error if timeout does not fit into redis integer.
+pub fn HExpireIn<T, K>(
+    key: T,
+    timeout: Duration,
+    field: K,
+) -> Result<HExpireCommand, TimeRangeError>
+where
+    BulkString: From<T> + From<K>,
+{
+    Ok(HPExpire(key, Millis::try_from(timeout)?, field))
+}
+
+/// Hash command with `FIELDS numfields field [field ...]` arguments
+///
+/// `R` is the result type for each of the fields.
+pub struct HFieldsCommand<R> {
+    req: Vec<Request>,
+    fields: Vec<Request>,
+    _r: PhantomData<R>,
+}
+
+pub type HExpireCommand = HFieldsCommand<HExpireResult>;
+pub type HTtlCommand = HFieldsCommand<TtlResult>;
+pub type HPTtlCommand = HFieldsCommand<PTtlResult>;
+pub type HPersistCommand = HFieldsCommand<HPersistResult>;
+
+impl<R> HFieldsCommand<R> {
+    fn new<K>(req: Vec<Request>, field: K) -> Self
+    where
+        BulkString: From<K>,
+    {
+        HFieldsCommand {
+            req,
+            fields: vec![Request::BulkString(field.into())],
+            _r: PhantomData,
+        }
+    }
+
+    /// Add a field to this command.
+    pub fn field<K>(mut self, field: K) -> Self
+    where
+        BulkString: From<K>,
+    {
+        self.fields.push(field.into());
+        self
+    }
+
+    /// Add more fields to this command.
+    pub fn fields<K>(mut self, other: impl IntoIterator<Item = K>) -> Self
+    where
+        BulkString: From<K>,
+    {
+        self.fields.extend(other.into_iter().map(|t| t.into()));
+        self
+    }
+
+    fn into_request(self) -> Request {
+        let mut req = self.req;
+        req.push(Request::from_bstatic(b"FIELDS"));
+        req.push(Request::BulkInteger(self.fields.len() as i64));
+        req.extend(self.fields);
+        Request::Array(req)
+    }
+}
+
+impl HExpireCommand {
+    /// Set expiry only when the field has no expiry.
+    pub fn if_no_expiry(mut self) -> Self {
+        self.req.push(Request::from_bstatic(b"NX"));
+        self
+    }
+
+    /// Set expiry only when the field has an existing expiry.
+    pub fn if_has_expiry(mut self) -> Self {
+        self.req.push(Request::from_bstatic(b"XX"));
+        self
+    }
+
+    /// Set expiry only when the new expiry is greater than current one.
+    pub fn if_greater(mut self) -> Self {
+        self.req.push(Request::from_bstatic(b"GT"));
+        self
+    }
+
+    /// Set expiry only when the new expiry is less than current one.
+    pub fn if_less(mut self) -> Self {
+        self.req.push(Request::from_bstatic(b"LT"));
+        self
+    }
+}
+
+impl Command for HExpireCommand {
+    type Output = Vec<HExpireResult>;
+
+    fn to_request(self) -> Request {
+        self.into_request()
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Vec::<i64>::try_from(val)?
+            .into_iter()
+            .map(|res| match res {
+                -2 => Ok(HExpireResult::NotFound),
+                0 => Ok(HExpireResult::NotSet),
+                1 => Ok(HExpireResult::Set),
+                2 => Ok(HExpireResult::Deleted),
+                _ => Err(CommandError::Output(
+                    "Unexpected value",
+                    Response::Integer(res),
+                )),
+            })
+            .collect()
+    }
+}
+
+/// HTTL redis command
+///
+/// Returns the remaining time to live of hash fields, available since
+/// redis 7.4.
+pub fn HTtl<T, K>(key: T, field: K) -> HTtlCommand
+where
+    BulkString: From<T> + From<K>,
+{
+    HFieldsCommand::new(
+        vec![
+            Request::from_static("HTTL"),
+            Request::BulkString(key.into()),
+        ],
+        field,
+    )
+}
+
+impl Command for HTtlCommand {
+    type Output = Vec<TtlResult>;
+
+    fn to_request(self) -> Request {
+        self.into_request()
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(Vec::<i64>::try_from(val)?
+            .into_iter()
+            .map(|res| match res {
+                -1 => TtlResult::NoExpire,
+                -2 => TtlResult::NotFound,
+                s => TtlResult::Seconds(s),
+            })
+            .collect())
+    }
+}
+
+/// HPTTL redis command
+///
+/// Returns the remaining time to live of hash fields in milliseconds,
+/// available since redis 7.4.
+pub fn HPTtl<T, K>(key: T, field: K) -> HPTtlCommand
+where
+    BulkString: From<T> + From<K>,
+{
+    HFieldsCommand::new(
+        vec![
+            Request::from_static("HPTTL"),
+            Request::BulkString(key.into()),
+        ],
+        field,
+    )
+}
+
+impl Command for HPTtlCommand {
+    type Output = Vec<PTtlResult>;
+
+    fn to_request(self) -> Request {
+        self.into_request()
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(Vec::<i64>::try_from(val)?
+            .into_iter()
+            .map(|res| match res {
+                -1 => PTtlResult::NoExpire,
+                -2 => PTtlResult::NotFound,
+                s => PTtlResult::Millis(s),
+            })
+            .collect())
+    }
+}
+
+/// HPERSIST redis command
+///
+/// Removes the existing expiration time of hash fields, available since
+/// redis 7.4.
+pub fn HPersist<T, K>(key: T, field: K) -> HPersistCommand
+where
+    BulkString: From<T> + From<K>,
+{
+    HFieldsCommand::new(
+        vec![
+            Request::from_static("HPERSIST"),
+            Request::BulkString(key.into()),
+        ],
+        field,
+    )
+}
+
+impl Command for HPersistCommand {
+    type Output = Vec<HPersistResult>;
+
+    fn to_request(self) -> Request {
+        self.into_request()
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Vec::<i64>::try_from(val)?
+            .into_iter()
+            .map(|res| match res {
+                -2 => Ok(HPersistResult::NotFound),
+                -1 => Ok(HPersistResult::NoExpire),
+                1 => Ok(HPersistResult::Persisted),
+                _ => Err(CommandError::Output(
+                    "Unexpected value",
+                    Response::Integer(res),
+                )),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,142 @@
+use std::convert::TryFrom;
+
+use super::{keys::KeysCommand, Command, CommandError};
+use crate::codec_redis::{BulkString, Request, Response};
+
+/// PFADD redis command
+///
+/// Adds the elements to the HyperLogLog data structure stored at key.
+/// Returns `true` if approximated cardinality was altered.
+///
+/// ```rust,no_run
+/// use mco_redis::cmd;
+/// use mco_redis::connector::RedisConnector;
+/// # use rand::{thread_rng, Rng, distributions::Alphanumeric};
+/// # fn gen_random_key() -> String {
+/// #    thread_rng().sample_iter(&Alphanumeric).take(12).map(char::from).collect::<String>()
+/// # }
+///
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // requires real redis server, PFADD and PFCOUNT are not implemented by `MockServer`
+///     let redis = RedisConnector::new("127.0.0.1:6379").connect()?;
+///     let monday = gen_random_key();
+///     let tuesday = gen_random_key();
+///
+///     // track daily active users
+///     redis.exec(cmd::PfAdd(&monday, "user:1").elements(vec!["user:2", "user:3"]))?;
+///     redis.exec(cmd::PfAdd(&tuesday, "user:3").element("user:4"))?;
+///
+///     // approximated number of unique users for both days
+///     let count = redis.exec(cmd::PfCount(&monday).key(&tuesday))?;
+///
+///     assert_eq!(count, 4);
+///     Ok(())
+/// }
+/// ```
+pub fn PfAdd<T, V>(key: T, element: V) -> PfAddCommand
+where
+    BulkString: From<T> + From<V>,
+{
+    PfAddCommand(vec![
+        Request::from_static("PFADD"),
+        Request::BulkString(key.into()),
+        Request::BulkString(element.into()),
+    ])
+}
+
+pub struct PfAddCommand(Vec<Request>);
+
+impl PfAddCommand {
+    /// Add an element to this command.
+    pub fn element<V>(mut self, element: V) -> Self
+    where
+        BulkString: From<V>,
+    {
+        self.0.push(element.into());
+        self
+    }
+
+    /// Add more elements to this command.
+    pub fn elements<V>(mut self, other: impl IntoIterator<Item = V>) -> Self
+    where
+        BulkString: From<V>,
+    {
+        self.0.extend(other.into_iter().map(|t| t.into()));
+        self
+    }
+}
+
+impl Command for PfAddCommand {
+    type Output = bool;
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(bool::try_from(val)?)
+    }
+}
+
+/// PFCOUNT redis command
+///
+/// Returns the approximated cardinality of the union of the HyperLogLogs
+/// stored at the specified keys.
+pub fn PfCount<T>(key: T) -> KeysCommand
+where
+    BulkString: From<T>,
+{
+    KeysCommand(vec![
+        Request::from_static("PFCOUNT"),
+        Request::BulkString(key.into()),
+    ])
+}
+
+/// PFMERGE redis command
+///
+/// Merges multiple HyperLogLog values into `destination` key.
+pub fn PfMerge<T, S>(destination: T, source: S) -> PfMergeCommand
+where
+    BulkString: From<T> + From<S>,
+{
+    PfMergeCommand(vec![
+        Request::from_static("PFMERGE"),
+        Request::BulkString(destination.into()),
+        Request::BulkString(source.into()),
+    ])
+}
+
+pub struct PfMergeCommand(Vec<Request>);
+
+impl PfMergeCommand {
+    /// Add a source key to this command.
+    pub fn key<T>(mut self, other: T) -> Self
+    where
+        BulkString: From<T>,
+    {
+        self.0.push(other.into());
+        self
+    }
+
+    /// Add more source keys to this command.
+    pub fn keys<T>(mut self, other: impl IntoIterator<Item = T>) -> Self
+    where
+        BulkString: From<T>,
+    {
+        self.0.extend(other.into_iter().map(|t| t.into()));
+        self
+    }
+}
+
+impl Command for PfMergeCommand {
+    type Output = ();
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(<()>::try_from(val)?)
+    }
+}
@@ -1,13 +1,17 @@
+use std::convert::TryFrom;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
 use super::{utils, Command, CommandError};
+use crate::bytes::{ByteString, Bytes};
 use crate::codec_redis::{BulkString, Request, Response};
-use std::convert::TryFrom;
 
 /// DEL redis command
 ///
 /// Removes the specified keys. A key is ignored if it does not exist.
 ///
 /// ```rust
-/// use mco_redis::{cmd, RedisConnector};
+/// use mco_redis::cmd;
+/// use mco_redis::testing::MockServer;
 /// # use rand::{thread_rng, Rng, distributions::Alphanumeric};
 /// # fn gen_random_key() -> String {
 /// #    thread_rng().sample_iter(&Alphanumeric).take(12).map(char::from).collect::<String>()
@@ -15,7 +19,8 @@ use std::convert::TryFrom;
 ///
 ///
 /// fn main() -> Result<(), Box<dyn std::error::Error>> {
-///     let redis = RedisConnector::new("127.0.0.1:6379").connect()?;
+///     let server = MockServer::start()?;
+///     let redis = server.connector().connect()?;
 ///     let key = gen_random_key();
 ///
 ///     // set string value
@@ -45,7 +50,8 @@ where
 /// Returns if key exists.
 ///
 /// ```rust
-/// use mco_redis::{cmd, RedisConnector};
+/// use mco_redis::cmd;
+/// use mco_redis::testing::MockServer;
 /// # use rand::{thread_rng, Rng, distributions::Alphanumeric};
 /// # fn gen_random_key() -> String {
 /// #    thread_rng().sample_iter(&Alphanumeric).take(12).map(char::from).collect::<String>()
@@ -53,7 +59,8 @@ where
 ///
 ///
 /// fn main() -> Result<(), Box<dyn std::error::Error>> {
-///     let redis = RedisConnector::new("127.0.0.1:6379").connect()?;
+///     let server = MockServer::start()?;
+///     let redis = server.connector().connect()?;
 ///     let key = gen_random_key();
 ///
 ///     // set string value
@@ -78,7 +85,7 @@ where
     ])
 }
 
-pub struct KeysCommand(Vec<Request>);
+pub struct KeysCommand(pub(crate) Vec<Request>);
 
 impl KeysCommand {
     /// Add a key to this command.
@@ -115,33 +122,266 @@ impl Command for KeysCommand {
     }
 }
 
+/// Time value in seconds
+///
+/// Integers are used as is. `Duration` and `SystemTime` are converted with
+/// `TryFrom`, fractions of a second are rounded up so that a short timeout
+/// does not become `0`. `SystemTime` is converted to a Unix timestamp,
+/// which is useful for `EXPIREAT` command.
+///
+/// ```rust
+/// use std::convert::TryFrom;
+/// use std::time::Duration;
+/// use mco_redis::cmd::Seconds;
+///
+/// assert_eq!(Seconds::try_from(Duration::from_millis(500)).unwrap(), Seconds(1));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Seconds(pub i64);
+
+/// Time value in milliseconds
+///
+/// Integers are used as is. `Duration` and `SystemTime` are converted with
+/// `TryFrom`, fractions of a millisecond are rounded up. `SystemTime` is
+/// converted to a Unix timestamp in milliseconds, which is useful for
+/// `PEXPIREAT` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Millis(pub i64);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, derive_more::Display)]
+/// Time value does not fit into redis integer
+#[display(fmt = "Time value is out of range")]
+pub struct TimeRangeError;
+
+impl std::error::Error for TimeRangeError {}
+
+macro_rules! impl_from_integers {
+    ($tp:ident, $($int_ty:ident),* $(,)*) => {
+        $(
+            impl From<$int_ty> for $tp {
+                fn from(val: $int_ty) -> $tp {
+                    $tp(i64::from(val))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integers!(Seconds, i8, i16, i32, i64, u8, u16, u32);
+impl_from_integers!(Millis, i8, i16, i32, i64, u8, u16, u32);
+
+/// Nanoseconds to `unit`, rounded up
+fn nanos_to(nanos: i128, unit: i128) -> Result<i64, TimeRangeError> {
+    let val = nanos.div_euclid(unit) + i128::from(nanos.rem_euclid(unit) > 0);
+    i64::try_from(val).map_err(|_| TimeRangeError)
+}
+
+/// Nanoseconds since Unix epoch, negative for earlier time
+fn unix_nanos(val: SystemTime) -> i128 {
+    match val.duration_since(UNIX_EPOCH) {
+        Ok(d) => d.as_nanos() as i128,
+        Err(e) => -(e.duration().as_nanos() as i128),
+    }
+}
+
+impl TryFrom<Duration> for Seconds {
+    type Error = TimeRangeError;
+
+    fn try_from(val: Duration) -> Result<Seconds, TimeRangeError> {
+        nanos_to(val.as_nanos() as i128, 1_000_000_000).map(Seconds)
+    }
+}
+
+impl TryFrom<Duration> for Millis {
+    type Error = TimeRangeError;
+
+    fn try_from(val: Duration) -> Result<Millis, TimeRangeError> {
+        nanos_to(val.as_nanos() as i128, 1_000_000).map(Millis)
+    }
+}
+
+impl TryFrom<SystemTime> for Seconds {
+    type Error = TimeRangeError;
+
+    fn try_from(val: SystemTime) -> Result<Seconds, TimeRangeError> {
+        nanos_to(unix_nanos(val), 1_000_000_000).map(Seconds)
+    }
+}
+
+impl TryFrom<SystemTime> for Millis {
+    type Error = TimeRangeError;
+
+    fn try_from(val: SystemTime) -> Result<Millis, TimeRangeError> {
+        nanos_to(unix_nanos(val), 1_000_000).map(Millis)
+    }
+}
+
 /// EXPIRE redis command
 ///
-/// Set a timeout on `key`.
-pub fn Expire<T, S>(key: T, seconds: S) -> utils::BoolOutputCommand
+/// Set a timeout on `key`. Timeout could be specified as a number of
+/// seconds or as `Seconds` converted from a `Duration`, use `ExpireIn`
+/// to pass `Duration` directly.
+///
+/// ```rust,no_run
+/// use std::convert::TryFrom;
+/// use std::time::Duration;
+/// use mco_redis::cmd::{self, Seconds};
+/// use mco_redis::connector::RedisConnector;
+/// # use rand::{thread_rng, Rng, distributions::Alphanumeric};
+/// # fn gen_random_key() -> String {
+/// #    thread_rng().sample_iter(&Alphanumeric).take(12).map(char::from).collect::<String>()
+/// # }
+///
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // requires real redis server, EXPIRE options are not implemented by `MockServer`
+///     let redis = RedisConnector::new("127.0.0.1:6379").connect()?;
+///     let key = gen_random_key();
+///
+///     redis.exec(cmd::Set(&key, "value"))?;
+///
+///     // set timeout only if key has no timeout
+///     let value = redis.exec(
+///         cmd::Expire(&key, Seconds::try_from(Duration::from_secs(100))?).if_no_expiry()
+///     )?;
+///
+///     assert!(value);
+///     Ok(())
+/// }
+/// ```
+pub fn Expire<T, S>(key: T, seconds: S) -> ExpireCommand
 where
     BulkString: From<T>,
-    i64: From<S>,
+    Seconds: From<S>,
 {
-    utils::BoolOutputCommand(Request::Array(vec![
+    ExpireCommand(vec![
         Request::from_static("EXPIRE"),
         Request::BulkString(key.into()),
-        Request::BulkString(i64::from(seconds).to_string().into()),
-    ]))
+        Request::BulkInteger(Seconds::from(seconds).0),
+    ])
 }
 
 /// EXPIREAT redis command
 ///
-/// Set a timeout on `key`.
-pub fn ExpireAt<T, S>(key: T, timestamp: S) -> utils::BoolOutputCommand
+/// Set a timeout on `key`. Timeout could be specified as a Unix
+/// timestamp in seconds or as `Seconds` converted from a `SystemTime`,
+/// use `ExpireAtTime` to pass `SystemTime` directly.
+pub fn ExpireAt<T, S>(key: T, timestamp: S) -> ExpireCommand
 where
     BulkString: From<T>,
-    i64: From<S>,
+    Seconds: From<S>,
 {
-    utils::BoolOutputCommand(Request::Array(vec![
+    ExpireCommand(vec![
         Request::from_static("EXPIREAT"),
         Request::BulkString(key.into()),
-        Request::BulkString(i64::from(timestamp).to_string().into()),
+        Request::BulkInteger(Seconds::from(timestamp).0),
+    ])
+}
+
+/// PEXPIRE redis command
+///
+/// Set a timeout on `key`. Timeout could be specified as a number of
+/// milliseconds or as `Millis` converted from a `Duration`.
+pub fn PExpire<T, S>(key: T, millis: S) -> ExpireCommand
+where
+    BulkString: From<T>,
+    Millis: From<S>,
+{
+    ExpireCommand(vec![
+        Request::from_static("PEXPIRE"),
+        Request::BulkString(key.into()),
+        Request::BulkInteger(Millis::from(millis).0),
+    ])
+}
+
+/// PEXPIREAT redis command
+///
+/// Set a timeout on `key`. Timeout could be specified as a Unix
+/// timestamp in milliseconds or as `Millis` converted from a `SystemTime`.
+pub fn PExpireAt<T, S>(key: T, timestamp: S) -> ExpireCommand
+where
+    BulkString: From<T>,
+    Millis: From<S>,
+{
+    ExpireCommand(vec![
+        Request::from_static("PEXPIREAT"),
+        Request::BulkString(key.into()),
+        Request::BulkInteger(Millis::from(timestamp).0),
+    ])
+}
+
+/// PEXPIRE redis command with `Duration` timeout
+///
+/// Set a timeout on `key`, fractions of a millisecond are rounded up.
+/// Returns error if timeout does not fit into redis integer.
+pub fn ExpireIn<T>(key: T, timeout: Duration) -> Result<ExpireCommand, TimeRangeError>
+where
+    BulkString: From<T>,
+{
+    Ok(PExpire(key, Millis::try_from(timeout)?))
+}
+
+/// PEXPIREAT redis command with `SystemTime` timestamp
+///
+/// Set a timeout on `key`, fractions of a millisecond are rounded up.
+/// Returns error if timestamp does not fit into redis integer.
+pub fn ExpireAtTime<T>(key: T, time: SystemTime) -> Result<ExpireCommand, TimeRangeError>
+where
+    BulkString: From<T>,
+{
+    Ok(PExpireAt(key, Millis::try_from(time)?))
+}
+
+pub struct ExpireCommand(Vec<Request>);
+
+impl ExpireCommand {
+    /// Set expiry only when the key has no expiry.
+    pub fn if_no_expiry(mut self) -> Self {
+        self.0.push(Request::from_bstatic(b"NX"));
+        self
+    }
+
+    /// Set expiry only when the key has an existing expiry.
+    pub fn if_has_expiry(mut self) -> Self {
+        self.0.push(Request::from_bstatic(b"XX"));
+        self
+    }
+
+    /// Set expiry only when the new expiry is greater than current one.
+    pub fn if_greater(mut self) -> Self {
+        self.0.push(Request::from_bstatic(b"GT"));
+        self
+    }
+
+    /// Set expiry only when the new expiry is less than current one.
+    pub fn if_less(mut self) -> Self {
+        self.0.push(Request::from_bstatic(b"LT"));
+        self
+    }
+}
+
+impl Command for ExpireCommand {
+    type Output = bool;
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(bool::try_from(val)?)
+    }
+}
+
+/// PERSIST redis command
+///
+/// Remove the existing timeout on `key`.
+pub fn Persist<T>(key: T) -> utils::BoolOutputCommand
+where
+    BulkString: From<T>,
+{
+    utils::BoolOutputCommand(Request::Array(vec![
+        Request::from_static("PERSIST"),
+        Request::BulkString(key.into()),
     ]))
 }
 
@@ -158,6 +398,20 @@ where
     ])
 }
 
+/// EXPIRETIME redis command
+///
+/// Returns the absolute Unix timestamp in seconds at which the given
+/// `key` will expire.
+pub fn ExpireTime<T>(key: T) -> ExpireTimeCommand
+where
+    BulkString: From<T>,
+{
+    ExpireTimeCommand(vec![
+        Request::from_static("EXPIRETIME"),
+        Request::BulkString(key.into()),
+    ])
+}
+
 #[derive(Debug, PartialEq)]
 pub enum TtlResult {
     Seconds(i64),
@@ -183,3 +437,582 @@ impl Command for TtlCommand {
         })
     }
 }
+
+/// PTTL redis command
+///
+/// Returns the remaining time to live of a `key` that has a timeout,
+/// in milliseconds.
+pub fn PTtl<T>(key: T) -> PTtlCommand
+where
+    BulkString: From<T>,
+{
+    PTtlCommand(vec![
+        Request::from_static("PTTL"),
+        Request::BulkString(key.into()),
+    ])
+}
+
+/// PEXPIRETIME redis command
+///
+/// Returns the absolute Unix timestamp in milliseconds at which the given
+/// `key` will expire.
+pub fn PExpireTime<T>(key: T) -> PExpireTimeCommand
+where
+    BulkString: From<T>,
+{
+    PExpireTimeCommand(vec![
+        Request::from_static("PEXPIRETIME"),
+        Request::BulkString(key.into()),
+    ])
+}
+
+#[derive(Debug, PartialEq)]
+pub enum PTtlResult {
+    Millis(i64),
+    NoExpire,
+    NotFound,
+}
+
+pub struct PTtlCommand(Vec<Request>);
+
+impl Command for PTtlCommand {
+    type Output = PTtlResult;
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        let result = i64::try_from(val)?;
+        Ok(match result {
+            -1 => PTtlResult::NoExpire,
+            -2 => PTtlResult::NotFound,
+            s => PTtlResult::Millis(s),
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+/// Output of `EXPIRETIME` and `PEXPIRETIME` commands
+pub enum ExpireTimeResult {
+    /// Time at which the key will expire
+    At(SystemTime),
+    NoExpire,
+    NotFound,
+}
+
+fn expire_time(val: Response, millis: bool) -> Result<ExpireTimeResult, CommandError> {
+    Ok(match i64::try_from(val)? {
+        -1 => ExpireTimeResult::NoExpire,
+        -2 => ExpireTimeResult::NotFound,
+        t if t >= 0 => ExpireTimeResult::At(
+            UNIX_EPOCH
+                + if millis {
+                    Duration::from_millis(t as u64)
+                } else {
+                    Duration::from_secs(t as u64)
+                },
+        ),
+        t => {
+            return Err(CommandError::Output(
+                "Invalid expire time",
+                Response::Integer(t),
+            ))
+        }
+    })
+}
+
+pub struct ExpireTimeCommand(Vec<Request>);
+
+impl Command for ExpireTimeCommand {
+    type Output = ExpireTimeResult;
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        expire_time(val, false)
+    }
+}
+
+pub struct PExpireTimeCommand(Vec<Request>);
+
+impl Command for PExpireTimeCommand {
+    type Output = ExpireTimeResult;
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        expire_time(val, true)
+    }
+}
+
+/// UNLINK redis command
+///
+/// Removes the specified keys, actual memory reclaiming is performed
+/// asynchronously by the server.
+pub fn Unlink<T>(key: T) -> KeysCommand
+where
+    BulkString: From<T>,
+{
+    KeysCommand(vec![
+        Request::from_static("UNLINK"),
+        Request::BulkString(key.into()),
+    ])
+}
+
+/// TOUCH redis command
+///
+/// Alters the last access time of a key(s). Returns the number of keys
+/// that were touched.
+pub fn Touch<T>(key: T) -> KeysCommand
+where
+    BulkString: From<T>,
+{
+    KeysCommand(vec![
+        Request::from_static("TOUCH"),
+        Request::BulkString(key.into()),
+    ])
+}
+
+/// Type of the value stored at key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyType {
+    /// Key does not exist
+    None,
+    String,
+    List,
+    Set,
+    ZSet,
+    Hash,
+    Stream,
+    /// Type that is not known to the client, i.e. a module type
+    Other(ByteString),
+}
+
+/// TYPE redis command
+///
+/// Returns the type of the value stored at `key`.
+///
+/// ```rust
+/// use mco_redis::cmd;
+/// use mco_redis::testing::MockServer;
+/// use mco_redis::cmd::commands::KeyType;
+/// # use rand::{thread_rng, Rng, distributions::Alphanumeric};
+/// # fn gen_random_key() -> String {
+/// #    thread_rng().sample_iter(&Alphanumeric).take(12).map(char::from).collect::<String>()
+/// # }
+///
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let server = MockServer::start()?;
+///     let redis = server.connector().connect()?;
+///     let key = gen_random_key();
+///
+///     redis.exec(cmd::LPush(&key, "value"))?;
+///
+///     let tp = redis.exec(cmd::Type(&key))?;
+///
+///     assert_eq!(tp, KeyType::List);
+///     Ok(())
+/// }
+/// ```
+pub fn Type<T>(key: T) -> TypeCommand
+where
+    BulkString: From<T>,
+{
+    TypeCommand(Request::Array(vec![
+        Request::from_static("TYPE"),
+        Request::BulkString(key.into()),
+    ]))
+}
+
+pub struct TypeCommand(Request);
+
+impl Command for TypeCommand {
+    type Output = KeyType;
+
+    fn to_request(self) -> Request {
+        self.0
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        let tp = ByteString::try_from(val)?;
+        Ok(match tp.as_ref() {
+            "none" => KeyType::None,
+            "string" => KeyType::String,
+            "list" => KeyType::List,
+            "set" => KeyType::Set,
+            "zset" => KeyType::ZSet,
+            "hash" => KeyType::Hash,
+            "stream" => KeyType::Stream,
+            _ => KeyType::Other(tp),
+        })
+    }
+}
+
+/// RENAME redis command
+///
+/// Renames `key` to `newkey`, `newkey` is overwritten if it already exists.
+pub fn Rename<T, K>(key: T, newkey: K) -> utils::OkOutputCommand
+where
+    BulkString: From<T> + From<K>,
+{
+    utils::OkOutputCommand(Request::Array(vec![
+        Request::from_static("RENAME"),
+        Request::BulkString(key.into()),
+        Request::BulkString(newkey.into()),
+    ]))
+}
+
+/// RENAMENX redis command
+///
+/// Renames `key` to `newkey` if `newkey` does not yet exist. Command
+/// returns true if key was renamed.
+pub fn RenameNx<T, K>(key: T, newkey: K) -> utils::BoolOutputCommand
+where
+    BulkString: From<T> + From<K>,
+{
+    utils::BoolOutputCommand(Request::Array(vec![
+        Request::from_static("RENAMENX"),
+        Request::BulkString(key.into()),
+        Request::BulkString(newkey.into()),
+    ]))
+}
+
+/// COPY redis command
+///
+/// Copies the value stored at the `source` key to the `destination` key.
+/// Command returns true if value was copied.
+pub fn Copy<T, K>(source: T, destination: K) -> CopyCommand
+where
+    BulkString: From<T> + From<K>,
+{
+    CopyCommand(vec![
+        Request::from_static("COPY"),
+        Request::BulkString(source.into()),
+        Request::BulkString(destination.into()),
+    ])
+}
+
+pub struct CopyCommand(Vec<Request>);
+
+impl CopyCommand {
+    /// Copy value to the specified logical database.
+    pub fn db(mut self, db: u32) -> Self {
+        self.0.push(Request::from_bstatic(b"DB"));
+        self.0.push(Request::BulkInteger(db as i64));
+        self
+    }
+
+    /// Remove the destination key before copying the value to it.
+    pub fn replace(mut self) -> Self {
+        self.0.push(Request::from_bstatic(b"REPLACE"));
+        self
+    }
+}
+
+impl Command for CopyCommand {
+    type Output = bool;
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(bool::try_from(val)?)
+    }
+}
+
+/// MOVE redis command
+///
+/// Move `key` from the currently selected database to the specified
+/// destination database.
+pub fn Move<T>(key: T, db: u32) -> utils::BoolOutputCommand
+where
+    BulkString: From<T>,
+{
+    utils::BoolOutputCommand(Request::Array(vec![
+        Request::from_static("MOVE"),
+        Request::BulkString(key.into()),
+        Request::BulkInteger(db as i64),
+    ]))
+}
+
+/// RANDOMKEY redis command
+///
+/// Returns a random key from the currently selected database.
+pub fn RandomKey() -> utils::BulkOutputCommand {
+    utils::BulkOutputCommand(Request::Array(vec![Request::from_static("RANDOMKEY")]))
+}
+
+/// OBJECT ENCODING redis command
+///
+/// Returns the internal encoding for the object stored at `key`.
+pub fn ObjectEncoding<T>(key: T) -> utils::BulkOutputCommand
+where
+    BulkString: From<T>,
+{
+    utils::BulkOutputCommand(Request::Array(vec![
+        Request::from_static("OBJECT"),
+        Request::from_static("ENCODING"),
+        Request::BulkString(key.into()),
+    ]))
+}
+
+/// OBJECT FREQ redis command
+///
+/// Returns the logarithmic access frequency counter of the object
+/// stored at `key`.
+pub fn ObjectFreq<T>(key: T) -> utils::IntOutputCommand
+where
+    BulkString: From<T>,
+{
+    utils::IntOutputCommand(Request::Array(vec![
+        Request::from_static("OBJECT"),
+        Request::from_static("FREQ"),
+        Request::BulkString(key.into()),
+    ]))
+}
+
+/// OBJECT IDLETIME redis command
+///
+/// Returns the time in seconds since the last access to the value
+/// stored at `key`.
+pub fn ObjectIdleTime<T>(key: T) -> utils::IntOutputCommand
+where
+    BulkString: From<T>,
+{
+    utils::IntOutputCommand(Request::Array(vec![
+        Request::from_static("OBJECT"),
+        Request::from_static("IDLETIME"),
+        Request::BulkString(key.into()),
+    ]))
+}
+
+/// DUMP redis command
+///
+/// Serialize the value stored at `key` in a Redis-specific format.
+pub fn Dump<T>(key: T) -> utils::BulkOutputCommand
+where
+    BulkString: From<T>,
+{
+    utils::BulkOutputCommand(Request::Array(vec![
+        Request::from_static("DUMP"),
+        Request::BulkString(key.into()),
+    ]))
+}
+
+/// RESTORE redis command
+///
+/// Create a `key` associated with a value that is obtained by
+/// deserializing the provided serialized value (obtained via `DUMP`).
+/// If `ttl` is 0 the key is created without any expire.
+///
+/// ```rust,no_run
+/// use mco_redis::cmd;
+/// use mco_redis::connector::RedisConnector;
+/// # use rand::{thread_rng, Rng, distributions::Alphanumeric};
+/// # fn gen_random_key() -> String {
+/// #    thread_rng().sample_iter(&Alphanumeric).take(12).map(char::from).collect::<String>()
+/// # }
+///
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // requires real redis server, DUMP and RESTORE are not implemented by `MockServer`
+///     let redis = RedisConnector::new("127.0.0.1:6379").connect()?;
+///     let key = gen_random_key();
+///     let key2 = gen_random_key();
+///
+///     redis.exec(cmd::Set(&key, "value"))?;
+///
+///     // copy value with dump and restore
+///     let data = redis.exec(cmd::Dump(&key))?.unwrap();
+///     redis.exec(cmd::Restore(&key2, 0, data).replace())?;
+///
+///     let value = redis.exec(cmd::Get(&key2))?;
+///     assert_eq!(value.unwrap(), "value");
+///     Ok(())
+/// }
+/// ```
+pub fn Restore<T, S, V>(key: T, ttl: S, value: V) -> RestoreCommand
+where
+    BulkString: From<T> + From<V>,
+    Millis: From<S>,
+{
+    RestoreCommand(vec![
+        Request::from_static("RESTORE"),
+        Request::BulkString(key.into()),
+        Request::BulkInteger(Millis::from(ttl).0),
+        Request::BulkString(value.into()),
+    ])
+}
+
+pub struct RestoreCommand(Vec<Request>);
+
+impl RestoreCommand {
+    /// Replace existing key.
+    pub fn replace(mut self) -> Self {
+        self.0.push(Request::from_bstatic(b"REPLACE"));
+        self
+    }
+
+    /// Treat `ttl` as an absolute Unix timestamp in milliseconds.
+    pub fn absttl(mut self) -> Self {
+        self.0.push(Request::from_bstatic(b"ABSTTL"));
+        self
+    }
+
+    /// Set object idle time, in seconds.
+    pub fn idletime(mut self, secs: i64) -> Self {
+        self.0.push(Request::from_bstatic(b"IDLETIME"));
+        self.0.push(Request::BulkInteger(secs));
+        self
+    }
+
+    /// Set object access frequency counter.
+    pub fn freq(mut self, freq: u8) -> Self {
+        self.0.push(Request::from_bstatic(b"FREQ"));
+        self.0.push(Request::BulkInteger(freq as i64));
+        self
+    }
+}
+
+impl Command for RestoreCommand {
+    type Output = ();
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(<()>::try_from(val)?)
+    }
+}
+
+/// SORT redis command
+///
+/// Returns the elements contained in the list, set or sorted set at `key`.
+///
+/// ```rust,no_run
+/// use mco_redis::cmd;
+/// use mco_redis::connector::RedisConnector;
+/// # use rand::{thread_rng, Rng, distributions::Alphanumeric};
+/// # fn gen_random_key() -> String {
+/// #    thread_rng().sample_iter(&Alphanumeric).take(12).map(char::from).collect::<String>()
+/// # }
+///
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // requires real redis server, SORT is not implemented by `MockServer`
+///     let redis = RedisConnector::new("127.0.0.1:6379").connect()?;
+///     let key = gen_random_key();
+///
+///     redis.exec(cmd::LPush(&key, "b").extend(vec!["c", "a"]))?;
+///
+///     // sort list lexicographically
+///     let values = redis.exec(cmd::Sort(&key).alpha().limit(0, 2))?;
+///
+///     assert_eq!(values[0].as_ref().unwrap(), "a");
+///     assert_eq!(values[1].as_ref().unwrap(), "b");
+///     Ok(())
+/// }
+/// ```
+pub fn Sort<T>(key: T) -> SortCommand
+where
+    BulkString: From<T>,
+{
+    SortCommand(vec![
+        Request::from_static("SORT"),
+        Request::BulkString(key.into()),
+    ])
+}
+
+/// SORT_RO redis command
+///
+/// Read-only variant of the `SORT` command.
+pub fn SortRo<T>(key: T) -> SortCommand<true>
+where
+    BulkString: From<T>,
+{
+    SortCommand(vec![
+        Request::from_static("SORT_RO"),
+        Request::BulkString(key.into()),
+    ])
+}
+
+/// `SORT` command, or `SORT_RO` if `READ_ONLY` is true
+pub struct SortCommand<const READ_ONLY: bool = false>(Vec<Request>);
+
+impl<const READ_ONLY: bool> SortCommand<READ_ONLY> {
+    /// Use external keys matching `pattern` as weights for sorting.
+    pub fn by<T>(mut self, pattern: T) -> Self
+    where
+        BulkString: From<T>,
+    {
+        self.0.push(Request::from_bstatic(b"BY"));
+        self.0.push(pattern.into());
+        self
+    }
+
+    /// Limit the number of returned elements.
+    pub fn limit(mut self, offset: i64, count: i64) -> Self {
+        self.0.push(Request::from_bstatic(b"LIMIT"));
+        self.0.push(Request::BulkInteger(offset));
+        self.0.push(Request::BulkInteger(count));
+        self
+    }
+
+    /// Retrieve external keys matching `pattern` instead of the elements.
+    pub fn get<T>(mut self, pattern: T) -> Self
+    where
+        BulkString: From<T>,
+    {
+        self.0.push(Request::from_bstatic(b"GET"));
+        self.0.push(pattern.into());
+        self
+    }
+
+    /// Sort in descending order.
+    pub fn desc(mut self) -> Self {
+        self.0.push(Request::from_bstatic(b"DESC"));
+        self
+    }
+
+    /// Sort elements lexicographically.
+    pub fn alpha(mut self) -> Self {
+        self.0.push(Request::from_bstatic(b"ALPHA"));
+        self
+    }
+
+}
+
+impl SortCommand {
+    /// Store result at `destination` key.
+    ///
+    /// Changes command output to the number of stored elements. `SORT_RO`
+    /// does not support this option.
+    pub fn store<T>(mut self, destination: T) -> utils::IntOutputCommand
+    where
+        BulkString: From<T>,
+    {
+        self.0.push(Request::from_bstatic(b"STORE"));
+        self.0.push(destination.into());
+        utils::IntOutputCommand(Request::Array(self.0))
+    }
+}
+
+impl<const READ_ONLY: bool> Command for SortCommand<READ_ONLY> {
+    type Output = Vec<Option<Bytes>>;
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        utils::MultiBulkOutputCommand::to_output(val)
+    }
+}
@@ -1,4 +1,9 @@
-use super::{utils, Command, CommandError};
+use std::convert::TryFrom;
+use std::time::Duration;
+
+use super::{utils, BlockingCommand, Command, CommandError};
+use crate::args::{ToArg, ToArgs};
+use crate::bytes::Bytes;
 use crate::codec_redis::{BulkString, Request, Response};
 
 /// LINDEX redis command
@@ -6,7 +11,8 @@ use crate::codec_redis::{BulkString, Request, Response};
 /// Returns the element at index index in the list stored at key.
 ///
 /// ```rust
-/// use mco_redis::{cmd, RedisConnector};
+/// use mco_redis::cmd;
+/// use mco_redis::testing::MockServer;
 /// # use rand::{thread_rng, Rng, distributions::Alphanumeric};
 /// # fn gen_random_key() -> String {
 /// #    thread_rng().sample_iter(&Alphanumeric).take(12).map(char::from).collect::<String>()
@@ -14,7 +20,8 @@ use crate::codec_redis::{BulkString, Request, Response};
 ///
 ///
 /// fn main() -> Result<(), Box<dyn std::error::Error>> {
-///     let redis = RedisConnector::new("127.0.0.1:6379").connect()?;
+///     let server = MockServer::start()?;
+///     let redis = server.connector().connect()?;
 ///     let key = gen_random_key();
 ///
 ///     // create list with one value
@@ -43,7 +50,8 @@ where
 /// Removes and returns the first element of the list stored at key.
 ///
 /// ```rust
-/// use mco_redis::{cmd, RedisConnector};
+/// use mco_redis::cmd;
+/// use mco_redis::testing::MockServer;
 /// # use rand::{thread_rng, Rng, distributions::Alphanumeric};
 /// # fn gen_random_key() -> String {
 /// #    thread_rng().sample_iter(&Alphanumeric).take(12).map(char::from).collect::<String>()
@@ -51,7 +59,8 @@ where
 ///
 ///
 /// fn main() -> Result<(), Box<dyn std::error::Error>> {
-///     let redis = RedisConnector::new("127.0.0.1:6379").connect()?;
+///     let server = MockServer::start()?;
+///     let redis = server.connector().connect()?;
 ///     let key = gen_random_key();
 ///
 ///     // create list with one value
@@ -64,14 +73,14 @@ where
 ///     Ok(())
 /// }
 /// ```
-pub fn LPop<T>(key: T) -> utils::BulkOutputCommand
+pub fn LPop<T>(key: T) -> PopCommand
 where
     BulkString: From<T>,
 {
-    utils::BulkOutputCommand(Request::Array(vec![
+    PopCommand(vec![
         Request::from_static("LPOP"),
         Request::BulkString(key.into()),
-    ]))
+    ])
 }
 
 /// RPOP redis command
@@ -79,7 +88,8 @@ where
 /// Removes and returns the last element of the list stored at key.
 ///
 /// ```rust
-/// use mco_redis::{cmd, RedisConnector};
+/// use mco_redis::cmd;
+/// use mco_redis::testing::MockServer;
 /// # use rand::{thread_rng, Rng, distributions::Alphanumeric};
 /// # fn gen_random_key() -> String {
 /// #    thread_rng().sample_iter(&Alphanumeric).take(12).map(char::from).collect::<String>()
@@ -87,7 +97,8 @@ where
 ///
 ///
 /// fn main() -> Result<(), Box<dyn std::error::Error>> {
-///     let redis = RedisConnector::new("127.0.0.1:6379").connect()?;
+///     let server = MockServer::start()?;
+///     let redis = server.connector().connect()?;
 ///     let key = gen_random_key();
 ///
 ///     // create list with one value
@@ -100,14 +111,63 @@ where
 ///     Ok(())
 /// }
 /// ```
-pub fn RPop<T>(key: T) -> utils::BulkOutputCommand
+pub fn RPop<T>(key: T) -> PopCommand
 where
     BulkString: From<T>,
 {
-    utils::BulkOutputCommand(Request::Array(vec![
+    PopCommand(vec![
         Request::from_static("RPOP"),
         Request::BulkString(key.into()),
-    ]))
+    ])
+}
+
+pub struct PopCommand(Vec<Request>);
+
+impl PopCommand {
+    /// Pop up to `count` elements.
+    ///
+    /// Changes command output to the list of popped elements, the list is
+    /// empty if key does not exist.
+    ///
+    /// ```rust
+    /// use mco_redis::cmd;
+    /// use mco_redis::testing::MockServer;
+    /// # use rand::{thread_rng, Rng, distributions::Alphanumeric};
+    /// # fn gen_random_key() -> String {
+    /// #    thread_rng().sample_iter(&Alphanumeric).take(12).map(char::from).collect::<String>()
+    /// # }
+    ///
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let server = MockServer::start()?;
+    ///     let redis = server.connector().connect()?;
+    ///     let key = gen_random_key();
+    ///
+    ///     redis.exec(cmd::RPush(&key, "value1").value("value2"))?;
+    ///
+    ///     // pop two elements from the list
+    ///     let values = redis.exec(cmd::LPop(&key).count(2))?;
+    ///
+    ///     assert_eq!(values, vec!["value1", "value2"]);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn count(mut self, count: usize) -> utils::BulkArrayOutputCommand {
+        self.0.push(Request::BulkInteger(count as i64));
+        utils::BulkArrayOutputCommand(Request::Array(self.0))
+    }
+}
+
+impl Command for PopCommand {
+    type Output = Option<Bytes>;
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        utils::BulkOutputCommand::to_output(val)
+    }
 }
 
 /// LPUSH redis command
@@ -115,7 +175,8 @@ where
 /// Insert all the specified values at the head of the list stored at key.
 ///
 /// ```rust
-/// use mco_redis::{cmd, RedisConnector};
+/// use mco_redis::cmd;
+/// use mco_redis::testing::MockServer;
 /// # use rand::{thread_rng, Rng, distributions::Alphanumeric};
 /// # fn gen_random_key() -> String {
 /// #    thread_rng().sample_iter(&Alphanumeric).take(12).map(char::from).collect::<String>()
@@ -123,7 +184,8 @@ where
 ///
 ///
 /// fn main() -> Result<(), Box<dyn std::error::Error>> {
-///     let redis = RedisConnector::new("127.0.0.1:6379").connect()?;
+///     let server = MockServer::start()?;
+///     let redis = server.connector().connect()?;
 ///     let key = gen_random_key();
 ///
 ///     // create list with one value
@@ -138,8 +200,9 @@ where
 ///
 /// `LPushCommand::if_exists()` method changes `LPUSH` command to `LPUSHX` command
 ///
-/// ```rust
-/// # use mco_redis::{cmd, RedisConnector};
+/// ```rust,no_run
+/// # use mco_redis::cmd;
+/// # use mco_redis::connector::RedisConnector;
 /// # use rand::{thread_rng, Rng, distributions::Alphanumeric};
 /// # fn gen_random_key() -> String {
 /// #    thread_rng().sample_iter(&Alphanumeric).take(12).map(char::from).collect::<String>()
@@ -147,6 +210,7 @@ where
 ///
 ///
 /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // requires real redis server, LPUSHX is not implemented by `MockServer`
 ///     let redis = RedisConnector::new("127.0.0.1:6379").connect()?;
 ///     let key = gen_random_key();
 ///
@@ -162,13 +226,15 @@ where
 /// ```
 pub fn LPush<T, V>(key: T, value: V) -> LPushCommand
 where
-    BulkString: From<T> + From<V>,
+    BulkString: From<T>,
+    V: ToArg,
 {
-    LPushCommand(vec![
+    let req = vec![
         Request::from_static("LPUSH"),
         Request::BulkString(key.into()),
-        Request::BulkString(value.into()),
-    ])
+        value.to_arg(),
+    ];
+    LPushCommand(req)
 }
 
 /// RPUSH redis command
@@ -176,13 +242,15 @@ where
 /// Insert all the specified values at the tail of the list stored at key.
 pub fn RPush<T, V>(key: T, value: V) -> LPushCommand
 where
-    BulkString: From<T> + From<V>,
+    BulkString: From<T>,
+    V: ToArg,
 {
-    LPushCommand(vec![
+    let req = vec![
         Request::from_static("RPUSH"),
         Request::BulkString(key.into()),
-        Request::BulkString(value.into()),
-    ])
+        value.to_arg(),
+    ];
+    LPushCommand(req)
 }
 
 pub struct LPushCommand(Vec<Request>);
@@ -204,20 +272,16 @@ impl LPushCommand {
     }
 
     /// Add a value to this command.
-    pub fn value<T>(mut self, other: T) -> Self
-    where
-        BulkString: From<T>,
-    {
-        self.0.push(other.into());
+    pub fn value<T: ToArg>(mut self, other: T) -> Self {
+        self.0.push(other.to_arg());
         self
     }
 
     /// Add more values to this command.
-    pub fn extend<T>(mut self, other: impl IntoIterator<Item = T>) -> Self
-    where
-        BulkString: From<T>,
-    {
-        self.0.extend(other.into_iter().map(|t| t.into()));
+    pub fn extend<T: ToArgs>(mut self, other: impl IntoIterator<Item = T>) -> Self {
+        for value in other {
+            value.write_args(&mut self.0);
+        }
         self
     }
 }
@@ -236,3 +300,641 @@ impl Command for LPushCommand {
         }
     }
 }
+
+/// LLEN redis command
+///
+/// Returns the length of the list stored at key.
+pub fn LLen<T>(key: T) -> utils::IntOutputCommand
+where
+    BulkString: From<T>,
+{
+    utils::IntOutputCommand(Request::Array(vec![
+        Request::from_static("LLEN"),
+        Request::BulkString(key.into()),
+    ]))
+}
+
+/// LRANGE redis command
+///
+/// Returns the specified elements of the list stored at key. `start`
+/// and `stop` are zero-based inclusive indexes, negative indexes are
+/// offsets from the end of the list.
+///
+/// ```rust
+/// use mco_redis::cmd;
+/// use mco_redis::testing::MockServer;
+/// # use rand::{thread_rng, Rng, distributions::Alphanumeric};
+/// # fn gen_random_key() -> String {
+/// #    thread_rng().sample_iter(&Alphanumeric).take(12).map(char::from).collect::<String>()
+/// # }
+///
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let server = MockServer::start()?;
+///     let redis = server.connector().connect()?;
+///     let key = gen_random_key();
+///
+///     redis.exec(cmd::RPush(&key, "value1").extend(vec!["value2", "value3"]))?;
+///
+///     // get all elements of the list
+///     let values = redis.exec(cmd::LRange(&key, 0, -1))?;
+///
+///     assert_eq!(values, vec!["value1", "value2", "value3"]);
+///     Ok(())
+/// }
+/// ```
+pub fn LRange<T>(key: T, start: i64, stop: i64) -> utils::BulkArrayOutputCommand
+where
+    BulkString: From<T>,
+{
+    utils::BulkArrayOutputCommand(Request::Array(vec![
+        Request::from_static("LRANGE"),
+        Request::BulkString(key.into()),
+        Request::BulkInteger(start),
+        Request::BulkInteger(stop),
+    ]))
+}
+
+/// LSET redis command
+///
+/// Sets the list element at index to element.
+pub fn LSet<T, V>(key: T, index: i64, element: V) -> utils::OkOutputCommand
+where
+    BulkString: From<T> + From<V>,
+{
+    utils::OkOutputCommand(Request::Array(vec![
+        Request::from_static("LSET"),
+        Request::BulkString(key.into()),
+        Request::BulkInteger(index),
+        Request::BulkString(element.into()),
+    ]))
+}
+
+/// LINSERT BEFORE redis command
+///
+/// Inserts element in the list stored at key before the reference value
+/// pivot. Returns the length of the list after the insert operation, or
+/// -1 when the value pivot was not found.
+pub fn LInsertBefore<T, P, V>(key: T, pivot: P, element: V) -> utils::IntOutputCommand
+where
+    BulkString: From<T> + From<P> + From<V>,
+{
+    utils::IntOutputCommand(Request::Array(vec![
+        Request::from_static("LINSERT"),
+        Request::BulkString(key.into()),
+        Request::from_static("BEFORE"),
+        Request::BulkString(pivot.into()),
+        Request::BulkString(element.into()),
+    ]))
+}
+
+/// LINSERT AFTER redis command
+///
+/// Inserts element in the list stored at key after the reference value
+/// pivot. Returns the length of the list after the insert operation, or
+/// -1 when the value pivot was not found.
+pub fn LInsertAfter<T, P, V>(key: T, pivot: P, element: V) -> utils::IntOutputCommand
+where
+    BulkString: From<T> + From<P> + From<V>,
+{
+    utils::IntOutputCommand(Request::Array(vec![
+        Request::from_static("LINSERT"),
+        Request::BulkString(key.into()),
+        Request::from_static("AFTER"),
+        Request::BulkString(pivot.into()),
+        Request::BulkString(element.into()),
+    ]))
+}
+
+/// LREM redis command
+///
+/// Removes the first `count` occurrences of elements equal to element
+/// from the list stored at key. Negative `count` removes elements moving
+/// from tail to head, zero removes all elements equal to element.
+pub fn LRem<T, V>(key: T, count: i64, element: V) -> utils::IntOutputCommand
+where
+    BulkString: From<T> + From<V>,
+{
+    utils::IntOutputCommand(Request::Array(vec![
+        Request::from_static("LREM"),
+        Request::BulkString(key.into()),
+        Request::BulkInteger(count),
+        Request::BulkString(element.into()),
+    ]))
+}
+
+/// LTRIM redis command
+///
+/// Trim an existing list so that it will contain only the specified
+/// range of elements.
+pub fn LTrim<T>(key: T, start: i64, stop: i64) -> utils::OkOutputCommand
+where
+    BulkString: From<T>,
+{
+    utils::OkOutputCommand(Request::Array(vec![
+        Request::from_static("LTRIM"),
+        Request::BulkString(key.into()),
+        Request::BulkInteger(start),
+        Request::BulkInteger(stop),
+    ]))
+}
+
+/// LPOS redis command
+///
+/// Returns the index of matching elements inside a list.
+///
+/// ```rust,no_run
+/// use mco_redis::cmd;
+/// use mco_redis::connector::RedisConnector;
+/// # use rand::{thread_rng, Rng, distributions::Alphanumeric};
+/// # fn gen_random_key() -> String {
+/// #    thread_rng().sample_iter(&Alphanumeric).take(12).map(char::from).collect::<String>()
+/// # }
+///
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // requires real redis server, LPOS is not implemented by `MockServer`
+///     let redis = RedisConnector::new("127.0.0.1:6379").connect()?;
+///     let key = gen_random_key();
+///
+///     redis.exec(cmd::RPush(&key, "a").extend(vec!["b", "c", "b"]))?;
+///
+///     let pos = redis.exec(cmd::LPos(&key, "b"))?;
+///     assert_eq!(pos, Some(1));
+///
+///     // find all matches
+///     let pos = redis.exec(cmd::LPos(&key, "b").count(0))?;
+///     assert_eq!(pos, vec![1, 3]);
+///     Ok(())
+/// }
+/// ```
+pub fn LPos<T, V>(key: T, element: V) -> LPosCommand
+where
+    BulkString: From<T> + From<V>,
+{
+    LPosCommand(vec![
+        Request::from_static("LPOS"),
+        Request::BulkString(key.into()),
+        Request::BulkString(element.into()),
+    ])
+}
+
+pub struct LPosCommand(Vec<Request>);
+
+impl LPosCommand {
+    /// Return the `rank`-th match, negative rank searches from the tail.
+    pub fn rank(mut self, rank: i64) -> Self {
+        self.0.push(Request::from_bstatic(b"RANK"));
+        self.0.push(Request::BulkInteger(rank));
+        self
+    }
+
+    /// Compare at most `len` elements, zero means all elements.
+    pub fn maxlen(mut self, len: usize) -> Self {
+        self.0.push(Request::from_bstatic(b"MAXLEN"));
+        self.0.push(Request::BulkInteger(len as i64));
+        self
+    }
+
+    /// Return up to `count` matches, zero means all matches.
+    ///
+    /// Changes command output to the list of positions.
+    pub fn count(mut self, count: usize) -> LPosCountCommand {
+        self.0.push(Request::from_bstatic(b"COUNT"));
+        self.0.push(Request::BulkInteger(count as i64));
+        LPosCountCommand(self.0)
+    }
+}
+
+impl Command for LPosCommand {
+    type Output = Option<usize>;
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(Option::try_from(val)?)
+    }
+}
+
+pub struct LPosCountCommand(Vec<Request>);
+
+impl LPosCountCommand {
+    /// Skip first `rank - 1` matches, negative rank searches from the tail.
+    pub fn rank(mut self, rank: i64) -> Self {
+        self.0.push(Request::from_bstatic(b"RANK"));
+        self.0.push(Request::BulkInteger(rank));
+        self
+    }
+
+    /// Compare at most `len` elements, zero means all elements.
+    pub fn maxlen(mut self, len: usize) -> Self {
+        self.0.push(Request::from_bstatic(b"MAXLEN"));
+        self.0.push(Request::BulkInteger(len as i64));
+        self
+    }
+}
+
+impl Command for LPosCountCommand {
+    type Output = Vec<usize>;
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(Vec::try_from(val)?)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// Side of the list
+pub enum ListSide {
+    /// Head of the list
+    Left,
+    /// Tail of the list
+    Right,
+}
+
+impl ListSide {
+    pub(crate) fn to_request(self) -> Request {
+        match self {
+            ListSide::Left => Request::from_static("LEFT"),
+            ListSide::Right => Request::from_static("RIGHT"),
+        }
+    }
+}
+
+/// LMOVE redis command
+///
+/// Atomically removes the first/last element of the list stored at
+/// `source`, and pushes the element at the first/last element of the
+/// list stored at `destination`. Returns the element being moved.
+///
+/// ```rust,no_run
+/// use mco_redis::cmd;
+/// use mco_redis::connector::RedisConnector;
+/// use mco_redis::cmd::commands::ListSide;
+/// # use rand::{thread_rng, Rng, distributions::Alphanumeric};
+/// # fn gen_random_key() -> String {
+/// #    thread_rng().sample_iter(&Alphanumeric).take(12).map(char::from).collect::<String>()
+/// # }
+///
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // requires real redis server, LMOVE is not implemented by `MockServer`
+///     let redis = RedisConnector::new("127.0.0.1:6379").connect()?;
+///     let queue = gen_random_key();
+///     let processing = gen_random_key();
+///
+///     redis.exec(cmd::RPush(&queue, "job1").value("job2"))?;
+///
+///     // move job to the processing list
+///     let job = redis.exec(
+///         cmd::LMove(&queue, &processing, ListSide::Left, ListSide::Right)
+///     )?;
+///
+///     assert_eq!(job.unwrap(), "job1");
+///     Ok(())
+/// }
+/// ```
+pub fn LMove<T, D>(
+    source: T,
+    destination: D,
+    from: ListSide,
+    to: ListSide,
+) -> utils::BulkOutputCommand
+where
+    BulkString: From<T> + From<D>,
+{
+    utils::BulkOutputCommand(Request::Array(vec![
+        Request::from_static("LMOVE"),
+        Request::BulkString(source.into()),
+        Request::BulkString(destination.into()),
+        from.to_request(),
+        to.to_request(),
+    ]))
+}
+
+/// LMPOP redis command
+///
+/// Pops one or more elements from the first non-empty list key from the
+/// list of provided key names. Returns name of the key and popped
+/// elements, or `None` if all lists are empty.
+///
+/// ```rust,no_run
+/// use mco_redis::cmd;
+/// use mco_redis::connector::RedisConnector;
+/// use mco_redis::cmd::commands::ListSide;
+/// # use rand::{thread_rng, Rng, distributions::Alphanumeric};
+/// # fn gen_random_key() -> String {
+/// #    thread_rng().sample_iter(&Alphanumeric).take(12).map(char::from).collect::<String>()
+/// # }
+///
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // requires real redis server, LMPOP is not implemented by `MockServer`
+///     let redis = RedisConnector::new("127.0.0.1:6379").connect()?;
+///     let key1 = gen_random_key();
+///     let key2 = gen_random_key();
+///
+///     redis.exec(cmd::RPush(&key2, "value1").value("value2"))?;
+///
+///     let (key, values) = redis.exec(
+///         cmd::LMPop(&key1, ListSide::Left).key(&key2).count(10)
+///     )?.unwrap();
+///
+///     assert_eq!(key, key2);
+///     assert_eq!(values, vec!["value1", "value2"]);
+///     Ok(())
+/// }
+/// ```
+pub fn LMPop<T>(key: T, side: ListSide) -> LMPopCommand
+where
+    BulkString: From<T>,
+{
+    LMPopCommand {
+        keys: vec![Request::BulkString(key.into())],
+        side,
+        count: None,
+    }
+}
+
+pub struct LMPopCommand {
+    keys: Vec<Request>,
+    side: ListSide,
+    count: Option<usize>,
+}
+
+impl LMPopCommand {
+    /// Add a key to this command.
+    pub fn key<T>(mut self, other: T) -> Self
+    where
+        BulkString: From<T>,
+    {
+        self.keys.push(other.into());
+        self
+    }
+
+    /// Add more keys to this command.
+    pub fn keys<T>(mut self, other: impl IntoIterator<Item = T>) -> Self
+    where
+        BulkString: From<T>,
+    {
+        self.keys.extend(other.into_iter().map(|t| t.into()));
+        self
+    }
+
+    /// Pop up to `count` elements.
+    pub fn count(mut self, count: usize) -> Self {
+        self.count = Some(count);
+        self
+    }
+
+    pub(crate) fn into_request_vec(self, name: &'static str) -> Vec<Request> {
+        let mut req = Vec::with_capacity(self.keys.len() + 5);
+        req.push(Request::from_static(name));
+        req.push(Request::BulkInteger(self.keys.len() as i64));
+        req.extend(self.keys);
+        req.push(self.side.to_request());
+        if let Some(count) = self.count {
+            req.push(Request::from_bstatic(b"COUNT"));
+            req.push(Request::BulkInteger(count as i64));
+        }
+        req
+    }
+}
+
+impl Command for LMPopCommand {
+    type Output = Option<(Bytes, Vec<Bytes>)>;
+
+    fn to_request(self) -> Request {
+        Request::Array(self.into_request_vec("LMPOP"))
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(Option::try_from(val)?)
+    }
+}
+
+/// BLPOP redis command
+///
+/// Blocking version of the `LPOP` command. Pops an element from the first
+/// non-empty list of the provided keys, or blocks the connection until
+/// an element is available or `timeout` expires. Returns name of the key
+/// and popped element, or `None` if timeout expired.
+///
+/// Blocking commands cannot be executed by shared `Client`, use
+/// `SimpleClient::exec_blocking()` on a dedicated connection.
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use mco_redis::cmd;
+/// use mco_redis::connector::RedisConnector;
+/// # use rand::{thread_rng, Rng, distributions::Alphanumeric};
+/// # fn gen_random_key() -> String {
+/// #    thread_rng().sample_iter(&Alphanumeric).take(12).map(char::from).collect::<String>()
+/// # }
+///
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // requires real redis server, BLPOP is not implemented by `MockServer`
+///     let redis = RedisConnector::new("127.0.0.1:6379").connect_simple()?;
+///     let key = gen_random_key();
+///
+///     redis.exec(cmd::RPush(&key, "job1"))?;
+///
+///     // wait for a job
+///     let (_, job) = redis.exec_blocking(
+///         cmd::BLPop(&key, Duration::from_millis(500))
+///     )?.unwrap();
+///
+///     assert_eq!(job, "job1");
+///     Ok(())
+/// }
+/// ```
+pub fn BLPop<T>(key: T, timeout: Duration) -> BPopCommand
+where
+    BulkString: From<T>,
+{
+    BPopCommand {
+        req: vec![
+            Request::from_static("BLPOP"),
+            Request::BulkString(key.into()),
+        ],
+        timeout,
+    }
+}
+
+/// BRPOP redis command
+///
+/// Blocking version of the `RPOP` command. Pops an element from the first
+/// non-empty list of the provided keys, or blocks the connection until
+/// an element is available or `timeout` expires. Returns name of the key
+/// and popped element, or `None` if timeout expired.
+pub fn BRPop<T>(key: T, timeout: Duration) -> BPopCommand
+where
+    BulkString: From<T>,
+{
+    BPopCommand {
+        req: vec![
+            Request::from_static("BRPOP"),
+            Request::BulkString(key.into()),
+        ],
+        timeout,
+    }
+}
+
+pub struct BPopCommand {
+    req: Vec<Request>,
+    timeout: Duration,
+}
+
+impl BPopCommand {
+    /// Add a key to this command.
+    pub fn key<T>(mut self, other: T) -> Self
+    where
+        BulkString: From<T>,
+    {
+        self.req.push(other.into());
+        self
+    }
+
+    /// Add more keys to this command.
+    pub fn keys<T>(mut self, other: impl IntoIterator<Item = T>) -> Self
+    where
+        BulkString: From<T>,
+    {
+        self.req.extend(other.into_iter().map(|t| t.into()));
+        self
+    }
+}
+
+impl BlockingCommand for BPopCommand {
+    type Output = Option<(Bytes, Bytes)>;
+
+    fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    fn to_request(mut self) -> Request {
+        self.req.push(utils::timeout_request(self.timeout));
+        Request::Array(self.req)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(Option::try_from(val)?)
+    }
+}
+
+/// BLMOVE redis command
+///
+/// Blocking version of the `LMOVE` command. Blocks the connection until
+/// `source` list is non-empty or `timeout` expires.
+pub fn BLMove<T, D>(
+    source: T,
+    destination: D,
+    from: ListSide,
+    to: ListSide,
+    timeout: Duration,
+) -> BLMoveCommand
+where
+    BulkString: From<T> + From<D>,
+{
+    BLMoveCommand {
+        req: vec![
+            Request::from_static("BLMOVE"),
+            Request::BulkString(source.into()),
+            Request::BulkString(destination.into()),
+            from.to_request(),
+            to.to_request(),
+            utils::timeout_request(timeout),
+        ],
+        timeout,
+    }
+}
+
+pub struct BLMoveCommand {
+    req: Vec<Request>,
+    timeout: Duration,
+}
+
+impl BlockingCommand for BLMoveCommand {
+    type Output = Option<Bytes>;
+
+    fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    fn to_request(self) -> Request {
+        Request::Array(self.req)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        utils::BulkOutputCommand::to_output(val)
+    }
+}
+
+/// BLMPOP redis command
+///
+/// Blocking version of the `LMPOP` command. Blocks the connection until
+/// one of the lists is non-empty or `timeout` expires.
+pub fn BLMPop<T>(key: T, side: ListSide, timeout: Duration) -> BLMPopCommand
+where
+    BulkString: From<T>,
+{
+    BLMPopCommand {
+        cmd: LMPop(key, side),
+        timeout,
+    }
+}
+
+pub struct BLMPopCommand {
+    cmd: LMPopCommand,
+    timeout: Duration,
+}
+
+impl BLMPopCommand {
+    /// Add a key to this command.
+    pub fn key<T>(mut self, other: T) -> Self
+    where
+        BulkString: From<T>,
+    {
+        self.cmd = self.cmd.key(other);
+        self
+    }
+
+    /// Add more keys to this command.
+    pub fn keys<T>(mut self, other: impl IntoIterator<Item = T>) -> Self
+    where
+        BulkString: From<T>,
+    {
+        self.cmd = self.cmd.keys(other);
+        self
+    }
+
+    /// Pop up to `count` elements.
+    pub fn count(mut self, count: usize) -> Self {
+        self.cmd = self.cmd.count(count);
+        self
+    }
+}
+
+impl BlockingCommand for BLMPopCommand {
+    type Output = Option<(Bytes, Vec<Bytes>)>;
+
+    fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    fn to_request(self) -> Request {
+        let mut req = self.cmd.into_request_vec("BLMPOP");
+        req.insert(1, utils::timeout_request(self.timeout));
+        Request::Array(req)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        LMPopCommand::to_output(val)
+    }
+}
@@ -1,23 +1,72 @@
 //! Redis commands
 #![allow(non_snake_case, clippy::wrong_self_convention)]
 
+use std::time::Duration;
+
 use super::codec_redis::{Request, Response};
 use super::errors::CommandError;
 
+mod acl;
 mod auth;
+mod bitmaps;
 mod connection;
+mod functions;
+mod geo;
 mod hashes;
+mod hyperloglog;
 mod keys;
 mod lists;
+mod replication;
+mod scan;
+mod scripting;
+mod server;
+mod sorted_sets;
 mod strings;
-mod utils;
+pub(crate) mod utils;
 
+pub use self::acl::{
+    AclCat, AclDelUser, AclDryRun, AclGenPass, AclGetUser, AclList, AclLog, AclSetUser, AclUsers,
+    AclWhoAmI,
+};
 pub use self::auth::Auth;
-pub use self::connection::{Ping, Select};
-pub use self::hashes::{HDel, HGet, HGetAll, HIncrBy, HLen, HSet};
-pub use self::keys::{Del, Exists, Expire, ExpireAt, Ttl, TtlResult};
-pub use self::lists::{LIndex, LPop, LPush, RPop, RPush};
-pub use self::strings::{Get, IncrBy, Set};
+pub use self::bitmaps::{BitCount, BitField, BitFieldRo, BitOp, BitPos, GetBit, SetBit};
+pub use self::connection::{
+    ClientGetName, ClientId, ClientInfo, ClientKill, ClientList, ClientNoEvict, ClientNoTouch,
+    ClientPause, ClientReply, ClientReplyOn, ClientSetName, ClientTracking, ClientUnpause, Ping, Select,
+};
+pub use self::functions::{
+    FCall, FCallRo, FunctionDelete, FunctionDump, FunctionFlush, FunctionList, FunctionLoad,
+    FunctionRestore,
+};
+pub use self::geo::{GeoAdd, GeoDist, GeoHash, GeoPos, GeoSearch, GeoSearchStore};
+pub use self::hashes::{
+    HDel, HExists, HExpire, HExpireIn, HGet, HGetAll, HIncrBy, HIncrByFloat, HKeys, HLen, HMGet,
+    HPExpire, HPTtl, HPersist, HRandField, HSet, HSetEntries, HSetNx, HStrLen, HTtl, HVals,
+};
+pub use self::hyperloglog::{PfAdd, PfCount, PfMerge};
+pub use self::keys::{
+    Copy, Del, Dump, Exists, Expire, ExpireAt, ExpireAtTime, ExpireIn, ExpireTime,
+    ExpireTimeResult, Millis, Move, ObjectEncoding, ObjectFreq, ObjectIdleTime, PExpire, PExpireAt,
+    PExpireTime, PTtl, PTtlResult, Persist, RandomKey, Rename, RenameNx, Restore, Seconds, Sort,
+    SortRo, TimeRangeError, Touch, Ttl, TtlResult, Type, Unlink,
+};
+pub use self::lists::{
+    BLMPop, BLMove, BLPop, BRPop, LIndex, LInsertAfter, LInsertBefore, LLen, LMPop, LMove, LPop,
+    LPos, LPush, LRange, LRem, LSet, LTrim, RPop, RPush,
+};
+pub use self::replication::{Failover, ReplicaOf, ReplicaOfNoOne, Role, Wait, WaitAof};
+pub use self::scan::{HScan, SScan, Scan, ZScan};
+pub use self::scripting::{Eval, EvalSha, ScriptExists, ScriptFlush, ScriptLoad};
+pub use self::server::{
+    BgRewriteAof, BgSave, ConfigGet, ConfigResetStat, ConfigRewrite, ConfigSet, DbSize, FlushAll,
+    FlushDb, Info, LastSave, LatencyHistory, LatencyLatest, LatencyReset, MemoryStats, MemoryUsage,
+    Save, SlowlogGet, SlowlogLen, SlowlogReset, Time,
+};
+pub use self::sorted_sets::{BZMPop, BZPopMax, BZPopMin};
+pub use self::strings::{
+    Append, Decr, DecrBy, Get, GetDel, GetEx, GetRange, GetSet, Incr, IncrBy, IncrByFloat, Lcs,
+    MGet, MSet, MSetNx, PSetEx, Set, SetEx, SetNx, SetRange, StrLen,
+};
 
 /// Trait implemented by types that can be used as redis commands
 pub trait Command {
@@ -31,13 +80,103 @@ pub trait Command {
     fn to_output(val: Response) -> Result<Self::Output, CommandError>;
 }
 
+/// Trait implemented by types that can be sent without waiting for a reply
+///
+/// Every `Command` can be sent this way, commands that server never
+/// acknowledges implement only this trait.
+pub trait NoReplyCommand {
+    /// Convert command to a redis request
+    fn into_request(self) -> Request;
+}
+
+impl<T: Command> NoReplyCommand for T {
+    fn into_request(self) -> Request {
+        self.to_request()
+    }
+}
+
+/// Trait implemented by blocking redis commands
+///
+/// Blocking command holds connection until data is available or server-side
+/// timeout expires, so it cannot be executed by shared `Client`. Use
+/// `SimpleClient::exec_blocking()` on a dedicated connection instead.
+pub trait BlockingCommand {
+    /// Command output type
+    type Output;
+
+    /// Server-side block timeout, zero timeout blocks indefinitely
+    fn timeout(&self) -> Duration;
+
+    /// Convert command to a redis request
+    fn to_request(self) -> Request;
+
+    /// Create command response from a redis response
+    fn to_output(val: Response) -> Result<Self::Output, CommandError>;
+}
+
 pub mod commands {
     //! Command implementations
+    pub use super::acl::{
+        AclCatCommand, AclDelUserCommand, AclDryRunCommand, AclGenPassCommand, AclGetUserCommand,
+        AclLogCommand, AclLogEntry, AclRules, AclSelector, AclSelectorInfo, AclSetUserCommand,
+        AclStringCommand, AclStringsCommand, AclUser,
+    };
     pub use super::auth::AuthCommand;
-    pub use super::hashes::{HDelCommand, HGetAllCommand, HSetCommand};
-    pub use super::keys::{KeysCommand, TtlCommand};
-    pub use super::lists::LPushCommand;
-    pub use super::strings::SetCommand;
-    pub use super::utils::{BulkOutputCommand, IntOutputCommand};
+    pub use super::bitmaps::{
+        BitCountCommand, BitFieldCommand, BitFieldOverflow, BitFieldRoCommand, BitFieldType,
+        BitOpCommand, BitOperation, BitPosCommand,
+    };
+    pub use super::connection::{
+        ClientInfoCommand, ClientKillCommand, ClientListCommand, ClientPauseCommand,
+        ClientReplyCommand, ClientTrackingCommand, ClientType, ConnectionInfo, PingCommand, ReplyMode, SelectCommand,
+    };
+    pub use super::functions::{
+        FunctionInfo, FunctionListCommand, FunctionLoadCommand, FunctionRestoreCommand, Library,
+        LibraryInfo, RestorePolicy,
+    };
+    pub use super::geo::{
+        GeoAddCommand, GeoBy, GeoDistCommand, GeoFrom, GeoHashCommand, GeoPosCommand, GeoResult,
+        GeoSearchCommand, GeoSearchStoreCommand, GeoUnit,
+    };
+    pub use super::hashes::{
+        HDelCommand, HExpireCommand, HExpireResult, HFieldsCommand, HGetAllCommand, HMGetCommand,
+        HPTtlCommand, HPersistCommand, HPersistResult, HRandFieldCommand,
+        HRandFieldWithValuesCommand, HSetCommand, HTtlCommand,
+    };
+    pub use super::hyperloglog::{PfAddCommand, PfMergeCommand};
+    pub use super::keys::{
+        CopyCommand, ExpireCommand, ExpireTimeCommand, KeyType, KeysCommand, PExpireTimeCommand,
+        PTtlCommand, RestoreCommand, SortCommand, TtlCommand, TypeCommand,
+    };
+    pub use super::lists::{
+        BLMPopCommand, BLMoveCommand, BPopCommand, LMPopCommand, LPosCommand, LPosCountCommand,
+        LPushCommand, ListSide, PopCommand,
+    };
+    pub use super::replication::{
+        FailoverCommand, ReplicaOfCommand, RoleCommand, RoleReplica, ServerRole, WaitAofCommand,
+        WaitCommand,
+    };
+    pub use super::scan::{
+        CursorCommand, HScanCommand, SScanCommand, ScanCommand, ScanIter, ZScanCommand,
+    };
+    pub use super::scripting::{
+        EvalCommand, Script, ScriptCall, ScriptExistsCommand, ScriptFlushCommand, ScriptLoadCommand,
+    };
+    pub use super::server::{
+        BackgroundCommand, ClientsSection, ConfigGetCommand, ConfigSetCommand, FlushCommand,
+        InfoCommand, KeyspaceInfo, LastSaveCommand, LatencyEvent, LatencyHistoryCommand,
+        LatencyLatestCommand, LatencyResetCommand, LatencySample, MemorySection,
+        MemoryStatsCommand, MemoryStatsInfo, MemoryUsageCommand, PersistenceSection,
+        ReplicationSection, ServerInfo, ServerSection, SlowlogEntry, SlowlogGetCommand,
+        StatsSection, TimeCommand,
+    };
+    pub use super::sorted_sets::{BZMPopCommand, BZPopCommand, ZSetSide};
+    pub use super::strings::{
+        GetExCommand, LcsCommand, LcsIdxCommand, LcsIdxResult, LcsMatch, MGetCommand, MSetCommand,
+        SetCommand, SetGetCommand,
+    };
+    pub use super::utils::{
+        BoolOutputCommand, BulkArrayOutputCommand, BulkOutputCommand, FloatOutputCommand,
+        FlushMode, IntOutputCommand, MultiBulkOutputCommand, OkOutputCommand, OutputCommand,
+    };
 }
-
@@ -0,0 +1,293 @@
+use std::convert::TryFrom;
+use std::time::Duration;
+
+use super::{utils, BlockingCommand, Command, CommandError};
+use crate::bytes::{ByteString, Bytes};
+use crate::codec_redis::{BulkString, Request, Response};
+
+/// REPLICAOF redis command
+///
+/// Makes the server a replica of the master at `host:port`.
+pub fn ReplicaOf<T>(host: T, port: u16) -> ReplicaOfCommand
+where
+    BulkString: From<T>,
+{
+    ReplicaOfCommand(Request::Array(vec![
+        Request::from_static("REPLICAOF"),
+        Request::BulkString(host.into()),
+        Request::BulkInteger(port as i64),
+    ]))
+}
+
+/// REPLICAOF NO ONE redis command
+///
+/// Stops replication and turns the replica into a master.
+pub fn ReplicaOfNoOne() -> ReplicaOfCommand {
+    ReplicaOfCommand(Request::Array(vec![
+        Request::from_static("REPLICAOF"),
+        Request::from_static("NO"),
+        Request::from_static("ONE"),
+    ]))
+}
+
+pub struct ReplicaOfCommand(Request);
+
+impl Command for ReplicaOfCommand {
+    type Output = ();
+
+    fn to_request(self) -> Request {
+        self.0
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        // status message depends on the replication state
+        match val {
+            Response::String(_) => Ok(()),
+            _ => Err(CommandError::Output("Cannot parse response", val)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Replica connected to the master
+pub struct RoleReplica {
+    pub ip: ByteString,
+    pub port: u16,
+    /// Last acknowledged replication offset
+    pub offset: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Output of the `ROLE` command
+pub enum ServerRole {
+    Master {
+        /// Current replication offset
+        offset: i64,
+        replicas: Vec<RoleReplica>,
+    },
+    Replica {
+        master_ip: ByteString,
+        master_port: u16,
+        /// `connect`, `connecting`, `sync` or `connected`
+        state: ByteString,
+        /// Amount of data received from the master
+        offset: i64,
+    },
+    Sentinel {
+        /// Names of monitored masters
+        masters: Vec<ByteString>,
+    },
+}
+
+/// Integers are returned either as integers or as bulk strings
+fn parse_int<T>(val: Response) -> Result<T, (&'static str, Response)>
+where
+    T: TryFrom<i64> + std::str::FromStr,
+{
+    let parsed = match val {
+        Response::Integer(i) => T::try_from(i).ok(),
+        Response::Bytes(ref b) => std::str::from_utf8(b).ok().and_then(|s| s.parse().ok()),
+        _ => None,
+    };
+    parsed.ok_or(("Cannot parse integer", val))
+}
+
+impl TryFrom<Response> for RoleReplica {
+    type Error = (&'static str, Response);
+
+    fn try_from(val: Response) -> Result<Self, Self::Error> {
+        let mut items = match val {
+            Response::Array(items) if items.len() >= 3 => items.into_iter(),
+            _ => return Err(("Cannot be converted into a replica", val)),
+        };
+        let mut next = || items.next().unwrap_or(Response::Nil);
+
+        Ok(RoleReplica {
+            ip: ByteString::try_from(next())?,
+            port: parse_int(next())?,
+            offset: parse_int(next())?,
+        })
+    }
+}
+
+impl TryFrom<Response> for ServerRole {
+    type Error = (&'static str, Response);
+
+    fn try_from(val: Response) -> Result<Self, Self::Error> {
+        let mut items = match val {
+            Response::Array(items) if !items.is_empty() => items.into_iter(),
+            _ => return Err(("Cannot be converted into a role", val)),
+        };
+        let role = Bytes::try_from(items.next().unwrap_or(Response::Nil))?;
+        let mut next = || items.next().unwrap_or(Response::Nil);
+
+        match role.as_ref() {
+            b"master" => Ok(ServerRole::Master {
+                offset: parse_int(next())?,
+                replicas: Vec::try_from(next())?,
+            }),
+            b"slave" => Ok(ServerRole::Replica {
+                master_ip: ByteString::try_from(next())?,
+                master_port: parse_int(next())?,
+                state: ByteString::try_from(next())?,
+                offset: parse_int(next())?,
+            }),
+            b"sentinel" => Ok(ServerRole::Sentinel {
+                masters: Vec::try_from(next())?,
+            }),
+            _ => Err(("Unknown role", Response::Bytes(role))),
+        }
+    }
+}
+
+/// ROLE redis command
+///
+/// Returns the replication role of the server.
+pub fn Role() -> RoleCommand {
+    RoleCommand(Request::Array(vec![Request::from_static("ROLE")]))
+}
+
+pub struct RoleCommand(Request);
+
+impl Command for RoleCommand {
+    type Output = ServerRole;
+
+    fn to_request(self) -> Request {
+        self.0
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(ServerRole::try_from(val)?)
+    }
+}
+
+/// WAIT redis command
+///
+/// Blocks until all previous write commands of the connection are
+/// acknowledged by at least `replicas` replicas, or until timeout expires.
+/// Returns the number of replicas that acknowledged the writes.
+pub fn Wait(replicas: usize, timeout: Duration) -> WaitCommand {
+    WaitCommand {
+        req: vec![
+            Request::from_static("WAIT"),
+            Request::BulkInteger(replicas as i64),
+        ],
+        timeout,
+    }
+}
+
+pub struct WaitCommand {
+    req: Vec<Request>,
+    timeout: Duration,
+}
+
+impl BlockingCommand for WaitCommand {
+    type Output = usize;
+
+    fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    fn to_request(mut self) -> Request {
+        self.req.push(utils::millis_request(self.timeout));
+        Request::Array(self.req)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(usize::try_from(val)?)
+    }
+}
+
+/// WAITAOF redis command
+///
+/// Blocks until all previous write commands of the connection are fsynced
+/// to the append only file of the local server and/or at least `replicas`
+/// replicas. Returns the number of local servers and replicas that
+/// acknowledged the writes.
+pub fn WaitAof(local: usize, replicas: usize, timeout: Duration) -> WaitAofCommand {
+    WaitAofCommand {
+        req: vec![
+            Request::from_static("WAITAOF"),
+            Request::BulkInteger(local as i64),
+            Request::BulkInteger(replicas as i64),
+        ],
+        timeout,
+    }
+}
+
+pub struct WaitAofCommand {
+    req: Vec<Request>,
+    timeout: Duration,
+}
+
+impl BlockingCommand for WaitAofCommand {
+    type Output = (usize, usize);
+
+    fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    fn to_request(mut self) -> Request {
+        self.req.push(utils::millis_request(self.timeout));
+        Request::Array(self.req)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(<(usize, usize)>::try_from(val)?)
+    }
+}
+
+/// FAILOVER redis command
+///
+/// Starts coordinated failover from the master to one of its replicas.
+pub fn Failover() -> FailoverCommand {
+    FailoverCommand(vec![Request::from_static("FAILOVER")])
+}
+
+pub struct FailoverCommand(Vec<Request>);
+
+impl FailoverCommand {
+    /// Failover to the specific replica.
+    pub fn to<T>(mut self, host: T, port: u16) -> Self
+    where
+        BulkString: From<T>,
+    {
+        self.0.push(Request::from_bstatic(b"TO"));
+        self.0.push(host.into());
+        self.0.push(Request::BulkInteger(port as i64));
+        self
+    }
+
+    /// Failover even if replica does not catch up within the timeout,
+    /// requires both `to()` and `timeout()`.
+    pub fn force(mut self) -> Self {
+        self.0.push(Request::from_bstatic(b"FORCE"));
+        self
+    }
+
+    /// Abort failover if replica does not catch up within the timeout.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.0.push(Request::from_bstatic(b"TIMEOUT"));
+        self.0.push(utils::millis_request(timeout));
+        self
+    }
+
+    /// Abort ongoing failover.
+    pub fn abort(mut self) -> Self {
+        self.0.truncate(1);
+        self.0.push(Request::from_bstatic(b"ABORT"));
+        self
+    }
+}
+
+impl Command for FailoverCommand {
+    type Output = ();
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(<()>::try_from(val)?)
+    }
+}
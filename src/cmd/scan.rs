@@ -0,0 +1,430 @@
+use std::collections::{HashSet, VecDeque};
+use std::convert::TryFrom;
+
+use super::{Command, CommandError};
+use crate::args::ToArg;
+use crate::bytes::Bytes;
+use crate::codec_redis::{BulkString, Request, Response};
+
+/// Trait implemented by cursor based commands, like `SCAN` or `HSCAN`
+pub trait CursorCommand:
+    Command<Output = (u64, Vec<<Self as CursorCommand>::Item>)> + Clone
+{
+    /// Type of the returned elements
+    type Item;
+
+    /// Set cursor for the next iteration
+    fn set_cursor(&mut self, cursor: u64);
+
+    /// Key used for duplicates detection
+    fn item_key(item: &Self::Item) -> &Bytes;
+}
+
+#[derive(Clone)]
+struct Scan {
+    name: &'static str,
+    key: Option<Request>,
+    cursor: u64,
+    opts: Vec<Request>,
+}
+
+impl Scan {
+    fn new(name: &'static str, key: Option<Request>, cursor: u64) -> Self {
+        Scan {
+            name,
+            key,
+            cursor,
+            opts: Vec::new(),
+        }
+    }
+
+    fn pattern(mut self, pattern: BulkString) -> Self {
+        self.opts.push(Request::from_bstatic(b"MATCH"));
+        self.opts.push(Request::BulkString(pattern));
+        self
+    }
+
+    fn count(mut self, count: usize) -> Self {
+        self.opts.push(Request::from_bstatic(b"COUNT"));
+        self.opts.push(Request::BulkInteger(count as i64));
+        self
+    }
+
+    fn to_request(self) -> Request {
+        let mut req = Vec::with_capacity(self.opts.len() + 3);
+        req.push(Request::from_static(self.name));
+        if let Some(key) = self.key {
+            req.push(key);
+        }
+        // cursor is unsigned 64-bit number
+        req.push(self.cursor.to_arg());
+        req.extend(self.opts);
+        Request::Array(req)
+    }
+}
+
+/// Split scan response to the next cursor and the list of elements
+fn scan_output(val: Response) -> Result<(u64, Vec<Response>), CommandError> {
+    match val {
+        Response::Array(ary) if ary.len() == 2 => {
+            let mut ary = ary.into_iter();
+            let cursor = match ary.next().unwrap() {
+                Response::Bytes(cursor) => btoi::btou(&cursor)
+                    .map_err(|_| CommandError::Output("Invalid cursor", Response::Bytes(cursor)))?,
+                val => u64::try_from(val)?,
+            };
+            match ary.next().unwrap() {
+                Response::Array(items) => Ok((cursor, items)),
+                val => Err(CommandError::Output("Cannot parse scan elements", val)),
+            }
+        }
+        _ => Err(CommandError::Output("Cannot parse scan response", val)),
+    }
+}
+
+/// Convert flat list of elements to the list of pairs
+fn scan_pairs<A, B>(items: Vec<Response>) -> Result<Vec<(A, B)>, CommandError>
+where
+    A: TryFrom<Response, Error = (&'static str, Response)>,
+    B: TryFrom<Response, Error = (&'static str, Response)>,
+{
+    let mut result = Vec::with_capacity(items.len() / 2);
+    let mut items = items.into_iter();
+    while let Some(a) = items.next() {
+        let b = items.next().ok_or((
+            "Cannot convert an odd number of elements into pairs",
+            Response::Nil,
+        ))?;
+        result.push((A::try_from(a)?, B::try_from(b)?));
+    }
+    Ok(result)
+}
+
+/// SCAN redis command
+///
+/// Incrementally iterate over the keys of the currently selected database.
+/// Command returns the next cursor and a batch of keys, iteration is
+/// complete when the returned cursor is 0.
+///
+/// Use `Client::scan()` or `SimpleClient::scan()` to iterate over all keys.
+///
+/// ```rust,no_run
+/// use mco_redis::cmd;
+/// use mco_redis::connector::RedisConnector;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // requires real redis server, SCAN is not implemented by `MockServer`
+///     let redis = RedisConnector::new("127.0.0.1:6379").connect()?;
+///
+///     redis.exec(cmd::Set("scan-key-1", "value"))?;
+///
+///     // iterate over all matching keys
+///     for key in redis.scan(cmd::Scan(0).pattern("scan-key-*").count(100)) {
+///         let key = key?;
+///         assert!(key.starts_with(b"scan-key-"));
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn Scan(cursor: u64) -> ScanCommand {
+    ScanCommand(Scan::new("SCAN", None, cursor))
+}
+
+/// SSCAN redis command
+///
+/// Incrementally iterate over the members of the set stored at `key`.
+pub fn SScan<T>(key: T, cursor: u64) -> SScanCommand
+where
+    BulkString: From<T>,
+{
+    SScanCommand(Scan::new(
+        "SSCAN",
+        Some(Request::BulkString(key.into())),
+        cursor,
+    ))
+}
+
+#[derive(Clone)]
+pub struct ScanCommand(Scan);
+
+impl ScanCommand {
+    /// Only return elements matching glob-style `pattern`.
+    pub fn pattern<T>(self, pattern: T) -> Self
+    where
+        BulkString: From<T>,
+    {
+        ScanCommand(self.0.pattern(pattern.into()))
+    }
+
+    /// Amount of work that should be done at every call.
+    pub fn count(self, count: usize) -> Self {
+        ScanCommand(self.0.count(count))
+    }
+
+    /// Only return keys of the specified type, i.e. "string" or "hash".
+    pub fn key_type<T>(mut self, tp: T) -> Self
+    where
+        BulkString: From<T>,
+    {
+        self.0.opts.push(Request::from_bstatic(b"TYPE"));
+        self.0.opts.push(Request::BulkString(tp.into()));
+        self
+    }
+}
+
+impl Command for ScanCommand {
+    type Output = (u64, Vec<Bytes>);
+
+    fn to_request(self) -> Request {
+        self.0.to_request()
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        let (cursor, items) = scan_output(val)?;
+        let items = items
+            .into_iter()
+            .map(Bytes::try_from)
+            .collect::<Result<_, _>>()?;
+        Ok((cursor, items))
+    }
+}
+
+impl CursorCommand for ScanCommand {
+    type Item = Bytes;
+
+    fn set_cursor(&mut self, cursor: u64) {
+        self.0.cursor = cursor;
+    }
+
+    fn item_key(item: &Bytes) -> &Bytes {
+        item
+    }
+}
+
+#[derive(Clone)]
+pub struct SScanCommand(Scan);
+
+impl SScanCommand {
+    /// Only return members matching glob-style `pattern`.
+    pub fn pattern<T>(self, pattern: T) -> Self
+    where
+        BulkString: From<T>,
+    {
+        SScanCommand(self.0.pattern(pattern.into()))
+    }
+
+    /// Amount of work that should be done at every call.
+    pub fn count(self, count: usize) -> Self {
+        SScanCommand(self.0.count(count))
+    }
+}
+
+impl Command for SScanCommand {
+    type Output = (u64, Vec<Bytes>);
+
+    fn to_request(self) -> Request {
+        self.0.to_request()
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        ScanCommand::to_output(val)
+    }
+}
+
+impl CursorCommand for SScanCommand {
+    type Item = Bytes;
+
+    fn set_cursor(&mut self, cursor: u64) {
+        self.0.cursor = cursor;
+    }
+
+    fn item_key(item: &Bytes) -> &Bytes {
+        item
+    }
+}
+
+/// HSCAN redis command
+///
+/// Incrementally iterate over the fields and values of the hash stored
+/// at `key`.
+pub fn HScan<T>(key: T, cursor: u64) -> HScanCommand
+where
+    BulkString: From<T>,
+{
+    HScanCommand(Scan::new(
+        "HSCAN",
+        Some(Request::BulkString(key.into())),
+        cursor,
+    ))
+}
+
+#[derive(Clone)]
+pub struct HScanCommand(Scan);
+
+impl HScanCommand {
+    /// Only return fields matching glob-style `pattern`.
+    pub fn pattern<T>(self, pattern: T) -> Self
+    where
+        BulkString: From<T>,
+    {
+        HScanCommand(self.0.pattern(pattern.into()))
+    }
+
+    /// Amount of work that should be done at every call.
+    pub fn count(self, count: usize) -> Self {
+        HScanCommand(self.0.count(count))
+    }
+}
+
+impl Command for HScanCommand {
+    type Output = (u64, Vec<(Bytes, Bytes)>);
+
+    fn to_request(self) -> Request {
+        self.0.to_request()
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        let (cursor, items) = scan_output(val)?;
+        Ok((cursor, scan_pairs(items)?))
+    }
+}
+
+impl CursorCommand for HScanCommand {
+    type Item = (Bytes, Bytes);
+
+    fn set_cursor(&mut self, cursor: u64) {
+        self.0.cursor = cursor;
+    }
+
+    fn item_key(item: &(Bytes, Bytes)) -> &Bytes {
+        &item.0
+    }
+}
+
+/// ZSCAN redis command
+///
+/// Incrementally iterate over the members and scores of the sorted set
+/// stored at `key`.
+pub fn ZScan<T>(key: T, cursor: u64) -> ZScanCommand
+where
+    BulkString: From<T>,
+{
+    ZScanCommand(Scan::new(
+        "ZSCAN",
+        Some(Request::BulkString(key.into())),
+        cursor,
+    ))
+}
+
+#[derive(Clone)]
+pub struct ZScanCommand(Scan);
+
+impl ZScanCommand {
+    /// Only return members matching glob-style `pattern`.
+    pub fn pattern<T>(self, pattern: T) -> Self
+    where
+        BulkString: From<T>,
+    {
+        ZScanCommand(self.0.pattern(pattern.into()))
+    }
+
+    /// Amount of work that should be done at every call.
+    pub fn count(self, count: usize) -> Self {
+        ZScanCommand(self.0.count(count))
+    }
+}
+
+impl Command for ZScanCommand {
+    type Output = (u64, Vec<(Bytes, f64)>);
+
+    fn to_request(self) -> Request {
+        self.0.to_request()
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        let (cursor, items) = scan_output(val)?;
+        Ok((cursor, scan_pairs(items)?))
+    }
+}
+
+impl CursorCommand for ZScanCommand {
+    type Item = (Bytes, f64);
+
+    fn set_cursor(&mut self, cursor: u64) {
+        self.0.cursor = cursor;
+    }
+
+    fn item_key(item: &(Bytes, f64)) -> &Bytes {
+        &item.0
+    }
+}
+
+/// Iterator over all elements of the cursor based command
+///
+/// Iterator drives the cursor until server returns 0 and yields elements
+/// lazily, one batch per request. Redis may return the same element
+/// multiple times, use `ScanIter::dedup()` to filter out duplicates.
+pub struct ScanIter<T: CursorCommand, F> {
+    exec: F,
+    next: Option<T>,
+    items: VecDeque<T::Item>,
+    seen: Option<HashSet<Bytes>>,
+}
+
+impl<T, F> ScanIter<T, F>
+where
+    T: CursorCommand,
+    F: FnMut(T) -> Result<T::Output, CommandError>,
+{
+    pub(crate) fn new(cmd: T, exec: F) -> Self {
+        ScanIter {
+            exec,
+            next: Some(cmd),
+            items: VecDeque::new(),
+            seen: None,
+        }
+    }
+
+    /// Skip elements that were already returned.
+    ///
+    /// Keys of all returned elements are kept in memory until the
+    /// iterator is dropped.
+    pub fn dedup(mut self) -> Self {
+        self.seen = Some(HashSet::new());
+        self
+    }
+}
+
+impl<T, F> Iterator for ScanIter<T, F>
+where
+    T: CursorCommand,
+    F: FnMut(T) -> Result<T::Output, CommandError>,
+{
+    type Item = Result<T::Item, CommandError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            while let Some(item) = self.items.pop_front() {
+                if let Some(ref mut seen) = self.seen {
+                    if !seen.insert(T::item_key(&item).clone()) {
+                        continue;
+                    }
+                }
+                return Some(Ok(item));
+            }
+
+            let cmd = self.next.take()?;
+            let mut next = cmd.clone();
+            match (self.exec)(cmd) {
+                Ok((cursor, items)) => {
+                    if cursor != 0 {
+                        next.set_cursor(cursor);
+                        self.next = Some(next);
+                    }
+                    self.items.extend(items);
+                }
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+}
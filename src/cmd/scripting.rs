@@ -0,0 +1,416 @@
+use std::convert::TryFrom;
+use std::marker::PhantomData;
+
+use super::utils::{self, FlushMode};
+use super::{Command, CommandError};
+use crate::args::ToArgs;
+use crate::bytes::{ByteString, Bytes};
+use crate::codec_redis::{BulkString, Request, Response};
+use crate::errors::ServerErrorKind;
+use crate::response::FromResponse;
+
+/// EVAL redis command
+///
+/// Invoke the execution of a server-side Lua script. Command output is
+/// decoded into any type that implements `FromResponse`.
+///
+/// ```rust,no_run
+/// use mco_redis::cmd;
+/// use mco_redis::connector::RedisConnector;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // requires real redis server, EVAL is not implemented by `MockServer`
+///     let redis = RedisConnector::new("127.0.0.1:6379").connect()?;
+///
+///     let value: i64 = redis.exec(
+///         cmd::Eval("return tonumber(ARGV[1]) + 1").arg("41")
+///     )?;
+///
+///     assert_eq!(value, 42);
+///     Ok(())
+/// }
+/// ```
+pub fn Eval<S, T>(script: S) -> EvalCommand<T>
+where
+    BulkString: From<S>,
+{
+    EvalCommand::new("EVAL", Request::BulkString(script.into()))
+}
+
+/// EVALSHA redis command
+///
+/// Evaluate a script from the server's cache by its SHA1 digest.
+pub fn EvalSha<S, T>(sha: S) -> EvalCommand<T>
+where
+    BulkString: From<S>,
+{
+    EvalCommand::new("EVALSHA", Request::BulkString(sha.into()))
+}
+
+pub struct EvalCommand<T> {
+    name: &'static str,
+    script: Request,
+    keys: Vec<Request>,
+    args: Vec<Request>,
+    _t: PhantomData<T>,
+}
+
+impl<T> Clone for EvalCommand<T> {
+    fn clone(&self) -> Self {
+        EvalCommand {
+            name: self.name,
+            script: self.script.clone(),
+            keys: self.keys.clone(),
+            args: self.args.clone(),
+            _t: PhantomData,
+        }
+    }
+}
+
+impl<T> EvalCommand<T> {
+    pub(crate) fn new(name: &'static str, script: Request) -> Self {
+        EvalCommand {
+            name,
+            script,
+            keys: Vec::new(),
+            args: Vec::new(),
+            _t: PhantomData,
+        }
+    }
+
+    /// Add a key, accessible in script via `KEYS` table.
+    pub fn key<K>(mut self, key: K) -> Self
+    where
+        BulkString: From<K>,
+    {
+        self.keys.push(key.into());
+        self
+    }
+
+    /// Add more keys to this command.
+    pub fn keys<K>(mut self, other: impl IntoIterator<Item = K>) -> Self
+    where
+        BulkString: From<K>,
+    {
+        self.keys.extend(other.into_iter().map(|t| t.into()));
+        self
+    }
+
+    /// Returns true for read-only variants, like `FCALL_RO`, which could
+    /// be executed on a replica
+    pub fn is_read_only(&self) -> bool {
+        self.name.ends_with("_RO")
+    }
+
+    /// Add an argument, accessible in script via `ARGV` table.
+    pub fn arg<A: ToArgs>(mut self, arg: A) -> Self {
+        arg.write_args(&mut self.args);
+        self
+    }
+
+    /// Add more arguments to this command.
+    pub fn args<A: ToArgs>(mut self, other: impl IntoIterator<Item = A>) -> Self {
+        for arg in other {
+            arg.write_args(&mut self.args);
+        }
+        self
+    }
+}
+
+impl<T> Command for EvalCommand<T>
+where
+    T: FromResponse,
+{
+    type Output = T;
+
+    fn to_request(self) -> Request {
+        let mut req = Vec::with_capacity(self.keys.len() + self.args.len() + 3);
+        req.push(Request::from_static(self.name));
+        req.push(self.script);
+        req.push(Request::BulkInteger(self.keys.len() as i64));
+        req.extend(self.keys);
+        req.extend(self.args);
+        Request::Array(req)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        T::from_response(val)
+    }
+}
+
+/// Lua script with its SHA1 digest
+///
+/// Script is executed with `EVALSHA` command, if script is not loaded to
+/// the server's cache yet it falls back to the `EVAL` command.
+///
+/// ```rust,no_run
+/// use mco_redis::cmd;
+/// use mco_redis::connector::RedisConnector;
+/// use mco_redis::cmd::commands::Script;
+/// # use rand::{thread_rng, Rng, distributions::Alphanumeric};
+/// # fn gen_random_key() -> String {
+/// #    thread_rng().sample_iter(&Alphanumeric).take(12).map(char::from).collect::<String>()
+/// # }
+///
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // requires real redis server, EVALSHA and EVAL are not implemented by `MockServer`
+///     let redis = RedisConnector::new("127.0.0.1:6379").connect()?;
+///     let key = gen_random_key();
+///
+///     let script = Script::new("return redis.call('INCRBY', KEYS[1], ARGV[1])");
+///
+///     let value: i64 = redis.exec_script(script.key(&key).arg("10"))?;
+///     assert_eq!(value, 10);
+///
+///     let value: i64 = redis.exec_script(script.key(&key).arg("5"))?;
+///     assert_eq!(value, 15);
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Script {
+    source: Bytes,
+    sha: ByteString,
+}
+
+impl Script {
+    /// Create new script and calculate its SHA1 digest
+    pub fn new<S: AsRef<str>>(source: S) -> Self {
+        let source = source.as_ref();
+        let sha = sha1_smol::Sha1::from(source).digest().to_string();
+        Script {
+            source: Bytes::copy_from_slice(source.as_bytes()),
+            sha: ByteString::from(sha),
+        }
+    }
+
+    /// Script source
+    pub fn source(&self) -> &[u8] {
+        &self.source
+    }
+
+    /// SHA1 digest of the script
+    pub fn sha(&self) -> &str {
+        &self.sha
+    }
+
+    /// Create script invocation with a key.
+    pub fn key<K, T>(&self, key: K) -> ScriptCall<T>
+    where
+        BulkString: From<K>,
+    {
+        self.call().key(key)
+    }
+
+    /// Create script invocation with an argument.
+    pub fn arg<A: ToArgs, T>(&self, arg: A) -> ScriptCall<T> {
+        self.call().arg(arg)
+    }
+
+    /// Create script invocation.
+    pub fn call<T>(&self) -> ScriptCall<T> {
+        ScriptCall {
+            script: self.clone(),
+            keys: Vec::new(),
+            args: Vec::new(),
+            _t: PhantomData,
+        }
+    }
+
+    /// Create `SCRIPT LOAD` command for this script.
+    pub fn load(&self) -> ScriptLoadCommand {
+        ScriptLoad(self.source.clone())
+    }
+}
+
+/// Script invocation with keys and arguments
+///
+/// Use `Client::exec_script()` or `SimpleClient::exec_script()` to
+/// execute script.
+pub struct ScriptCall<T> {
+    script: Script,
+    keys: Vec<Request>,
+    args: Vec<Request>,
+    _t: PhantomData<T>,
+}
+
+impl<T> ScriptCall<T> {
+    /// Add a key, accessible in script via `KEYS` table.
+    pub fn key<K>(mut self, key: K) -> Self
+    where
+        BulkString: From<K>,
+    {
+        self.keys.push(key.into());
+        self
+    }
+
+    /// Add more keys to this invocation.
+    pub fn keys<K>(mut self, other: impl IntoIterator<Item = K>) -> Self
+    where
+        BulkString: From<K>,
+    {
+        self.keys.extend(other.into_iter().map(|t| t.into()));
+        self
+    }
+
+    /// Add an argument, accessible in script via `ARGV` table.
+    pub fn arg<A: ToArgs>(mut self, arg: A) -> Self {
+        arg.write_args(&mut self.args);
+        self
+    }
+
+    /// Add more arguments to this invocation.
+    pub fn args<A: ToArgs>(mut self, other: impl IntoIterator<Item = A>) -> Self {
+        for arg in other {
+            arg.write_args(&mut self.args);
+        }
+        self
+    }
+
+    /// Convert invocation to `EVALSHA` command
+    pub fn evalsha(&self) -> EvalCommand<T> {
+        EvalCommand {
+            name: "EVALSHA",
+            script: Request::BulkString(self.script.sha.clone().into()),
+            keys: self.keys.clone(),
+            args: self.args.clone(),
+            _t: PhantomData,
+        }
+    }
+
+    /// Convert invocation to `EVAL` command
+    pub fn eval(self) -> EvalCommand<T> {
+        EvalCommand {
+            name: "EVAL",
+            script: Request::BulkString(self.script.source.into()),
+            keys: self.keys,
+            args: self.args,
+            _t: PhantomData,
+        }
+    }
+
+    /// Execute `EVALSHA` command and fall back to `EVAL` if the script
+    /// is not loaded.
+    pub(crate) fn exec_with<F>(self, mut exec: F) -> Result<T, CommandError>
+    where
+        F: FnMut(EvalCommand<T>) -> Result<T, CommandError>,
+    {
+        match exec(self.evalsha()) {
+            Err(CommandError::Error(ref err)) if err.kind == ServerErrorKind::NoScript => {
+                exec(self.eval())
+            }
+            res => res,
+        }
+    }
+}
+
+/// SCRIPT LOAD redis command
+///
+/// Load a script into the scripts cache, without executing it. Returns
+/// SHA1 digest of the script.
+pub fn ScriptLoad<S>(script: S) -> ScriptLoadCommand
+where
+    BulkString: From<S>,
+{
+    ScriptLoadCommand(Request::Array(vec![
+        Request::from_static("SCRIPT"),
+        Request::from_static("LOAD"),
+        Request::BulkString(script.into()),
+    ]))
+}
+
+pub struct ScriptLoadCommand(Request);
+
+impl Command for ScriptLoadCommand {
+    type Output = ByteString;
+
+    fn to_request(self) -> Request {
+        self.0
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(ByteString::try_from(val)?)
+    }
+}
+
+/// SCRIPT EXISTS redis command
+///
+/// Returns information about the existence of the scripts in the script
+/// cache, for each of the provided SHA1 digests.
+pub fn ScriptExists<S>(sha: S) -> ScriptExistsCommand
+where
+    BulkString: From<S>,
+{
+    ScriptExistsCommand(vec![
+        Request::from_static("SCRIPT"),
+        Request::from_static("EXISTS"),
+        Request::BulkString(sha.into()),
+    ])
+}
+
+pub struct ScriptExistsCommand(Vec<Request>);
+
+impl ScriptExistsCommand {
+    /// Add a SHA1 digest to this command.
+    pub fn sha<S>(mut self, sha: S) -> Self
+    where
+        BulkString: From<S>,
+    {
+        self.0.push(sha.into());
+        self
+    }
+
+    /// Add more SHA1 digests to this command.
+    pub fn shas<S>(mut self, other: impl IntoIterator<Item = S>) -> Self
+    where
+        BulkString: From<S>,
+    {
+        self.0.extend(other.into_iter().map(|t| t.into()));
+        self
+    }
+}
+
+impl Command for ScriptExistsCommand {
+    type Output = Vec<bool>;
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(Vec::try_from(val)?)
+    }
+}
+
+/// SCRIPT FLUSH redis command
+///
+/// Flush the Lua scripts cache.
+pub fn ScriptFlush() -> ScriptFlushCommand {
+    ScriptFlushCommand(vec![
+        Request::from_static("SCRIPT"),
+        Request::from_static("FLUSH"),
+    ])
+}
+
+pub struct ScriptFlushCommand(Vec<Request>);
+
+impl ScriptFlushCommand {
+    /// Set flush mode.
+    pub fn mode(mut self, mode: FlushMode) -> Self {
+        self.0.push(mode.to_request());
+        self
+    }
+}
+
+impl Command for ScriptFlushCommand {
+    type Output = ();
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        utils::OkOutputCommand::to_output(val)
+    }
+}
@@ -0,0 +1,925 @@
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
+use std::str;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::utils::{self, FlushMode};
+use super::{Command, CommandError};
+use crate::bytes::{ByteString, Bytes};
+use crate::codec_redis::{BulkString, Request, Response};
+
+/// INFO redis command
+///
+/// Returns information and statistics about the server. Use
+/// `InfoCommand::section()` to request specific sections only.
+///
+/// ```rust,no_run
+/// use mco_redis::cmd;
+/// use mco_redis::connector::RedisConnector;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // requires real redis server, INFO is not implemented by `MockServer`
+///     let redis = RedisConnector::new("127.0.0.1:6379").connect()?;
+///
+///     let info = redis.exec(cmd::Info().section("server").section("memory"))?;
+///
+///     let server = info.server.unwrap();
+///     assert!(!server.redis_version.is_empty());
+///     assert!(info.memory.unwrap().used_memory > 0);
+///     assert!(info.clients.is_none());
+///     Ok(())
+/// }
+/// ```
+pub fn Info() -> InfoCommand {
+    InfoCommand(vec![Request::from_static("INFO")])
+}
+
+pub struct InfoCommand(Vec<Request>);
+
+impl InfoCommand {
+    /// Request the section, i.e. `server`, `memory`, `all` or `everything`.
+    pub fn section<T>(mut self, name: T) -> Self
+    where
+        BulkString: From<T>,
+    {
+        self.0.push(name.into());
+        self
+    }
+}
+
+impl Command for InfoCommand {
+    type Output = ServerInfo;
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        ServerInfo::try_from(val)
+    }
+}
+
+/// Value of the `INFO` command field
+pub(crate) trait InfoValue: Sized {
+    /// Returns `None` if value is malformed
+    fn parse(val: &str) -> Option<Self>;
+}
+
+macro_rules! impl_info_value {
+    ($($tp:ty),*) => {
+        $(
+            impl InfoValue for $tp {
+                fn parse(val: &str) -> Option<Self> {
+                    val.parse().ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_info_value!(u16, u32, u64, i64, f64, String);
+
+impl InfoValue for bool {
+    fn parse(val: &str) -> Option<Self> {
+        match val {
+            "1" => Some(true),
+            "0" => Some(false),
+            _ => None,
+        }
+    }
+}
+
+impl<T: InfoValue> InfoValue for Option<T> {
+    fn parse(val: &str) -> Option<Self> {
+        T::parse(val).map(Some)
+    }
+}
+
+/// Parse field value, malformed value is reported with the field name
+pub(crate) fn parse_field<T: InfoValue>(name: &str, value: &str) -> Result<T, String> {
+    T::parse(value).ok_or_else(|| format!("Invalid value of `{}` field: {:?}", name, value))
+}
+
+macro_rules! info_section {
+    ($(#[$meta:meta])* $name:ident { $($(#[$fmeta:meta])* $field:ident: $tp:ty,)* }) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Default, PartialEq)]
+        pub struct $name {
+            $($(#[$fmeta])* pub $field: $tp,)*
+        }
+
+        impl $name {
+            fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+                match name {
+                    $(stringify!($field) => self.$field = parse_field(name, value)?,)*
+                    _ => (),
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+info_section!(
+    /// `Server` section of the `INFO` command
+    ServerSection {
+        redis_version: String,
+        /// `standalone`, `sentinel` or `cluster`
+        redis_mode: String,
+        os: String,
+        arch_bits: u32,
+        process_id: u32,
+        run_id: String,
+        tcp_port: u16,
+        uptime_in_seconds: u64,
+    }
+);
+
+info_section!(
+    /// `Clients` section of the `INFO` command
+    ClientsSection {
+        connected_clients: u64,
+        blocked_clients: u64,
+        tracking_clients: u64,
+        maxclients: u64,
+    }
+);
+
+info_section!(
+    /// `Memory` section of the `INFO` command
+    MemorySection {
+        used_memory: u64,
+        used_memory_rss: u64,
+        used_memory_peak: u64,
+        used_memory_lua: u64,
+        maxmemory: u64,
+        maxmemory_policy: String,
+        mem_fragmentation_ratio: f64,
+    }
+);
+
+info_section!(
+    /// `Persistence` section of the `INFO` command
+    PersistenceSection {
+        loading: bool,
+        rdb_changes_since_last_save: u64,
+        rdb_bgsave_in_progress: bool,
+        /// Unix timestamp of the last successful save
+        rdb_last_save_time: u64,
+        rdb_last_bgsave_status: String,
+        aof_enabled: bool,
+        aof_rewrite_in_progress: bool,
+        aof_last_bgrewrite_status: String,
+    }
+);
+
+info_section!(
+    /// `Stats` section of the `INFO` command
+    StatsSection {
+        total_connections_received: u64,
+        total_commands_processed: u64,
+        instantaneous_ops_per_sec: u64,
+        rejected_connections: u64,
+        expired_keys: u64,
+        evicted_keys: u64,
+        keyspace_hits: u64,
+        keyspace_misses: u64,
+        pubsub_channels: u64,
+    }
+);
+
+info_section!(
+    /// `Replication` section of the `INFO` command
+    ReplicationSection {
+        /// `master` or `slave`
+        role: String,
+        connected_slaves: u64,
+        master_host: Option<String>,
+        master_port: Option<u16>,
+        master_link_status: Option<String>,
+        master_replid: String,
+        master_repl_offset: i64,
+    }
+);
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// Statistics of the single database of the `Keyspace` section
+pub struct KeyspaceInfo {
+    pub keys: u64,
+    pub expires: u64,
+    /// Average ttl in milliseconds
+    pub avg_ttl: u64,
+}
+
+impl KeyspaceInfo {
+    fn parse(value: &str) -> Result<Self, String> {
+        let mut info = KeyspaceInfo::default();
+        for item in value.split(',') {
+            match item.split_once('=') {
+                Some((name @ "keys", val)) => info.keys = parse_field(name, val)?,
+                Some((name @ "expires", val)) => info.expires = parse_field(name, val)?,
+                Some((name @ "avg_ttl", val)) => info.avg_ttl = parse_field(name, val)?,
+                _ => (),
+            }
+        }
+        Ok(info)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+/// Parsed output of the `INFO` command
+///
+/// Sections are `None` if they are not requested, fields that are not
+/// covered by typed sections are available with `ServerInfo::get()`.
+pub struct ServerInfo {
+    pub server: Option<ServerSection>,
+    pub clients: Option<ClientsSection>,
+    pub memory: Option<MemorySection>,
+    pub persistence: Option<PersistenceSection>,
+    pub stats: Option<StatsSection>,
+    pub replication: Option<ReplicationSection>,
+    /// Database statistics by database index
+    pub keyspace: BTreeMap<u32, KeyspaceInfo>,
+    fields: HashMap<String, String>,
+}
+
+impl ServerInfo {
+    /// Parse `INFO` command output
+    ///
+    /// Malformed values of typed fields are reported as
+    /// `CommandError::Decode` error.
+    pub fn parse(text: &str) -> Result<Self, CommandError> {
+        ServerInfo::parse_text(text)
+            .map_err(|msg| CommandError::Decode(msg, Response::String(text.into())))
+    }
+
+    fn parse_text(text: &str) -> Result<Self, String> {
+        let mut info = ServerInfo::default();
+        let mut section = String::new();
+
+        for line in text.lines() {
+            let line = line.trim();
+            if let Some(name) = line.strip_prefix('#') {
+                section = name.trim().to_ascii_lowercase();
+                continue;
+            }
+            let (name, value) = match line.split_once(':') {
+                Some(item) => item,
+                None => continue,
+            };
+            match section.as_str() {
+                "server" => info
+                    .server
+                    .get_or_insert_with(Default::default)
+                    .set(name, value)?,
+                "clients" => info
+                    .clients
+                    .get_or_insert_with(Default::default)
+                    .set(name, value)?,
+                "memory" => info
+                    .memory
+                    .get_or_insert_with(Default::default)
+                    .set(name, value)?,
+                "persistence" => info
+                    .persistence
+                    .get_or_insert_with(Default::default)
+                    .set(name, value)?,
+                "stats" => info
+                    .stats
+                    .get_or_insert_with(Default::default)
+                    .set(name, value)?,
+                "replication" => info
+                    .replication
+                    .get_or_insert_with(Default::default)
+                    .set(name, value)?,
+                "keyspace" => {
+                    if let Some(db) = name.strip_prefix("db").and_then(|db| db.parse().ok()) {
+                        info.keyspace.insert(db, KeyspaceInfo::parse(value)?);
+                    }
+                }
+                _ => (),
+            }
+            info.fields.insert(name.to_string(), value.to_string());
+        }
+        Ok(info)
+    }
+
+    /// Get raw value of the field
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.fields.get(name).map(|s| s.as_str())
+    }
+}
+
+impl TryFrom<Response> for ServerInfo {
+    type Error = CommandError;
+
+    fn try_from(val: Response) -> Result<Self, Self::Error> {
+        let result = match val {
+            Response::Bytes(ref bytes) => match str::from_utf8(bytes) {
+                Ok(text) => ServerInfo::parse_text(text),
+                Err(_) => {
+                    return Err(CommandError::Output(
+                        "Server info is not a valid utf-8 string",
+                        val,
+                    ))
+                }
+            },
+            Response::String(ref text) => ServerInfo::parse_text(text),
+            _ => {
+                return Err(CommandError::Output(
+                    "Cannot be converted into a server info",
+                    val,
+                ))
+            }
+        };
+        result.map_err(|msg| CommandError::Decode(msg, val))
+    }
+}
+
+/// DBSIZE redis command
+///
+/// Returns the number of keys in the currently-selected database.
+pub fn DbSize() -> utils::IntOutputCommand {
+    utils::IntOutputCommand(Request::Array(vec![Request::from_static("DBSIZE")]))
+}
+
+/// FLUSHALL redis command
+///
+/// Delete all the keys of all the existing databases.
+pub fn FlushAll() -> FlushCommand {
+    FlushCommand(vec![Request::from_static("FLUSHALL")])
+}
+
+/// FLUSHDB redis command
+///
+/// Delete all the keys of the currently selected database.
+pub fn FlushDb() -> FlushCommand {
+    FlushCommand(vec![Request::from_static("FLUSHDB")])
+}
+
+pub struct FlushCommand(Vec<Request>);
+
+impl FlushCommand {
+    /// Set flush mode, server's `lazyfree-lazy-user-flush` config is used
+    /// by default.
+    pub fn mode(mut self, mode: FlushMode) -> Self {
+        self.0.truncate(1);
+        self.0.push(mode.to_request());
+        self
+    }
+}
+
+impl Command for FlushCommand {
+    type Output = ();
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        utils::OkOutputCommand::to_output(val)
+    }
+}
+
+/// LASTSAVE redis command
+///
+/// Returns the time of the last successful save to disk.
+pub fn LastSave() -> LastSaveCommand {
+    LastSaveCommand(Request::Array(vec![Request::from_static("LASTSAVE")]))
+}
+
+pub struct LastSaveCommand(Request);
+
+impl Command for LastSaveCommand {
+    type Output = SystemTime;
+
+    fn to_request(self) -> Request {
+        self.0
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(UNIX_EPOCH + Duration::from_secs(u64::try_from(val)?))
+    }
+}
+
+/// SAVE redis command
+///
+/// Performs a synchronous save of the dataset. Blocks all the other
+/// clients, use `BGSAVE` in production.
+pub fn Save() -> utils::OkOutputCommand {
+    utils::OkOutputCommand(Request::Array(vec![Request::from_static("SAVE")]))
+}
+
+/// BGSAVE redis command
+///
+/// Saves the database in background.
+pub fn BgSave() -> BackgroundCommand {
+    BackgroundCommand(vec![Request::from_static("BGSAVE")])
+}
+
+/// BGREWRITEAOF redis command
+///
+/// Starts append only file rewrite process in background.
+pub fn BgRewriteAof() -> BackgroundCommand {
+    BackgroundCommand(vec![Request::from_static("BGREWRITEAOF")])
+}
+
+pub struct BackgroundCommand(Vec<Request>);
+
+impl BackgroundCommand {
+    /// Schedule `BGSAVE` if `AOF` rewrite is in progress instead of
+    /// returning an error.
+    pub fn schedule(mut self) -> Self {
+        self.0.push(Request::from_bstatic(b"SCHEDULE"));
+        self
+    }
+}
+
+impl Command for BackgroundCommand {
+    type Output = ();
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        // status message depends on the command and server version
+        match val {
+            Response::String(_) => Ok(()),
+            _ => Err(CommandError::Output("Cannot parse response", val)),
+        }
+    }
+}
+
+/// TIME redis command
+///
+/// Returns the current server time.
+pub fn Time() -> TimeCommand {
+    TimeCommand(Request::Array(vec![Request::from_static("TIME")]))
+}
+
+pub struct TimeCommand(Request);
+
+impl Command for TimeCommand {
+    type Output = SystemTime;
+
+    fn to_request(self) -> Request {
+        self.0
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        let (secs, micros) = <(Bytes, Bytes)>::try_from(val)?;
+        match (btoi::btou::<u64>(&secs), btoi::btou::<u64>(&micros)) {
+            (Ok(secs), Ok(micros)) => {
+                Ok(UNIX_EPOCH + Duration::from_secs(secs) + Duration::from_micros(micros))
+            }
+            _ => Err(CommandError::Output(
+                "Cannot parse server time",
+                Response::Array(vec![Response::Bytes(secs), Response::Bytes(micros)]),
+            )),
+        }
+    }
+}
+
+/// CONFIG GET redis command
+///
+/// Returns the values of configuration parameters matching the glob-style
+/// pattern.
+pub fn ConfigGet<T>(pattern: T) -> ConfigGetCommand
+where
+    BulkString: From<T>,
+{
+    ConfigGetCommand(vec![
+        Request::from_static("CONFIG"),
+        Request::from_static("GET"),
+        Request::BulkString(pattern.into()),
+    ])
+}
+
+pub struct ConfigGetCommand(Vec<Request>);
+
+impl ConfigGetCommand {
+    /// Add a pattern to this command, requires redis 7.0 or later.
+    pub fn pattern<T>(mut self, pattern: T) -> Self
+    where
+        BulkString: From<T>,
+    {
+        self.0.push(pattern.into());
+        self
+    }
+}
+
+impl Command for ConfigGetCommand {
+    type Output = HashMap<ByteString, ByteString>;
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(HashMap::try_from(val)?)
+    }
+}
+
+/// CONFIG SET redis command
+///
+/// Sets configuration parameters at run time.
+pub fn ConfigSet<T, V>(parameter: T, value: V) -> ConfigSetCommand
+where
+    BulkString: From<T> + From<V>,
+{
+    ConfigSetCommand(vec![
+        Request::from_static("CONFIG"),
+        Request::from_static("SET"),
+        Request::BulkString(parameter.into()),
+        Request::BulkString(value.into()),
+    ])
+}
+
+pub struct ConfigSetCommand(Vec<Request>);
+
+impl ConfigSetCommand {
+    /// Add a parameter to this command, requires redis 7.0 or later.
+    pub fn parameter<T, V>(mut self, parameter: T, value: V) -> Self
+    where
+        BulkString: From<T> + From<V>,
+    {
+        self.0.push(parameter.into());
+        self.0.push(value.into());
+        self
+    }
+}
+
+impl Command for ConfigSetCommand {
+    type Output = ();
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        utils::OkOutputCommand::to_output(val)
+    }
+}
+
+/// CONFIG RESETSTAT redis command
+///
+/// Resets the statistics reported by the `INFO` command.
+pub fn ConfigResetStat() -> utils::OkOutputCommand {
+    utils::OkOutputCommand(Request::Array(vec![
+        Request::from_static("CONFIG"),
+        Request::from_static("RESETSTAT"),
+    ]))
+}
+
+/// CONFIG REWRITE redis command
+///
+/// Rewrites config file with the in memory configuration.
+pub fn ConfigRewrite() -> utils::OkOutputCommand {
+    utils::OkOutputCommand(Request::Array(vec![
+        Request::from_static("CONFIG"),
+        Request::from_static("REWRITE"),
+    ]))
+}
+
+/// MEMORY USAGE redis command
+///
+/// Returns the number of bytes that a key and its value require to be
+/// stored in RAM, `None` is returned if key does not exist.
+pub fn MemoryUsage<T>(key: T) -> MemoryUsageCommand
+where
+    BulkString: From<T>,
+{
+    MemoryUsageCommand(vec![
+        Request::from_static("MEMORY"),
+        Request::from_static("USAGE"),
+        Request::BulkString(key.into()),
+    ])
+}
+
+pub struct MemoryUsageCommand(Vec<Request>);
+
+impl MemoryUsageCommand {
+    /// Number of sampled nested values, 0 samples all of them.
+    pub fn samples(mut self, count: usize) -> Self {
+        self.0.push(Request::from_bstatic(b"SAMPLES"));
+        self.0.push(Request::BulkInteger(count as i64));
+        self
+    }
+}
+
+impl Command for MemoryUsageCommand {
+    type Output = Option<u64>;
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(Option::try_from(val)?)
+    }
+}
+
+/// MEMORY STATS redis command
+///
+/// Returns memory usage details of the server.
+pub fn MemoryStats() -> MemoryStatsCommand {
+    MemoryStatsCommand(Request::Array(vec![
+        Request::from_static("MEMORY"),
+        Request::from_static("STATS"),
+    ]))
+}
+
+pub struct MemoryStatsCommand(Request);
+
+#[derive(Debug, Clone, Default, PartialEq)]
+/// Output of the `MEMORY STATS` command
+pub struct MemoryStatsInfo {
+    pub peak_allocated: u64,
+    pub total_allocated: u64,
+    pub startup_allocated: u64,
+    pub keys_count: u64,
+    pub dataset_bytes: u64,
+    pub dataset_percentage: f64,
+    pub fragmentation: f64,
+}
+
+impl Command for MemoryStatsCommand {
+    type Output = MemoryStatsInfo;
+
+    fn to_request(self) -> Request {
+        self.0
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        let mut stats = MemoryStatsInfo::default();
+        for (name, value) in utils::map_entries(val)? {
+            match name.as_ref() {
+                "peak.allocated" => stats.peak_allocated = u64::try_from(value)?,
+                "total.allocated" => stats.total_allocated = u64::try_from(value)?,
+                "startup.allocated" => stats.startup_allocated = u64::try_from(value)?,
+                "keys.count" => stats.keys_count = u64::try_from(value)?,
+                "dataset.bytes" => stats.dataset_bytes = u64::try_from(value)?,
+                "dataset.percentage" => stats.dataset_percentage = f64::try_from(value)?,
+                "fragmentation" => stats.fragmentation = f64::try_from(value)?,
+                _ => (),
+            }
+        }
+        Ok(stats)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Entry of the `SLOWLOG GET` command output
+pub struct SlowlogEntry {
+    /// Unique progressive identifier of the entry
+    pub id: i64,
+    /// Time the command was processed
+    pub timestamp: SystemTime,
+    /// Execution time of the command
+    pub duration: Duration,
+    /// Command arguments
+    pub args: Vec<Bytes>,
+    /// Client address, empty for redis versions before 4.0
+    pub client_addr: ByteString,
+    /// Client name set with `CLIENT SETNAME`
+    pub client_name: ByteString,
+}
+
+impl TryFrom<Response> for SlowlogEntry {
+    type Error = (&'static str, Response);
+
+    fn try_from(val: Response) -> Result<Self, Self::Error> {
+        let mut items = match val {
+            Response::Array(items) if items.len() >= 4 => items.into_iter(),
+            _ => return Err(("Cannot be converted into a slowlog entry", val)),
+        };
+        let mut next = || items.next().unwrap_or(Response::Nil);
+
+        Ok(SlowlogEntry {
+            id: i64::try_from(next())?,
+            timestamp: UNIX_EPOCH + Duration::from_secs(u64::try_from(next())?),
+            duration: Duration::from_micros(u64::try_from(next())?),
+            args: Vec::try_from(next())?,
+            client_addr: Option::try_from(next())?.unwrap_or_else(ByteString::new),
+            client_name: Option::try_from(next())?.unwrap_or_else(ByteString::new),
+        })
+    }
+}
+
+/// SLOWLOG GET redis command
+///
+/// Returns entries of the slow log, most recent entries first.
+///
+/// ```rust,no_run
+/// use mco_redis::cmd;
+/// use mco_redis::connector::RedisConnector;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // requires real redis server, SLOWLOG is not implemented by `MockServer`
+///     let redis = RedisConnector::new("127.0.0.1:6379").connect()?;
+///
+///     for entry in redis.exec(cmd::SlowlogGet().count(10))? {
+///         println!("{:?} took {:?} from {}", entry.args, entry.duration, entry.client_addr);
+///     }
+///     redis.exec(cmd::SlowlogReset())?;
+///     Ok(())
+/// }
+/// ```
+pub fn SlowlogGet() -> SlowlogGetCommand {
+    SlowlogGetCommand(vec![
+        Request::from_static("SLOWLOG"),
+        Request::from_static("GET"),
+    ])
+}
+
+pub struct SlowlogGetCommand(Vec<Request>);
+
+impl SlowlogGetCommand {
+    /// Number of returned entries, default is 10 and -1 returns all entries.
+    pub fn count(mut self, count: i64) -> Self {
+        self.0.truncate(2);
+        self.0.push(Request::BulkInteger(count));
+        self
+    }
+}
+
+impl Command for SlowlogGetCommand {
+    type Output = Vec<SlowlogEntry>;
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(Vec::try_from(val)?)
+    }
+}
+
+/// SLOWLOG LEN redis command
+///
+/// Returns the number of entries in the slow log.
+pub fn SlowlogLen() -> utils::IntOutputCommand {
+    utils::IntOutputCommand(Request::Array(vec![
+        Request::from_static("SLOWLOG"),
+        Request::from_static("LEN"),
+    ]))
+}
+
+/// SLOWLOG RESET redis command
+///
+/// Removes all entries of the slow log.
+pub fn SlowlogReset() -> utils::OkOutputCommand {
+    utils::OkOutputCommand(Request::Array(vec![
+        Request::from_static("SLOWLOG"),
+        Request::from_static("RESET"),
+    ]))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Entry of the `LATENCY LATEST` command output
+pub struct LatencyEvent {
+    /// Event name, i.e. `command` or `fork`
+    pub event: ByteString,
+    /// Time of the latest latency spike
+    pub timestamp: SystemTime,
+    /// Latest latency of the event
+    pub latest: Duration,
+    /// All-time maximum latency of the event
+    pub max: Duration,
+}
+
+impl TryFrom<Response> for LatencyEvent {
+    type Error = (&'static str, Response);
+
+    fn try_from(val: Response) -> Result<Self, Self::Error> {
+        let mut items = match val {
+            Response::Array(items) if items.len() >= 4 => items.into_iter(),
+            _ => return Err(("Cannot be converted into a latency event", val)),
+        };
+        let mut next = || items.next().unwrap_or(Response::Nil);
+
+        Ok(LatencyEvent {
+            event: ByteString::try_from(next())?,
+            timestamp: UNIX_EPOCH + Duration::from_secs(u64::try_from(next())?),
+            latest: Duration::from_millis(u64::try_from(next())?),
+            max: Duration::from_millis(u64::try_from(next())?),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Entry of the `LATENCY HISTORY` command output
+pub struct LatencySample {
+    pub timestamp: SystemTime,
+    pub latency: Duration,
+}
+
+impl TryFrom<Response> for LatencySample {
+    type Error = (&'static str, Response);
+
+    fn try_from(val: Response) -> Result<Self, Self::Error> {
+        let (timestamp, latency) = <(u64, u64)>::try_from(val)?;
+        Ok(LatencySample {
+            timestamp: UNIX_EPOCH + Duration::from_secs(timestamp),
+            latency: Duration::from_millis(latency),
+        })
+    }
+}
+
+/// LATENCY LATEST redis command
+///
+/// Returns the latest latency samples for all events.
+pub fn LatencyLatest() -> LatencyLatestCommand {
+    LatencyLatestCommand(Request::Array(vec![
+        Request::from_static("LATENCY"),
+        Request::from_static("LATEST"),
+    ]))
+}
+
+pub struct LatencyLatestCommand(Request);
+
+impl Command for LatencyLatestCommand {
+    type Output = Vec<LatencyEvent>;
+
+    fn to_request(self) -> Request {
+        self.0
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(Vec::try_from(val)?)
+    }
+}
+
+/// LATENCY HISTORY redis command
+///
+/// Returns latency time series of the event.
+pub fn LatencyHistory<T>(event: T) -> LatencyHistoryCommand
+where
+    BulkString: From<T>,
+{
+    LatencyHistoryCommand(Request::Array(vec![
+        Request::from_static("LATENCY"),
+        Request::from_static("HISTORY"),
+        Request::BulkString(event.into()),
+    ]))
+}
+
+pub struct LatencyHistoryCommand(Request);
+
+impl Command for LatencyHistoryCommand {
+    type Output = Vec<LatencySample>;
+
+    fn to_request(self) -> Request {
+        self.0
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(Vec::try_from(val)?)
+    }
+}
+
+/// LATENCY RESET redis command
+///
+/// Resets latency data of all events or of the specified events only.
+/// Returns the number of reset events.
+pub fn LatencyReset() -> LatencyResetCommand {
+    LatencyResetCommand(vec![
+        Request::from_static("LATENCY"),
+        Request::from_static("RESET"),
+    ])
+}
+
+pub struct LatencyResetCommand(Vec<Request>);
+
+impl LatencyResetCommand {
+    /// Reset the event.
+    pub fn event<T>(mut self, event: T) -> Self
+    where
+        BulkString: From<T>,
+    {
+        self.0.push(event.into());
+        self
+    }
+
+    /// Reset more events.
+    pub fn events<T>(mut self, other: impl IntoIterator<Item = T>) -> Self
+    where
+        BulkString: From<T>,
+    {
+        self.0.extend(other.into_iter().map(|t| t.into()));
+        self
+    }
+}
+
+impl Command for LatencyResetCommand {
+    type Output = usize;
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(usize::try_from(val)?)
+    }
+}
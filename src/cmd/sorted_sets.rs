@@ -0,0 +1,210 @@
+use std::convert::TryFrom;
+use std::time::Duration;
+
+use super::{utils, BlockingCommand, CommandError};
+use crate::bytes::Bytes;
+use crate::codec_redis::{BulkString, Request, Response};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// Side of the sorted set
+pub enum ZSetSide {
+    /// Members with the lowest scores
+    Min,
+    /// Members with the highest scores
+    Max,
+}
+
+impl ZSetSide {
+    pub(crate) fn to_request(self) -> Request {
+        match self {
+            ZSetSide::Min => Request::from_static("MIN"),
+            ZSetSide::Max => Request::from_static("MAX"),
+        }
+    }
+}
+
+/// BZPOPMIN redis command
+///
+/// Blocking version of the `ZPOPMIN` command. Pops a member with the
+/// lowest score from the first non-empty sorted set of the provided keys,
+/// or blocks the connection until a member is available or `timeout`
+/// expires. Returns name of the key, popped member and its score.
+///
+/// Blocking commands cannot be executed by shared `Client`, use
+/// `SimpleClient::exec_blocking()` on a dedicated connection.
+pub fn BZPopMin<T>(key: T, timeout: Duration) -> BZPopCommand
+where
+    BulkString: From<T>,
+{
+    BZPopCommand {
+        req: vec![
+            Request::from_static("BZPOPMIN"),
+            Request::BulkString(key.into()),
+        ],
+        timeout,
+    }
+}
+
+/// BZPOPMAX redis command
+///
+/// Blocking version of the `ZPOPMAX` command. Pops a member with the
+/// highest score from the first non-empty sorted set of the provided keys,
+/// or blocks the connection until a member is available or `timeout`
+/// expires. Returns name of the key, popped member and its score.
+pub fn BZPopMax<T>(key: T, timeout: Duration) -> BZPopCommand
+where
+    BulkString: From<T>,
+{
+    BZPopCommand {
+        req: vec![
+            Request::from_static("BZPOPMAX"),
+            Request::BulkString(key.into()),
+        ],
+        timeout,
+    }
+}
+
+pub struct BZPopCommand {
+    req: Vec<Request>,
+    timeout: Duration,
+}
+
+impl BZPopCommand {
+    /// Add a key to this command.
+    pub fn key<T>(mut self, other: T) -> Self
+    where
+        BulkString: From<T>,
+    {
+        self.req.push(other.into());
+        self
+    }
+
+    /// Add more keys to this command.
+    pub fn keys<T>(mut self, other: impl IntoIterator<Item = T>) -> Self
+    where
+        BulkString: From<T>,
+    {
+        self.req.extend(other.into_iter().map(|t| t.into()));
+        self
+    }
+}
+
+impl BlockingCommand for BZPopCommand {
+    type Output = Option<(Bytes, Bytes, f64)>;
+
+    fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    fn to_request(mut self) -> Request {
+        self.req.push(utils::timeout_request(self.timeout));
+        Request::Array(self.req)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(Option::try_from(val)?)
+    }
+}
+
+/// BZMPOP redis command
+///
+/// Pops one or more members from the first non-empty sorted set of the
+/// provided keys, or blocks the connection until a member is available
+/// or `timeout` expires. Returns name of the key and popped members with
+/// their scores.
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use mco_redis::cmd;
+/// use mco_redis::connector::RedisConnector;
+/// use mco_redis::cmd::commands::ZSetSide;
+/// # use rand::{thread_rng, Rng, distributions::Alphanumeric};
+/// # fn gen_random_key() -> String {
+/// #    thread_rng().sample_iter(&Alphanumeric).take(12).map(char::from).collect::<String>()
+/// # }
+///
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // requires real redis server, BZMPOP is not implemented by `MockServer`
+///     let redis = RedisConnector::new("127.0.0.1:6379").connect_simple()?;
+///     let key = gen_random_key();
+///
+///     // sorted set is empty, command returns after timeout
+///     let result = redis.exec_blocking(
+///         cmd::BZMPop(&key, ZSetSide::Min, Duration::from_millis(100)).count(2)
+///     )?;
+///
+///     assert!(result.is_none());
+///     Ok(())
+/// }
+/// ```
+pub fn BZMPop<T>(key: T, side: ZSetSide, timeout: Duration) -> BZMPopCommand
+where
+    BulkString: From<T>,
+{
+    BZMPopCommand {
+        keys: vec![Request::BulkString(key.into())],
+        side,
+        count: None,
+        timeout,
+    }
+}
+
+pub struct BZMPopCommand {
+    keys: Vec<Request>,
+    side: ZSetSide,
+    count: Option<usize>,
+    timeout: Duration,
+}
+
+impl BZMPopCommand {
+    /// Add a key to this command.
+    pub fn key<T>(mut self, other: T) -> Self
+    where
+        BulkString: From<T>,
+    {
+        self.keys.push(other.into());
+        self
+    }
+
+    /// Add more keys to this command.
+    pub fn keys<T>(mut self, other: impl IntoIterator<Item = T>) -> Self
+    where
+        BulkString: From<T>,
+    {
+        self.keys.extend(other.into_iter().map(|t| t.into()));
+        self
+    }
+
+    /// Pop up to `count` members.
+    pub fn count(mut self, count: usize) -> Self {
+        self.count = Some(count);
+        self
+    }
+}
+
+impl BlockingCommand for BZMPopCommand {
+    type Output = Option<(Bytes, Vec<(Bytes, f64)>)>;
+
+    fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    fn to_request(self) -> Request {
+        let mut req = Vec::with_capacity(self.keys.len() + 6);
+        req.push(Request::from_static("BZMPOP"));
+        req.push(utils::timeout_request(self.timeout));
+        req.push(Request::BulkInteger(self.keys.len() as i64));
+        req.extend(self.keys);
+        req.push(self.side.to_request());
+        if let Some(count) = self.count {
+            req.push(Request::from_bstatic(b"COUNT"));
+            req.push(Request::BulkInteger(count as i64));
+        }
+        Request::Array(req)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(Option::try_from(val)?)
+    }
+}
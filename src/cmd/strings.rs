@@ -1,5 +1,11 @@
+use std::convert::TryFrom;
+use std::marker::PhantomData;
+
 use super::{utils, Command, CommandError};
+use crate::args::ToArg;
+use crate::bytes::{ByteString, Bytes};
 use crate::codec_redis::{BulkString, Request, Response};
+use crate::response::FromResponse;
 
 /// GET redis command
 pub fn Get<T>(key: T) -> utils::BulkOutputCommand
@@ -18,14 +24,16 @@ where
 /// otherwise it returns false
 pub fn Set<T, V>(key: T, value: V) -> SetCommand
 where
-    BulkString: From<T> + From<V>,
+    BulkString: From<T>,
+    V: ToArg,
 {
+    let req = vec![
+        Request::from_bstatic(b"SET"),
+        Request::BulkString(key.into()),
+        value.to_arg(),
+    ];
     SetCommand {
-        req: vec![
-            Request::from_bstatic(b"SET"),
-            Request::BulkString(key.into()),
-            Request::BulkString(value.into()),
-        ],
+        req,
         expire: Expire::None,
         keepttl: false,
         exists: None,
@@ -36,6 +44,22 @@ enum Expire {
     None,
     Ex(Request),
     Px(Request),
+    ExAt(Request),
+    PxAt(Request),
+}
+
+impl Expire {
+    fn push_to(self, req: &mut Vec<Request>) {
+        let (name, val): (&'static [u8], _) = match self {
+            Expire::None => return,
+            Expire::Ex(r) => (b"EX", r),
+            Expire::Px(r) => (b"PX", r),
+            Expire::ExAt(r) => (b"EXAT", r),
+            Expire::PxAt(r) => (b"PXAT", r),
+        };
+        req.push(Request::from_bstatic(name));
+        req.push(val);
+    }
 }
 
 pub struct SetCommand {
@@ -58,6 +82,18 @@ impl SetCommand {
         self
     }
 
+    /// Set the specified Unix time at which the key will expire, in seconds.
+    pub fn expire_at_secs(mut self, timestamp: i64) -> Self {
+        self.expire = Expire::ExAt(Request::BulkInteger(timestamp));
+        self
+    }
+
+    /// Set the specified Unix time at which the key will expire, in milliseconds.
+    pub fn expire_at_millis(mut self, timestamp: i64) -> Self {
+        self.expire = Expire::PxAt(Request::BulkInteger(timestamp));
+        self
+    }
+
     /// Only set the key if it already exist.
     pub fn if_exists(mut self) -> Self {
         self.exists = Some(true);
@@ -75,24 +111,17 @@ impl SetCommand {
         self.keepttl = true;
         self
     }
-}
 
-impl Command for SetCommand {
-    type Output = bool;
+    /// Return the old string stored at key, or nil if key did not exist.
+    ///
+    /// Changes command output to the old value of the key.
+    pub fn get(self) -> SetGetCommand {
+        SetGetCommand(self)
+    }
 
-    fn to_request(mut self) -> Request {
-        // EX|PX
-        match self.expire {
-            Expire::None => (),
-            Expire::Ex(r) => {
-                self.req.push(Request::from_bstatic(b"EX"));
-                self.req.push(r);
-            }
-            Expire::Px(r) => {
-                self.req.push(Request::from_bstatic(b"PX"));
-                self.req.push(r);
-            }
-        }
+    fn into_request_vec(mut self) -> Vec<Request> {
+        // EX|PX|EXAT|PXAT
+        self.expire.push_to(&mut self.req);
 
         // NX|XX
         if let Some(exists) = self.exists {
@@ -108,7 +137,15 @@ impl Command for SetCommand {
             self.req.push(Request::from_bstatic(b"KEEPTTL"))
         }
 
-        Request::Array(self.req)
+        self.req
+    }
+}
+
+impl Command for SetCommand {
+    type Output = bool;
+
+    fn to_request(self) -> Request {
+        Request::Array(self.into_request_vec())
     }
 
     fn to_output(val: Response) -> Result<Self::Output, CommandError> {
@@ -126,6 +163,440 @@ impl Command for SetCommand {
     }
 }
 
+/// SET redis command with GET option
+///
+/// Returns the old string stored at key, or `None` if key did not exist.
+///
+/// ```rust
+/// use mco_redis::cmd;
+/// use mco_redis::testing::MockServer;
+/// # use rand::{thread_rng, Rng, distributions::Alphanumeric};
+/// # fn gen_random_key() -> String {
+/// #    thread_rng().sample_iter(&Alphanumeric).take(12).map(char::from).collect::<String>()
+/// # }
+///
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let server = MockServer::start()?;
+///     let redis = server.connector().connect()?;
+///     let key = gen_random_key();
+///
+///     redis.exec(cmd::Set(&key, "value"))?;
+///
+///     // replace value and get previous one
+///     let value = redis.exec(cmd::Set(&key, "value2").get())?;
+///
+///     assert_eq!(value.unwrap(), "value");
+///     Ok(())
+/// }
+/// ```
+pub struct SetGetCommand(SetCommand);
+
+impl Command for SetGetCommand {
+    type Output = Option<Bytes>;
+
+    fn to_request(self) -> Request {
+        let mut req = self.0.into_request_vec();
+        req.push(Request::from_bstatic(b"GET"));
+        Request::Array(req)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        utils::BulkOutputCommand::to_output(val)
+    }
+}
+
+/// SETNX redis command
+///
+/// Set key to hold string value if key does not exist. Command returns
+/// true if value is set.
+pub fn SetNx<T, V>(key: T, value: V) -> utils::BoolOutputCommand
+where
+    BulkString: From<T> + From<V>,
+{
+    utils::BoolOutputCommand(Request::Array(vec![
+        Request::from_static("SETNX"),
+        Request::BulkString(key.into()),
+        Request::BulkString(value.into()),
+    ]))
+}
+
+/// SETEX redis command
+///
+/// Set key to hold the string value and set key to timeout after a given
+/// number of seconds.
+pub fn SetEx<T, V>(key: T, seconds: i64, value: V) -> utils::OkOutputCommand
+where
+    BulkString: From<T> + From<V>,
+{
+    utils::OkOutputCommand(Request::Array(vec![
+        Request::from_static("SETEX"),
+        Request::BulkString(key.into()),
+        Request::BulkInteger(seconds),
+        Request::BulkString(value.into()),
+    ]))
+}
+
+/// PSETEX redis command
+///
+/// Works exactly like `SETEX` with the sole difference that the expire
+/// time is specified in milliseconds instead of seconds.
+pub fn PSetEx<T, V>(key: T, millis: i64, value: V) -> utils::OkOutputCommand
+where
+    BulkString: From<T> + From<V>,
+{
+    utils::OkOutputCommand(Request::Array(vec![
+        Request::from_static("PSETEX"),
+        Request::BulkString(key.into()),
+        Request::BulkInteger(millis),
+        Request::BulkString(value.into()),
+    ]))
+}
+
+/// GETSET redis command
+///
+/// Atomically sets key to value and returns the old value stored at key.
+pub fn GetSet<T, V>(key: T, value: V) -> utils::BulkOutputCommand
+where
+    BulkString: From<T> + From<V>,
+{
+    utils::BulkOutputCommand(Request::Array(vec![
+        Request::from_static("GETSET"),
+        Request::BulkString(key.into()),
+        Request::BulkString(value.into()),
+    ]))
+}
+
+/// GETDEL redis command
+///
+/// Get the value of key and delete the key.
+pub fn GetDel<T>(key: T) -> utils::BulkOutputCommand
+where
+    BulkString: From<T>,
+{
+    utils::BulkOutputCommand(Request::Array(vec![
+        Request::from_static("GETDEL"),
+        Request::BulkString(key.into()),
+    ]))
+}
+
+/// GETEX redis command
+///
+/// Get the value of key and optionally set its expiration.
+///
+/// ```rust,no_run
+/// use mco_redis::cmd;
+/// use mco_redis::connector::RedisConnector;
+/// # use rand::{thread_rng, Rng, distributions::Alphanumeric};
+/// # fn gen_random_key() -> String {
+/// #    thread_rng().sample_iter(&Alphanumeric).take(12).map(char::from).collect::<String>()
+/// # }
+///
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // requires real redis server, GETEX is not implemented by `MockServer`
+///     let redis = RedisConnector::new("127.0.0.1:6379").connect()?;
+///     let key = gen_random_key();
+///
+///     redis.exec(cmd::Set(&key, "value"))?;
+///
+///     // get value and set expire time
+///     let value = redis.exec(cmd::GetEx(&key).expire_secs(100))?;
+///
+///     assert_eq!(value.unwrap(), "value");
+///     Ok(())
+/// }
+/// ```
+pub fn GetEx<T>(key: T) -> GetExCommand
+where
+    BulkString: From<T>,
+{
+    GetExCommand {
+        req: vec![
+            Request::from_static("GETEX"),
+            Request::BulkString(key.into()),
+        ],
+        expire: Expire::None,
+        persist: false,
+    }
+}
+
+pub struct GetExCommand {
+    req: Vec<Request>,
+    expire: Expire,
+    persist: bool,
+}
+
+impl GetExCommand {
+    /// Set the specified expire time, in seconds.
+    pub fn expire_secs(mut self, secs: i64) -> Self {
+        self.expire = Expire::Ex(Request::BulkInteger(secs));
+        self
+    }
+
+    /// Set the specified expire time, in milliseconds.
+    pub fn expire_millis(mut self, millis: i64) -> Self {
+        self.expire = Expire::Px(Request::BulkInteger(millis));
+        self
+    }
+
+    /// Set the specified Unix time at which the key will expire, in seconds.
+    pub fn expire_at_secs(mut self, timestamp: i64) -> Self {
+        self.expire = Expire::ExAt(Request::BulkInteger(timestamp));
+        self
+    }
+
+    /// Set the specified Unix time at which the key will expire, in milliseconds.
+    pub fn expire_at_millis(mut self, timestamp: i64) -> Self {
+        self.expire = Expire::PxAt(Request::BulkInteger(timestamp));
+        self
+    }
+
+    /// Remove the time to live associated with the key.
+    pub fn persist(mut self) -> Self {
+        self.persist = true;
+        self
+    }
+}
+
+impl Command for GetExCommand {
+    type Output = Option<Bytes>;
+
+    fn to_request(mut self) -> Request {
+        if self.persist {
+            self.req.push(Request::from_bstatic(b"PERSIST"));
+        } else {
+            self.expire.push_to(&mut self.req);
+        }
+        Request::Array(self.req)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        utils::BulkOutputCommand::to_output(val)
+    }
+}
+
+/// MGET redis command
+///
+/// Returns the values of all specified keys. For every key that does not
+/// hold a string value or does not exist, `None` is returned.
+///
+/// ```rust
+/// use mco_redis::cmd;
+/// use mco_redis::testing::MockServer;
+/// # use rand::{thread_rng, Rng, distributions::Alphanumeric};
+/// # fn gen_random_key() -> String {
+/// #    thread_rng().sample_iter(&Alphanumeric).take(12).map(char::from).collect::<String>()
+/// # }
+///
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let server = MockServer::start()?;
+///     let redis = server.connector().connect()?;
+///     let key1 = gen_random_key();
+///     let key2 = gen_random_key();
+///
+///     redis.exec(cmd::MSet(&key1, "value1").entry(&key2, "value2"))?;
+///
+///     // get multiple values
+///     let values = redis.exec(cmd::MGet(&key1).key(&key2).key("test_unknown"))?;
+///
+///     assert_eq!(values.len(), 3);
+///     assert_eq!(values[0].as_ref().unwrap(), "value1");
+///     assert!(values[2].is_none());
+///     Ok(())
+/// }
+/// ```
+pub fn MGet<T>(key: T) -> MGetCommand
+where
+    BulkString: From<T>,
+{
+    MGetCommand(vec![
+        Request::from_static("MGET"),
+        Request::BulkString(key.into()),
+    ])
+}
+
+pub struct MGetCommand(Vec<Request>);
+
+impl MGetCommand {
+    /// Add a key to this command.
+    pub fn key<T>(mut self, other: T) -> Self
+    where
+        BulkString: From<T>,
+    {
+        self.0.push(other.into());
+        self
+    }
+
+    /// Add more keys to this command.
+    pub fn keys<T>(mut self, other: impl IntoIterator<Item = T>) -> Self
+    where
+        BulkString: From<T>,
+    {
+        self.0.extend(other.into_iter().map(|t| t.into()));
+        self
+    }
+
+    /// Decode values into a different type
+    pub fn output<T: FromResponse>(self) -> utils::OutputCommand<Vec<Option<T>>> {
+        utils::OutputCommand(Request::Array(self.0), PhantomData)
+    }
+}
+
+impl Command for MGetCommand {
+    type Output = Vec<Option<Bytes>>;
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        utils::MultiBulkOutputCommand::to_output(val)
+    }
+}
+
+/// MSET redis command
+///
+/// Sets the given keys to their respective values. Command always
+/// returns true.
+pub fn MSet<T, V>(key: T, value: V) -> MSetCommand
+where
+    BulkString: From<T>,
+    V: ToArg,
+{
+    MSetCommand(vec![Request::from_static("MSET")]).entry(key, value)
+}
+
+/// MSETNX redis command
+///
+/// Sets the given keys to their respective values. Values are not set
+/// at all if at least one key already exists, in that case command
+/// returns false.
+pub fn MSetNx<T, V>(key: T, value: V) -> MSetCommand
+where
+    BulkString: From<T>,
+    V: ToArg,
+{
+    MSetCommand(vec![Request::from_static("MSETNX")]).entry(key, value)
+}
+
+pub struct MSetCommand(Vec<Request>);
+
+impl MSetCommand {
+    /// Add a key/value pair to this command.
+    pub fn entry<T, V>(mut self, key: T, value: V) -> Self
+    where
+        BulkString: From<T>,
+        V: ToArg,
+    {
+        self.0.push(key.into());
+        self.0.push(value.to_arg());
+        self
+    }
+
+    /// Add more key/value pairs to this command.
+    pub fn entries<T, V>(mut self, other: impl IntoIterator<Item = (T, V)>) -> Self
+    where
+        BulkString: From<T>,
+        V: ToArg,
+    {
+        for (key, value) in other {
+            self = self.entry(key, value);
+        }
+        self
+    }
+}
+
+impl Command for MSetCommand {
+    type Output = bool;
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        match val {
+            Response::String(ref string) if string == "OK" => Ok(true),
+            Response::Integer(_) => Ok(bool::try_from(val)?),
+            _ => Err(CommandError::Output("Unexpected value", val)),
+        }
+    }
+}
+
+/// SETRANGE redis command
+///
+/// Overwrites part of the string stored at key, starting at the specified
+/// offset. Returns the length of the string after it was modified.
+pub fn SetRange<T, V>(key: T, offset: usize, value: V) -> utils::IntOutputCommand
+where
+    BulkString: From<T> + From<V>,
+{
+    utils::IntOutputCommand(Request::Array(vec![
+        Request::from_static("SETRANGE"),
+        Request::BulkString(key.into()),
+        Request::BulkInteger(offset as i64),
+        Request::BulkString(value.into()),
+    ]))
+}
+
+/// GETRANGE redis command
+///
+/// Returns the substring of the string value stored at key, determined by
+/// the offsets `start` and `end` (both are inclusive).
+pub fn GetRange<T>(key: T, start: i64, end: i64) -> utils::BulkOutputCommand
+where
+    BulkString: From<T>,
+{
+    utils::BulkOutputCommand(Request::Array(vec![
+        Request::from_static("GETRANGE"),
+        Request::BulkString(key.into()),
+        Request::BulkInteger(start),
+        Request::BulkInteger(end),
+    ]))
+}
+
+/// APPEND redis command
+///
+/// Appends the value at the end of the string. Returns the length of the
+/// string after the append operation.
+pub fn Append<T, V>(key: T, value: V) -> utils::IntOutputCommand
+where
+    BulkString: From<T> + From<V>,
+{
+    utils::IntOutputCommand(Request::Array(vec![
+        Request::from_static("APPEND"),
+        Request::BulkString(key.into()),
+        Request::BulkString(value.into()),
+    ]))
+}
+
+/// STRLEN redis command
+///
+/// Returns the length of the string value stored at key.
+pub fn StrLen<T>(key: T) -> utils::IntOutputCommand
+where
+    BulkString: From<T>,
+{
+    utils::IntOutputCommand(Request::Array(vec![
+        Request::from_static("STRLEN"),
+        Request::BulkString(key.into()),
+    ]))
+}
+
+/// INCR redis command
+///
+/// Increments the number stored at `key` by one.
+pub fn Incr<T>(key: T) -> utils::IntOutputCommand
+where
+    BulkString: From<T>,
+{
+    utils::IntOutputCommand(Request::Array(vec![
+        Request::from_static("INCR"),
+        Request::BulkString(key.into()),
+    ]))
+}
+
 /// INCRBY redis command
 ///
 /// Increments the number stored at `key` by `increment`.
@@ -140,3 +611,199 @@ where
         Request::BulkString(i64::from(increment).to_string().into()),
     ]))
 }
+
+/// DECR redis command
+///
+/// Decrements the number stored at `key` by one.
+pub fn Decr<T>(key: T) -> utils::IntOutputCommand
+where
+    BulkString: From<T>,
+{
+    utils::IntOutputCommand(Request::Array(vec![
+        Request::from_static("DECR"),
+        Request::BulkString(key.into()),
+    ]))
+}
+
+/// DECRBY redis command
+///
+/// Decrements the number stored at `key` by `decrement`.
+pub fn DecrBy<T, I>(key: T, decrement: I) -> utils::IntOutputCommand
+where
+    BulkString: From<T>,
+    i64: From<I>,
+{
+    utils::IntOutputCommand(Request::Array(vec![
+        Request::from_static("DECRBY"),
+        Request::BulkString(key.into()),
+        Request::BulkInteger(i64::from(decrement)),
+    ]))
+}
+
+/// INCRBYFLOAT redis command
+///
+/// Increment the string representing a floating point number stored at
+/// `key` by the specified `increment`.
+///
+/// ```rust,no_run
+/// use mco_redis::cmd;
+/// use mco_redis::connector::RedisConnector;
+/// # use rand::{thread_rng, Rng, distributions::Alphanumeric};
+/// # fn gen_random_key() -> String {
+/// #    thread_rng().sample_iter(&Alphanumeric).take(12).map(char::from).collect::<String>()
+/// # }
+///
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // requires real redis server, INCRBYFLOAT is not implemented by `MockServer`
+///     let redis = RedisConnector::new("127.0.0.1:6379").connect()?;
+///     let key = gen_random_key();
+///
+///     redis.exec(cmd::Set(&key, "10.5"))?;
+///
+///     // increment float value
+///     let value = redis.exec(cmd::IncrByFloat(&key, 0.1))?;
+///
+///     assert_eq!(value, 10.6);
+///     Ok(())
+/// }
+/// ```
+pub fn IncrByFloat<T>(key: T, increment: f64) -> utils::FloatOutputCommand
+where
+    BulkString: From<T>,
+{
+    utils::FloatOutputCommand(Request::Array(vec![
+        Request::from_static("INCRBYFLOAT"),
+        Request::BulkString(key.into()),
+        Request::BulkString(increment.to_string().into()),
+    ]))
+}
+
+/// LCS redis command
+///
+/// Returns the longest common subsequence of the strings stored at `key1`
+/// and `key2`.
+///
+/// Use `LcsCommand::len()` to get the length of the match only, or
+/// `LcsCommand::idx()` to get the positions of matched ranges.
+pub fn Lcs<T, K>(key1: T, key2: K) -> LcsCommand
+where
+    BulkString: From<T> + From<K>,
+{
+    LcsCommand(vec![
+        Request::from_static("LCS"),
+        Request::BulkString(key1.into()),
+        Request::BulkString(key2.into()),
+    ])
+}
+
+pub struct LcsCommand(Vec<Request>);
+
+impl LcsCommand {
+    /// Return the length of the match.
+    pub fn len(mut self) -> utils::IntOutputCommand {
+        self.0.push(Request::from_bstatic(b"LEN"));
+        utils::IntOutputCommand(Request::Array(self.0))
+    }
+
+    /// Return the match positions.
+    pub fn idx(mut self) -> LcsIdxCommand {
+        self.0.push(Request::from_bstatic(b"IDX"));
+        LcsIdxCommand(self.0)
+    }
+}
+
+impl Command for LcsCommand {
+    type Output = Bytes;
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(Bytes::try_from(val)?)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Matched range of the `LCS` command, positions are inclusive
+pub struct LcsMatch {
+    /// Range within the first key
+    pub first: (usize, usize),
+    /// Range within the second key
+    pub second: (usize, usize),
+    /// Length of the match, available only with `WITHMATCHLEN` option
+    pub len: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Result of the `LCS` command with `IDX` option
+pub struct LcsIdxResult {
+    /// Matched ranges, from the last to the first one
+    pub matches: Vec<LcsMatch>,
+    /// Length of the longest common subsequence
+    pub len: usize,
+}
+
+pub struct LcsIdxCommand(Vec<Request>);
+
+impl LcsIdxCommand {
+    /// Only return matches of length greater or equal to `len`.
+    pub fn min_match_len(mut self, len: usize) -> Self {
+        self.0.push(Request::from_bstatic(b"MINMATCHLEN"));
+        self.0.push(Request::BulkInteger(len as i64));
+        self
+    }
+
+    /// Include length of each match into the result.
+    pub fn with_match_len(mut self) -> Self {
+        self.0.push(Request::from_bstatic(b"WITHMATCHLEN"));
+        self
+    }
+}
+
+impl Command for LcsIdxCommand {
+    type Output = LcsIdxResult;
+
+    fn to_request(self) -> Request {
+        Request::Array(self.0)
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        let items = match val {
+            Response::Array(items) if items.len() == 4 => items,
+            _ => return Err(CommandError::Output("Unexpected value", val)),
+        };
+        let mut items = items.into_iter();
+        let mut result = LcsIdxResult {
+            matches: Vec::new(),
+            len: 0,
+        };
+        while let (Some(name), Some(value)) = (items.next(), items.next()) {
+            match ByteString::try_from(name)?.as_ref() {
+                "matches" => {
+                    let items = match value {
+                        Response::Array(items) => items,
+                        _ => return Err(CommandError::Output("Unexpected value", value)),
+                    };
+                    for item in items {
+                        let mut parts = match item {
+                            Response::Array(parts) if parts.len() >= 2 => parts.into_iter(),
+                            _ => return Err(CommandError::Output("Unexpected match", item)),
+                        };
+                        let first = <(usize, usize)>::try_from(parts.next().unwrap())?;
+                        let second = <(usize, usize)>::try_from(parts.next().unwrap())?;
+                        let len = match parts.next() {
+                            Some(len) => Some(usize::try_from(len)?),
+                            None => None,
+                        };
+                        result.matches.push(LcsMatch { first, second, len });
+                    }
+                }
+                "len" => result.len = usize::try_from(value)?,
+                _ => (),
+            }
+        }
+        Ok(result)
+    }
+}
@@ -1,12 +1,22 @@
 use std::convert::TryFrom;
-use crate::bytes::Bytes;
+use std::marker::PhantomData;
+use std::time::Duration;
+use crate::bytes::{ByteString, Bytes};
 
 
 use super::{Command, CommandError};
 use crate::codec_redis::{Request, Response};
+use crate::response::FromResponse;
 
 pub struct BulkOutputCommand(pub(crate) Request);
 
+impl BulkOutputCommand {
+    /// Decode value into a different type
+    pub fn output<T: FromResponse>(self) -> OutputCommand<Option<T>> {
+        OutputCommand(self.0, PhantomData)
+    }
+}
+
 impl Command for BulkOutputCommand {
     type Output = Option<Bytes>;
 
@@ -53,3 +63,150 @@ impl Command for BoolOutputCommand {
         Ok(bool::try_from(val)?)
     }
 }
+
+pub struct OkOutputCommand(pub(crate) Request);
+
+impl Command for OkOutputCommand {
+    type Output = ();
+
+    fn to_request(self) -> Request {
+        self.0
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(<()>::try_from(val)?)
+    }
+}
+
+pub struct FloatOutputCommand(pub(crate) Request);
+
+impl Command for FloatOutputCommand {
+    type Output = f64;
+
+    fn to_request(self) -> Request {
+        self.0
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(f64::try_from(val)?)
+    }
+}
+
+pub struct MultiBulkOutputCommand(pub(crate) Request);
+
+impl MultiBulkOutputCommand {
+    /// Decode values into a different type
+    pub fn output<T: FromResponse>(self) -> OutputCommand<Vec<Option<T>>> {
+        OutputCommand(self.0, PhantomData)
+    }
+}
+
+impl Command for MultiBulkOutputCommand {
+    type Output = Vec<Option<Bytes>>;
+
+    fn to_request(self) -> Request {
+        self.0
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        Ok(Vec::try_from(val)?)
+    }
+}
+
+/// Command with output decoded by `FromResponse` implementation
+pub struct OutputCommand<T>(pub(crate) Request, pub(crate) PhantomData<T>);
+
+impl<T: FromResponse> Command for OutputCommand<T> {
+    type Output = T;
+
+    fn to_request(self) -> Request {
+        self.0
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        T::from_response(val)
+    }
+}
+
+pub struct BulkArrayOutputCommand(pub(crate) Request);
+
+impl Command for BulkArrayOutputCommand {
+    type Output = Vec<Bytes>;
+
+    fn to_request(self) -> Request {
+        self.0
+    }
+
+    fn to_output(val: Response) -> Result<Self::Output, CommandError> {
+        match val {
+            Response::Nil => Ok(Vec::new()),
+            val => Ok(Vec::try_from(val)?),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// Flush mode of the `FLUSHALL`, `FLUSHDB` and `SCRIPT FLUSH` commands
+pub enum FlushMode {
+    /// Flush synchronously
+    Sync,
+    /// Flush asynchronously
+    Async,
+}
+
+impl FlushMode {
+    pub(crate) fn to_request(self) -> Request {
+        match self {
+            FlushMode::Sync => Request::from_static("SYNC"),
+            FlushMode::Async => Request::from_static("ASYNC"),
+        }
+    }
+}
+
+/// Round timeout up to whole milliseconds
+///
+/// Redis timeouts have millisecond resolution, smaller non-zero timeout
+/// must not become zero, which blocks indefinitely.
+pub(crate) fn round_timeout(timeout: Duration) -> Duration {
+    let millis = timeout.as_nanos().div_ceil(1_000_000);
+    Duration::from_millis(u64::try_from(millis).unwrap_or(u64::MAX))
+}
+
+/// Encode timeout as number of milliseconds, rounded up
+pub(crate) fn millis_request(timeout: Duration) -> Request {
+    let millis = round_timeout(timeout).as_millis();
+    Request::BulkInteger(i64::try_from(millis).unwrap_or(i64::MAX))
+}
+
+/// Encode duration as number of whole seconds, rounded up
+pub(crate) fn secs_request(duration: Duration) -> Request {
+    let timeout = round_timeout(duration);
+    let secs = timeout.as_secs() + u64::from(timeout.subsec_millis() > 0);
+    Request::BulkInteger(i64::try_from(secs).unwrap_or(i64::MAX))
+}
+
+/// Encode blocking command timeout as number of seconds
+pub(crate) fn timeout_request(timeout: Duration) -> Request {
+    let timeout = round_timeout(timeout);
+    if timeout.subsec_millis() == 0 {
+        Request::BulkInteger(timeout.as_secs() as i64)
+    } else {
+        Request::BulkString(
+            format!("{}.{:03}", timeout.as_secs(), timeout.subsec_millis()).into(),
+        )
+    }
+}
+
+/// Split flat array of names and values to pairs
+pub(crate) fn map_entries(val: Response) -> Result<Vec<(ByteString, Response)>, CommandError> {
+    let items = match val {
+        Response::Array(items) => items,
+        _ => return Err(CommandError::Output("Cannot parse map", val)),
+    };
+    let mut result = Vec::with_capacity(items.len() / 2);
+    let mut items = items.into_iter();
+    while let (Some(name), Some(value)) = (items.next(), items.next()) {
+        result.push((ByteString::try_from(name)?, value));
+    }
+    Ok(result)
+}
@@ -1,12 +1,202 @@
 //! Redis protocol codec
-use std::{cmp, collections::HashMap, convert::TryFrom, hash::BuildHasher, hash::Hash, str};
+use std::{cell::Cell, cell::RefCell, cmp, collections::BTreeMap, collections::HashMap, convert::TryFrom, hash::BuildHasher, hash::Hash, hash::Hasher, io, mem, str};
 use crate::codec::{Decoder, Encoder};
 use crate::bytes::{BufMut, Bytes, BytesMut, ByteString};
 use super::errors::Error;
 /// Codec to read/write redis values
-pub struct Codec;
+///
+/// Decoder keeps state of partially received response, so codec
+/// must not be shared between connections.
+#[derive(Default)]
+pub struct Codec {
+    state: RefCell<DecodeState>,
+    limits: DecodeLimits,
+}
 use crate::bytes::Buf;
 
+impl Codec {
+    /// Create new codec
+    pub fn new() -> Self {
+        Codec::default()
+    }
+
+    /// Create new codec with custom decoder limits
+    pub fn with_limits(limits: DecodeLimits) -> Self {
+        Codec {
+            state: RefCell::new(DecodeState::default()),
+            limits,
+        }
+    }
+
+    /// Decoder limits
+    pub fn limits(&self) -> &DecodeLimits {
+        &self.limits
+    }
+
+    /// Discard partially decoded response
+    pub(crate) fn reset(&self) {
+        *self.state.borrow_mut() = DecodeState::default();
+    }
+
+    /// Encode request for vectored write
+    ///
+    /// Bulk strings larger than `VECTORED_WRITE_THRESHOLD` are referenced
+    /// instead of being copied to the buffer.
+    pub fn encode_chunks(&self, msg: Request, dst: &mut RequestChunks) -> Result<(), Error> {
+        match msg {
+            Request::Array(ary) => {
+                write_header(b'*', ary.len() as i64, &mut dst.buf, 0);
+                for v in ary {
+                    self.encode_chunks(v, dst)?;
+                }
+                Ok(())
+            }
+            Request::BulkString(bstr) if bstr.0.len() >= VECTORED_WRITE_THRESHOLD => {
+                write_header(b'$', bstr.0.len() as i64, &mut dst.buf, 2);
+                dst.push(bstr.0);
+                write_rn(&mut dst.buf);
+                Ok(())
+            }
+            msg => self.encode(msg, &mut dst.buf),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+/// Limits of the decoded responses
+///
+/// Decoder fails with `Error::Limit` if response exceeds any of the limits,
+/// so misbehaving server cannot exhaust client memory.
+pub struct DecodeLimits {
+    max_bulk_size: usize,
+    max_array_len: usize,
+    max_depth: usize,
+    max_line_size: usize,
+}
+
+impl Default for DecodeLimits {
+    fn default() -> Self {
+        DecodeLimits {
+            max_bulk_size: 512 * 1024 * 1024,
+            max_array_len: u32::MAX as usize,
+            max_depth: 64,
+            max_line_size: 64 * 1024,
+        }
+    }
+}
+
+impl DecodeLimits {
+    /// Max size of the bulk string
+    ///
+    /// Default is 512Mb, same as redis `proto-max-bulk-len`.
+    pub fn max_bulk_size(mut self, size: usize) -> Self {
+        self.max_bulk_size = size;
+        self
+    }
+
+    /// Max number of array items
+    ///
+    /// Default is `u32::MAX`.
+    pub fn max_array_len(mut self, len: usize) -> Self {
+        self.max_array_len = len;
+        self
+    }
+
+    /// Max nesting depth of arrays
+    ///
+    /// Default is 64.
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = depth;
+        self
+    }
+
+    /// Max size of simple string, error or length header line
+    ///
+    /// Default is 64Kb. `MONITOR` output lines contain full command
+    /// arguments, so monitor connection needs larger limit.
+    pub fn max_line_size(mut self, size: usize) -> Self {
+        self.max_line_size = size;
+        self
+    }
+}
+
+/// Max number of items or bytes allocated upfront from declared lengths
+const MAX_PREALLOC: usize = 64 * 1024;
+
+/// Bulk strings of this size and larger are written with vectored io
+pub const VECTORED_WRITE_THRESHOLD: usize = 64 * 1024;
+
+/// Max number of chunks passed to a single `write_vectored` call
+const MAX_IO_SLICES: usize = 64;
+
+#[derive(Debug, Default)]
+/// Encoded request, large bulk strings are kept as separate chunks
+pub struct RequestChunks {
+    chunks: Vec<Bytes>,
+    buf: BytesMut,
+}
+
+impl RequestChunks {
+    /// Create empty request
+    pub fn new() -> Self {
+        RequestChunks::default()
+    }
+
+    /// Size of the encoded request
+    pub fn len(&self) -> usize {
+        self.chunks.iter().map(|c| c.len()).sum::<usize>() + self.buf.len()
+    }
+
+    /// Returns true if request is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn push(&mut self, chunk: Bytes) {
+        if !self.buf.is_empty() {
+            self.chunks.push(self.buf.split().freeze());
+        }
+        self.chunks.push(chunk);
+    }
+
+    /// Write all chunks with vectored writes
+    pub fn write_to<W: io::Write>(mut self, io: &mut W) -> io::Result<()> {
+        if !self.buf.is_empty() {
+            self.chunks.push(self.buf.split().freeze());
+        }
+
+        // first chunk that is not written completely, and written part of it
+        let (mut idx, mut offset) = (0, 0);
+        while idx < self.chunks.len() {
+            let result = {
+                let end = cmp::min(self.chunks.len(), idx + MAX_IO_SLICES);
+                let mut slices = Vec::with_capacity(end - idx);
+                slices.push(io::IoSlice::new(&self.chunks[idx][offset..]));
+                slices.extend(self.chunks[idx + 1..end].iter().map(|c| io::IoSlice::new(c)));
+                io.write_vectored(&slices)
+            };
+            let mut written = match result {
+                Ok(0) => return Err(io::ErrorKind::WriteZero.into()),
+                Ok(n) => n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            while written > 0 {
+                let remaining = self.chunks[idx].len() - offset;
+                if written >= remaining {
+                    written -= remaining;
+                    idx += 1;
+                    offset = 0;
+                } else {
+                    offset += written;
+                    written = 0;
+                }
+            }
+        }
+        io.flush()
+    }
+}
+
 impl Encoder for Codec {
     type EncodeItem = Request;
     type EncodeError = Error;
@@ -55,13 +245,144 @@ impl Decoder for Codec {
     type DecodeError = Error;
 
     fn decode(&self, buf: &mut BytesMut) -> Result<Option<Self::DecodeItem>, Self::DecodeError> {
-        match decode(buf, 0)? {
-            Some((pos, item)) => {
-                buf.advance(pos);
-                Ok(Some(item))
+        let mut state = self.state.borrow_mut();
+        let result = state.decode(buf, &self.limits, false);
+        if result.is_err() {
+            *state = DecodeState::default();
+        }
+        result
+    }
+}
+
+#[derive(Default)]
+/// Server side codec, reads client requests and writes responses
+///
+/// Both multibulk and inline requests are decoded, inline request
+/// arguments are split and unquoted the same way as redis server does.
+/// Responses are written in RESP2 format, RESP3 only values are written
+/// as arrays, bulk strings and integers. Use `set_resp3()` after client
+/// switched protocol with `HELLO 3`.
+pub struct ServerCodec {
+    state: RefCell<DecodeState>,
+    limits: DecodeLimits,
+    resp3: Cell<bool>,
+}
+
+impl ServerCodec {
+    /// Create new server codec
+    pub fn new() -> Self {
+        ServerCodec::default()
+    }
+
+    /// Create new server codec with custom request limits
+    pub fn with_limits(limits: DecodeLimits) -> Self {
+        ServerCodec {
+            state: RefCell::new(DecodeState::default()),
+            limits,
+            resp3: Cell::new(false),
+        }
+    }
+
+    /// Write responses in RESP3 format
+    pub fn set_resp3(&self, resp3: bool) {
+        self.resp3.set(resp3);
+    }
+
+    /// Returns true if responses are written in RESP3 format
+    pub fn is_resp3(&self) -> bool {
+        self.resp3.get()
+    }
+}
+
+impl Encoder for ServerCodec {
+    type EncodeItem = Response;
+    type EncodeError = Error;
+
+    fn encode(&self, msg: Response, buf: &mut BytesMut) -> Result<(), Self::EncodeError> {
+        match msg {
+            Response::Nil => {
+                if self.resp3.get() {
+                    buf.extend_from_slice(b"_\r\n");
+                } else {
+                    buf.extend_from_slice(b"$-1\r\n");
+                }
+            }
+            Response::Array(ary) => {
+                write_header(b'*', ary.len() as i64, buf, 0);
+                for v in ary {
+                    self.encode(v, buf)?;
+                }
+            }
+            Response::Bytes(bytes) => {
+                let len = bytes.len();
+                write_header(b'$', len as i64, buf, len + 2);
+                buf.extend_from_slice(&bytes);
+                write_rn(buf);
+            }
+            Response::String(ref string) => write_string(b'+', string, buf),
+            Response::Error(ref string) => write_string(b'-', string, buf),
+            Response::Integer(val) => write_header(b':', val, buf, 0),
+            // RESP2 clients receive flat array of keys and values
+            Response::Map(pairs) => {
+                if self.resp3.get() {
+                    write_header(b'%', pairs.len() as i64, buf, 0);
+                } else {
+                    write_header(b'*', pairs.len() as i64 * 2, buf, 0);
+                }
+                for (k, v) in pairs {
+                    self.encode(k, buf)?;
+                    self.encode(v, buf)?;
+                }
+            }
+            Response::Set(items) => {
+                let marker = if self.resp3.get() { b'~' } else { b'*' };
+                write_header(marker, items.len() as i64, buf, 0);
+                for v in items {
+                    self.encode(v, buf)?;
+                }
+            }
+            Response::Push(items) => {
+                let marker = if self.resp3.get() { b'>' } else { b'*' };
+                write_header(marker, items.len() as i64, buf, 0);
+                for v in items {
+                    self.encode(v, buf)?;
+                }
+            }
+            Response::Double(val) => {
+                let val = if val.is_nan() {
+                    "nan".to_string()
+                } else {
+                    val.to_string()
+                };
+                if self.resp3.get() {
+                    write_string(b',', &val, buf);
+                } else {
+                    self.encode(Response::Bytes(Bytes::from(val)), buf)?;
+                }
+            }
+            Response::Boolean(val) => {
+                if self.resp3.get() {
+                    buf.extend_from_slice(if val { b"#t\r\n" } else { b"#f\r\n" });
+                } else {
+                    write_header(b':', val as i64, buf, 0);
+                }
             }
-            None => Ok(None),
         }
+        Ok(())
+    }
+}
+
+impl Decoder for ServerCodec {
+    type DecodeItem = Request;
+    type DecodeError = Error;
+
+    fn decode(&self, buf: &mut BytesMut) -> Result<Option<Self::DecodeItem>, Self::DecodeError> {
+        let mut state = self.state.borrow_mut();
+        let result = state.decode_request(buf, &self.limits);
+        if result.is_err() {
+            *state = DecodeState::default();
+        }
+        result
     }
 }
 
@@ -82,6 +403,16 @@ impl BulkString {
     pub fn from_bstatic(data: &'static [u8]) -> Self {
         BulkString(Bytes::from_static(data))
     }
+
+    /// Get reference to the string bytes
+    pub fn as_bytes(&self) -> &Bytes {
+        &self.0
+    }
+
+    /// Unwrap string bytes
+    pub fn into_bytes(self) -> Bytes {
+        self.0
+    }
 }
 
 impl From<ByteString> for BulkString {
@@ -178,6 +509,22 @@ pub enum Request {
     Integer(i64),
 }
 
+impl TryFrom<Response> for Request {
+    type Error = (&'static str, Response);
+
+    fn try_from(val: Response) -> Result<Self, Self::Error> {
+        match val {
+            Response::Array(ary) => Ok(Request::Array(
+                ary.into_iter().map(Request::try_from).collect::<Result<_, _>>()?,
+            )),
+            Response::Bytes(bytes) => Ok(Request::BulkString(bytes.into())),
+            Response::String(string) => Ok(Request::String(string)),
+            Response::Integer(val) => Ok(Request::Integer(val)),
+            val => Err(("Not a request object", val)),
+        }
+    }
+}
+
 impl Request {
     /// Create request from static str
     pub fn from_static(data: &'static str) -> Self {
@@ -287,7 +634,10 @@ impl From<usize> for Request {
 }
 
 /// A single RESP value, this owns the data that is read from Redis.
-#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+///
+/// `Map`, `Set`, `Double`, `Boolean` and `Push` values are sent by servers
+/// in RESP3 mode only. `Double` values are compared by their bit pattern.
+#[derive(Debug, Clone)]
 pub enum Response {
     Nil,
 
@@ -307,6 +657,60 @@ pub enum Response {
     /// Redis documentation defines an integer as being a signed 64-bit integer:
     /// https://redis.io/topics/protocol#resp-integers
     Integer(i64),
+
+    /// Key/value pairs in the order sent by server
+    Map(Vec<(Response, Response)>),
+
+    /// Unordered collection of unique values
+    Set(Vec<Response>),
+
+    /// A floating point number, `inf`, `-inf` and `nan` included
+    Double(f64),
+
+    /// A boolean value
+    Boolean(bool),
+
+    /// Out of band data, i.e. pub/sub message or invalidation message
+    Push(Vec<Response>),
+}
+
+impl PartialEq for Response {
+    fn eq(&self, other: &Response) -> bool {
+        match (self, other) {
+            (Response::Nil, Response::Nil) => true,
+            (Response::Array(a), Response::Array(b))
+            | (Response::Set(a), Response::Set(b))
+            | (Response::Push(a), Response::Push(b)) => a == b,
+            (Response::Bytes(a), Response::Bytes(b)) => a == b,
+            (Response::String(a), Response::String(b))
+            | (Response::Error(a), Response::Error(b)) => a == b,
+            (Response::Integer(a), Response::Integer(b)) => a == b,
+            (Response::Map(a), Response::Map(b)) => a == b,
+            (Response::Double(a), Response::Double(b)) => a.to_bits() == b.to_bits(),
+            (Response::Boolean(a), Response::Boolean(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Response {}
+
+impl Hash for Response {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        mem::discriminant(self).hash(state);
+        match self {
+            Response::Nil => {}
+            Response::Array(items) | Response::Set(items) | Response::Push(items) => {
+                items.hash(state)
+            }
+            Response::Bytes(val) => val.hash(state),
+            Response::String(val) | Response::Error(val) => val.hash(state),
+            Response::Integer(val) => val.hash(state),
+            Response::Map(items) => items.hash(state),
+            Response::Double(val) => val.to_bits().hash(state),
+            Response::Boolean(val) => val.hash(state),
+        }
+    }
 }
 
 impl Response {
@@ -352,6 +756,14 @@ impl TryFrom<Response> for ByteString {
     }
 }
 
+impl TryFrom<Response> for String {
+    type Error = (&'static str, Response);
+
+    fn try_from(val: Response) -> Result<Self, Self::Error> {
+        ByteString::try_from(val).map(|s| s.to_string())
+    }
+}
+
 impl TryFrom<Response> for i64 {
     type Error = (&'static str, Response);
 
@@ -368,6 +780,9 @@ impl TryFrom<Response> for bool {
     type Error = (&'static str, Response);
 
     fn try_from(val: Response) -> Result<bool, Self::Error> {
+        if let Response::Boolean(val) = val {
+            return Ok(val);
+        }
         i64::try_from(val).and_then(|x| match x {
             0 => Ok(false),
             1 => Ok(true),
@@ -379,6 +794,35 @@ impl TryFrom<Response> for bool {
     }
 }
 
+impl TryFrom<Response> for f64 {
+    type Error = (&'static str, Response);
+
+    fn try_from(val: Response) -> Result<f64, Self::Error> {
+        let parsed = match val {
+            Response::Bytes(ref bytes) => str::from_utf8(bytes).ok().and_then(|s| s.parse().ok()),
+            Response::String(ref s) => s.parse().ok(),
+            Response::Integer(i) => Some(i as f64),
+            Response::Double(val) => Some(val),
+            _ => None,
+        };
+        parsed.ok_or(("Cannot be converted into an f64", val))
+    }
+}
+
+impl<T> TryFrom<Response> for Option<T>
+    where
+        T: TryFrom<Response, Error = (&'static str, Response)>,
+{
+    type Error = (&'static str, Response);
+
+    fn try_from(val: Response) -> Result<Option<T>, Self::Error> {
+        match val {
+            Response::Nil => Ok(None),
+            val => T::try_from(val).map(Some),
+        }
+    }
+}
+
 impl<T> TryFrom<Response> for Vec<T>
     where
         T: TryFrom<Response, Error = (&'static str, Response)>,
@@ -386,7 +830,7 @@ impl<T> TryFrom<Response> for Vec<T>
     type Error = (&'static str, Response);
 
     fn try_from(val: Response) -> Result<Vec<T>, Self::Error> {
-        if let Response::Array(ary) = val {
+        if let Response::Array(ary) | Response::Set(ary) = val {
             let mut ar = Vec::with_capacity(ary.len());
             for value in ary {
                 ar.push(T::try_from(value)?);
@@ -490,11 +934,54 @@ impl<K, T, S> TryFrom<Response> for HashMap<K, T, S>
 
                 Ok(map)
             }
+            Response::Map(pairs) => {
+                let mut map = HashMap::with_capacity_and_hasher(pairs.len(), S::default());
+                for (k, v) in pairs {
+                    map.insert(K::try_from(k)?, T::try_from(v)?);
+                }
+                Ok(map)
+            }
             _ => Err(("Cannot be converted into a hashmap", val)),
         }
     }
 }
 
+impl<K, T> TryFrom<Response> for BTreeMap<K, T>
+    where
+        K: TryFrom<Response, Error = (&'static str, Response)> + Ord,
+        T: TryFrom<Response, Error = (&'static str, Response)>,
+{
+    type Error = (&'static str, Response);
+
+    fn try_from(val: Response) -> Result<BTreeMap<K, T>, Self::Error> {
+        match val {
+            Response::Array(ary) => {
+                let mut map = BTreeMap::new();
+                let mut items = ary.into_iter();
+
+                while let Some(k) = items.next() {
+                    let key = K::try_from(k)?;
+                    let value = T::try_from(items.next().ok_or((
+                        "Cannot convert an odd number of elements into a map",
+                        Response::Nil,
+                    ))?)?;
+                    map.insert(key, value);
+                }
+
+                Ok(map)
+            }
+            Response::Map(pairs) => {
+                let mut map = BTreeMap::new();
+                for (k, v) in pairs {
+                    map.insert(K::try_from(k)?, T::try_from(v)?);
+                }
+                Ok(map)
+            }
+            _ => Err(("Cannot be converted into a map", val)),
+        }
+    }
+}
+
 macro_rules! impl_tryfrom_integers {
     ($($int_ty:ident),* $(,)*) => {
         $(
@@ -550,122 +1037,309 @@ fn write_string(symb: u8, string: &str, buf: &mut BytesMut) {
     write_rn(buf);
 }
 
-type DecodeResult = Result<Option<(usize, Response)>, Error>;
+/// Partially decoded array
+struct PartialArray {
+    kind: ArrayKind,
+    len: usize,
+    items: Vec<Response>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// Aggregate type of the partially decoded array
+enum ArrayKind {
+    Array,
+    Map,
+    Set,
+    Push,
+}
 
-fn decode(buf: &mut BytesMut, idx: usize) -> DecodeResult {
-    if buf.len() > idx {
-        match buf[idx] {
-            b'$' => decode_bytes(buf, idx + 1),
-            b'*' => decode_array(buf, idx + 1),
-            b':' => decode_integer(buf, idx + 1),
-            b'+' => decode_string(buf, idx + 1),
-            b'-' => decode_error(buf, idx + 1),
-            _ => Err(Error::Parse(format!("Unexpected byte: {}", buf[idx]))),
+impl ArrayKind {
+    fn build(self, items: Vec<Response>) -> Response {
+        match self {
+            ArrayKind::Array => Response::Array(items),
+            ArrayKind::Set => Response::Set(items),
+            ArrayKind::Push => Response::Push(items),
+            ArrayKind::Map => {
+                let mut pairs = Vec::with_capacity(items.len() / 2);
+                let mut items = items.into_iter();
+                while let (Some(key), Some(value)) = (items.next(), items.next()) {
+                    pairs.push((key, value));
+                }
+                Response::Map(pairs)
+            }
         }
-    } else {
-        Ok(None)
     }
 }
 
-fn decode_length(buf: &mut BytesMut, idx: usize) -> Result<Option<(usize, i64)>, Error> {
-    // length is encoded as a string, terminated by "\r\n"
-    let (pos, int_str) = if let Some(pos) = buf[idx..].windows(2).position(|w| w == b"\r\n") {
-        (idx + pos + 2, &buf[idx..idx + pos])
-    } else {
-        return Ok(None);
-    };
+#[derive(Default)]
+/// Decoder state, preserved between `decode()` calls
+///
+/// Completed items are removed from the buffer, so every byte
+/// is examined only once.
+struct DecodeState {
+    /// Partially decoded arrays, innermost array is the last one
+    arrays: Vec<PartialArray>,
+    /// Length of the bulk string, header of which is already consumed
+    bulk: Option<usize>,
+    /// Buffer position to resume `\r\n` search from
+    scanned: usize,
+}
 
-    // int encoded as string
-    match btoi::btoi(int_str) {
-        Ok(int) => Ok(Some((pos, int))),
-        Err(_) => Err(Error::Parse(format!(
-            "Not an integer: {:?}",
-            &int_str[..cmp::min(int_str.len(), 10)]
-        ))),
+impl DecodeState {
+    /// Decode response, request items must be bulk strings
+    fn decode(&mut self, buf: &mut BytesMut, limits: &DecodeLimits, request: bool) -> Result<Option<Response>, Error> {
+        loop {
+            let value = if let Some(size) = self.bulk {
+                if buf.len() < size + 2 {
+                    // make room for the rest of bulk string, declared size is not trusted
+                    buf.reserve(cmp::min(size + 2 - buf.len(), MAX_PREALLOC));
+                    return Ok(None);
+                }
+                if &buf[size..size + 2] != b"\r\n" {
+                    return Err(Error::Parse("Bulk string is not terminated".to_string()));
+                }
+                self.bulk = None;
+                let value = buf.split_to(size).freeze();
+                buf.advance(2);
+                Response::Bytes(value)
+            } else {
+                let line = match self.next_line(buf, limits.max_line_size)? {
+                    Some(line) => line,
+                    None => return Ok(None),
+                };
+                let first = line.first().copied().unwrap_or(b'\r');
+                if request && !self.arrays.is_empty() && first != b'$' {
+                    return Err(Error::Parse(format!("Expected '$', got '{}'", first as char)));
+                }
+                match first {
+                    b'+' => Response::String(decode_string(line)?),
+                    b'-' => Response::Error(decode_string(line)?),
+                    b':' => Response::Integer(decode_length(&line[1..])?),
+                    // RESP3 types
+                    b'_' if line.len() == 1 => Response::Nil,
+                    b'#' if &line[..] == b"#t" => Response::Boolean(true),
+                    b'#' if &line[..] == b"#f" => Response::Boolean(false),
+                    b',' => match str::from_utf8(&line[1..]).ok().and_then(|s| s.parse().ok()) {
+                        Some(val) => Response::Double(val),
+                        None => return Err(Error::Parse("Not a double".to_string())),
+                    },
+                    b'$' => match decode_length(&line[1..])? {
+                        -1 if !request => Response::Nil,
+                        size if size > 0 && size as u64 > limits.max_bulk_size as u64 => {
+                            return Err(Error::Limit(format!("Bulk string size {} exceeds {}", size, limits.max_bulk_size)))
+                        }
+                        size if size >= 0 => {
+                            self.bulk = Some(size as usize);
+                            continue;
+                        }
+                        size => return Err(Error::Parse(format!("Invalid string size: {}", size))),
+                    },
+                    b'*' | b'%' | b'~' | b'>' => {
+                        let kind = match first {
+                            b'*' => ArrayKind::Array,
+                            b'%' => ArrayKind::Map,
+                            b'~' => ArrayKind::Set,
+                            _ => ArrayKind::Push,
+                        };
+                        match decode_length(&line[1..])? {
+                            -1 if kind == ArrayKind::Array => Response::Nil,
+                            0 => kind.build(Vec::new()),
+                            size if size > 0 && size as u64 > limits.max_array_len as u64 => {
+                                return Err(Error::Limit(format!("Array size {} exceeds {}", size, limits.max_array_len)))
+                            }
+                            size if size > 0 && self.arrays.len() >= limits.max_depth => {
+                                return Err(Error::Limit(format!("Array nesting depth exceeds {}", limits.max_depth)))
+                            }
+                            size if size > 0 => {
+                                // map header declares number of key/value pairs
+                                let len = if kind == ArrayKind::Map {
+                                    (size as usize).saturating_mul(2)
+                                } else {
+                                    size as usize
+                                };
+                                let items = Vec::with_capacity(cmp::min(len, MAX_PREALLOC));
+                                self.arrays.push(PartialArray { kind, len, items });
+                                continue;
+                            }
+                            size => return Err(Error::Parse(format!("Invalid array size: {}", size))),
+                        }
+                    }
+                    b => return Err(Error::Parse(format!("Unexpected byte: {}", b))),
+                }
+            };
+
+            if let Some(value) = self.complete(value) {
+                return Ok(Some(value));
+            }
+        }
     }
-}
 
-fn decode_bytes(buf: &mut BytesMut, idx: usize) -> DecodeResult {
-    match decode_length(buf, idx)? {
-        Some((pos, -1)) => Ok(Some((pos, Response::Nil))),
-        Some((pos, size)) if size >= 0 => {
-            let size = size as usize;
-            let remaining = buf.len() - pos;
-            let required_bytes = size + 2;
+    /// Decode client request, multibulk or inline
+    fn decode_request(&mut self, buf: &mut BytesMut, limits: &DecodeLimits) -> Result<Option<Request>, Error> {
+        loop {
+            let idle = self.arrays.is_empty() && self.bulk.is_none();
+            if !idle || buf.first().map(|b| *b == b'*').unwrap_or(true) {
+                let value = match self.decode(buf, limits, true)? {
+                    Some(value) => value,
+                    None => return Ok(None),
+                };
+                return match value {
+                    // empty multibulk requests are ignored
+                    Response::Nil => continue,
+                    Response::Array(ref items) if items.is_empty() => continue,
+                    value => Request::try_from(value)
+                        .map(Some)
+                        .map_err(|(msg, val)| Error::Parse(format!("{}: {:?}", msg, val))),
+                };
+            }
 
-            if remaining < required_bytes {
-                return Ok(None);
+            let line = match self.next_line(buf, limits.max_line_size)? {
+                Some(line) => line.freeze(),
+                None => return Ok(None),
+            };
+            let args = split_args(&line)?;
+            if args.len() > limits.max_array_len {
+                return Err(Error::Limit(format!("Array size {} exceeds {}", args.len(), limits.max_array_len)));
+            }
+            // empty lines are ignored
+            if !args.is_empty() {
+                return Ok(Some(Request::Array(args)));
             }
-            buf.advance(pos);
-            Ok(Some((2, Response::Bytes(buf.split_to(size).freeze()))))
         }
-        Some((_, size)) => Err(Error::Parse(format!("Invalid string size: {}", size))),
-        None => Ok(None),
     }
-}
 
-fn decode_array(buf: &mut BytesMut, idx: usize) -> DecodeResult {
-    match decode_length(buf, idx)? {
-        Some((pos, -1)) => Ok(Some((pos, Response::Nil))),
-        Some((pos, size)) if size >= 0 => {
-            let size = size as usize;
-            let mut pos = pos;
-            let mut values = Vec::with_capacity(size);
-            for _ in 0..size {
-                match decode(buf, pos) {
-                    Ok(None) => return Ok(None),
-                    Ok(Some((new_pos, value))) => {
-                        values.push(value);
-                        pos = new_pos;
-                    }
-                    Err(e) => return Err(e),
+    /// Split next `\r\n` terminated line from the buffer
+    fn next_line(&mut self, buf: &mut BytesMut, max_size: usize) -> Result<Option<BytesMut>, Error> {
+        self.scanned = cmp::min(self.scanned, buf.len());
+        let limit = || Error::Limit(format!("Line is longer than {} bytes", max_size));
+        match buf[self.scanned..].windows(2).position(|w| w == b"\r\n") {
+            Some(pos) if self.scanned + pos > max_size => Err(limit()),
+            Some(pos) => {
+                let line = buf.split_to(self.scanned + pos);
+                buf.advance(2);
+                self.scanned = 0;
+                Ok(Some(line))
+            }
+            None => {
+                // `\r` could be the last byte
+                self.scanned = buf.len().saturating_sub(1);
+                if self.scanned > max_size {
+                    Err(limit())
+                } else {
+                    Ok(None)
                 }
             }
-            Ok(Some((pos, Response::Array(values))))
         }
-        Some((_, size)) => Err(Error::Parse(format!("Invalid array size: {}", size))),
-        None => Ok(None),
     }
-}
 
-fn decode_integer(buf: &mut BytesMut, idx: usize) -> DecodeResult {
-    if let Some((pos, int)) = decode_length(buf, idx)? {
-        Ok(Some((pos, Response::Integer(int))))
-    } else {
-        Ok(None)
+    /// Add value to the innermost array, returns top level value if it is complete
+    fn complete(&mut self, mut value: Response) -> Option<Response> {
+        loop {
+            match self.arrays.last_mut() {
+                None => return Some(value),
+                Some(array) => {
+                    array.items.push(value);
+                    if array.items.len() < array.len {
+                        return None;
+                    }
+                }
+            }
+            let array = self.arrays.pop().unwrap();
+            value = array.kind.build(array.items);
+        }
     }
 }
 
-/// A simple string is any series of bytes that ends with `\r\n`
-fn decode_string(buf: &mut BytesMut, idx: usize) -> DecodeResult {
-    if let Some((pos, string)) = scan_string(buf, idx)? {
-        Ok(Some((pos, Response::String(string))))
-    } else {
-        Ok(None)
+/// Split inline request to arguments
+///
+/// Arguments are separated with whitespace, double quoted arguments
+/// support `\n`, `\r`, `\t`, `\b`, `\a` and `\xHH` escapes, single
+/// quoted arguments support `\'` escape only, same as `sdssplitargs()`.
+fn split_args(line: &[u8]) -> Result<Vec<Request>, Error> {
+    let unbalanced = || Error::Parse("Unbalanced quotes in request".to_string());
+    let mut args = Vec::new();
+    let mut pos = 0;
+    loop {
+        while pos < line.len() && line[pos].is_ascii_whitespace() {
+            pos += 1;
+        }
+        if pos == line.len() {
+            return Ok(args);
+        }
+
+        let mut arg = Vec::new();
+        let mut quote = None;
+        loop {
+            let ch = match line.get(pos) {
+                Some(ch) => *ch,
+                None if quote.is_some() => return Err(unbalanced()),
+                None => break,
+            };
+            match quote {
+                Some(q) if ch == q => {
+                    // closing quote must be followed by whitespace
+                    if line.get(pos + 1).map(|c| !c.is_ascii_whitespace()).unwrap_or(false) {
+                        return Err(unbalanced());
+                    }
+                    pos += 1;
+                    break;
+                }
+                Some(b'"') if ch == b'\\' && pos + 1 < line.len() => {
+                    let hex = line
+                        .get(pos + 2..pos + 4)
+                        .filter(|_| line[pos + 1] == b'x')
+                        .and_then(|hex| str::from_utf8(hex).ok())
+                        .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+                    if let Some(val) = hex {
+                        arg.push(val);
+                        pos += 4;
+                        continue;
+                    }
+                    arg.push(match line[pos + 1] {
+                        b'n' => b'\n',
+                        b'r' => b'\r',
+                        b't' => b'\t',
+                        b'b' => 0x08,
+                        b'a' => 0x07,
+                        ch => ch,
+                    });
+                    pos += 1;
+                }
+                Some(b'\'') if ch == b'\\' && line.get(pos + 1) == Some(&b'\'') => {
+                    arg.push(b'\'');
+                    pos += 1;
+                }
+                Some(_) => arg.push(ch),
+                None if ch.is_ascii_whitespace() => break,
+                None if ch == b'"' || ch == b'\'' => quote = Some(ch),
+                None => arg.push(ch),
+            }
+            pos += 1;
+        }
+        args.push(Request::BulkString(Bytes::from(arg).into()));
     }
 }
 
-fn decode_error(buf: &mut BytesMut, idx: usize) -> DecodeResult {
-    if let Some((pos, string)) = scan_string(buf, idx)? {
-        Ok(Some((pos, Response::Error(string))))
-    } else {
-        Ok(None)
-    }
+fn decode_length(int_str: &[u8]) -> Result<i64, Error> {
+    // int encoded as string
+    btoi::btoi(int_str).map_err(|_| {
+        Error::Parse(format!(
+            "Not an integer: {:?}",
+            &int_str[..cmp::min(int_str.len(), 10)]
+        ))
+    })
 }
 
-fn scan_string(buf: &mut BytesMut, idx: usize) -> Result<Option<(usize, ByteString)>, Error> {
-    if let Some(pos) = buf[idx..].windows(2).position(|w| w == b"\r\n") {
-        buf.advance(idx);
-        match ByteString::try_from(buf.split_to(pos)) {
-            Ok(s) => Ok(Some((2, s))),
-            Err(_) => Err(Error::Parse(format!(
-                "Not a valid string: {:?}",
-                &buf[idx..idx + cmp::min(pos, 10)]
-            ))),
-        }
-    } else {
-        Ok(None)
-    }
+/// A simple string is any series of bytes that ends with `\r\n`
+fn decode_string(mut line: BytesMut) -> Result<ByteString, Error> {
+    line.advance(1);
+    let line = line.freeze();
+    ByteString::try_from(line.clone()).map_err(|_| {
+        Error::Parse(format!(
+            "Not a valid string: {:?}",
+            &line[..cmp::min(line.len(), 10)]
+        ))
+    })
 }
 
 #[cfg(test)]
@@ -5,6 +5,7 @@ use mco::net::TcpStream;
 use crate::bytes::{ByteString};
 use crate::client::Client;
 use crate::cmd;
+use crate::codec_redis::DecodeLimits;
 use crate::simple::SimpleClient;
 use super::errors::ConnectError;
 
@@ -12,6 +13,8 @@ use super::errors::ConnectError;
 pub struct RedisConnector<A> {
     address: A,
     passwords: Vec<ByteString>,
+    name: Option<ByteString>,
+    limits: DecodeLimits,
 }
 
 impl<A> RedisConnector<A>
@@ -23,6 +26,8 @@ impl<A> RedisConnector<A>
         RedisConnector {
             address: address.clone(),
             passwords: Vec::new(),
+            name: None,
+            limits: DecodeLimits::default(),
         }
     }
 }
@@ -40,11 +45,30 @@ impl<A> RedisConnector<A>
         self
     }
 
+    /// Set connection name, it is displayed in the output of `CLIENT LIST`
+    pub fn name<U>(mut self, name: U) -> Self
+        where
+            U: AsRef<str>,
+    {
+        self.name = Some(ByteString::from(name.as_ref().to_string()));
+        self
+    }
+
+    /// Set limits of server responses
+    ///
+    /// Connection fails with `Error::Limit` if server sends response exceeding limits.
+    pub fn limits(mut self, limits: DecodeLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
     /// Use custom connector
     pub fn connector(self) -> RedisConnector<A> {
         RedisConnector {
             address: self.address,
             passwords: self.passwords,
+            name: self.name,
+            limits: self.limits,
         }
     }
 }
@@ -53,8 +77,26 @@ impl<A> RedisConnector<A>
     where
         A: ToSocketAddrs + Clone,
 {
+    fn setup(&self, client: SimpleClient) -> Result<SimpleClient, ConnectError> {
+        if !self.passwords.is_empty() {
+            let mut authorized = false;
+            for password in self.passwords.iter() {
+                if client.exec(cmd::Auth(password.clone()))? {
+                    authorized = true;
+                    break;
+                }
+            }
+            if !authorized {
+                return Err(ConnectError::Unauthorized);
+            }
+        }
+        if let Some(ref name) = self.name {
+            client.exec(cmd::ClientSetName(name.clone()))?;
+        }
+        Ok(client)
+    }
+
     fn _connect_timeout(&mut self, timeout: Duration) -> Result<SimpleClient, ConnectError> {
-        let passwords = self.passwords.clone();
         let mut addrs = self.address.to_socket_addrs()?;
         let mut addr = None;
         loop {
@@ -72,43 +114,22 @@ impl<A> RedisConnector<A>
         let conn = TcpStream::connect_timeout(&addr, timeout.clone())?;
         conn.set_read_timeout(Some(timeout.clone()));
         conn.set_write_timeout(Some(timeout));
-        if passwords.is_empty() {
-            Ok(SimpleClient::new(conn))
-        } else {
-            let client = SimpleClient::new(conn);
-            for password in passwords {
-                if client.exec(cmd::Auth(password))? {
-                    return Ok(client);
-                }
-            }
-            Err(ConnectError::Unauthorized)
-        }
+        self.setup(SimpleClient::with_limits(conn, self.limits))
     }
 
     fn _connect(&mut self) -> Result<SimpleClient, ConnectError> {
-        let passwords = self.passwords.clone();
         let conn = TcpStream::connect(self.address.clone())?;
-        if passwords.is_empty() {
-            Ok(SimpleClient::new(conn))
-        } else {
-            let client = SimpleClient::new(conn);
-            for password in passwords {
-                if client.exec(cmd::Auth(password))? {
-                    return Ok(client);
-                }
-            }
-            Err(ConnectError::Unauthorized)
-        }
+        self.setup(SimpleClient::with_limits(conn, self.limits))
     }
 
     /// Connect to redis server and create shared client
     pub fn connect(&mut self) -> Result<Client, ConnectError> {
-        Ok(Client::new(self._connect()?))
+        Ok(Client::new(self._connect()?, self.name.clone()))
     }
 
     /// Connect to redis server and create shared client with timeout
     pub fn connect_timeout(&mut self, timeout: Duration) -> Result<Client, ConnectError> {
-        Ok(Client::new(self._connect_timeout(timeout)?))
+        Ok(Client::new(self._connect_timeout(timeout)?, self.name.clone()))
     }
 
     /// Connect to redis server and create simple client
@@ -1,5 +1,5 @@
 //! Redis protocol related errors
-use std::io;
+use std::{fmt, io};
 
 use derive_more::{Display, From};
 use crate::bytes::ByteString;
@@ -12,6 +12,10 @@ pub enum Error {
     #[display(fmt = "Redis server response error: {}", _0)]
     Parse(String),
 
+    /// Response exceeds decoder limits
+    #[display(fmt = "Protocol limit exceeded: {}", _0)]
+    Limit(String),
+
     /// An IO error occurred
     #[display(fmt = "Io error: {:?}", _0)]
     PeerGone(Option<io::Error>),
@@ -29,6 +33,7 @@ impl Clone for Error {
     fn clone(&self) -> Self {
         match self {
             Error::Parse(s) => Error::Parse(s.clone()),
+            Error::Limit(s) => Error::Limit(s.clone()),
             Error::PeerGone(_) => Error::PeerGone(None),
             Error::Command(v)=> Error::Command(v.clone()),
             Error::Recv(v)=> Error::Recv(v.clone()),
@@ -67,16 +72,182 @@ impl From<std::io::Error> for ConnectError {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Kind of the redis server error, parsed from the error prefix
+pub enum ServerErrorKind {
+    /// Generic error, `ERR` prefix
+    Err,
+    /// Operation against a key holding the wrong kind of value
+    WrongType,
+    /// Key slot is served by another cluster node
+    Moved { slot: u16, addr: ByteString },
+    /// Key slot is being migrated, ask another cluster node
+    Ask { slot: u16, addr: ByteString },
+    /// Multi-key command during slot migration
+    TryAgain,
+    /// Server is loading the dataset in memory
+    Loading,
+    /// Server is busy running a script or a function
+    Busy,
+    /// Script is not loaded
+    NoScript,
+    /// Write command against a read only replica
+    ReadOnly,
+    /// Authentication is required
+    NoAuth,
+    /// Invalid username or password
+    WrongPass,
+    /// User has no permissions to run the command
+    NoPerm,
+    /// Command is not allowed when used memory exceeds `maxmemory`
+    Oom,
+    /// Transaction is discarded because of previous errors
+    ExecAbort,
+    /// Keys of the command do not hash to the same slot
+    CrossSlot,
+    /// Cluster is down
+    ClusterDown,
+    /// Other error prefix
+    Other(ByteString),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Redis server error response
+pub struct ServerError {
+    pub kind: ServerErrorKind,
+    /// Error message without the prefix
+    pub message: ByteString,
+}
+
+impl ServerError {
+    /// Parse redis error response
+    ///
+    /// Error response starts with an upper case prefix, i.e.
+    /// `WRONGTYPE Operation against a key holding the wrong kind of value`
+    /// or `MOVED 3999 127.0.0.1:6381`.
+    pub fn parse(err: ByteString) -> ServerError {
+        let (code, message) = match err.find(' ') {
+            Some(idx) => (err.slice(..idx), err.slice(idx + 1..)),
+            None => (err, ByteString::new()),
+        };
+
+        let kind = match &*code {
+            "ERR" => ServerErrorKind::Err,
+            "WRONGTYPE" => ServerErrorKind::WrongType,
+            "MOVED" | "ASK" => match parse_redirect(&message) {
+                Some((slot, addr)) if code == "MOVED" => ServerErrorKind::Moved { slot, addr },
+                Some((slot, addr)) => ServerErrorKind::Ask { slot, addr },
+                None => ServerErrorKind::Other(code),
+            },
+            "TRYAGAIN" => ServerErrorKind::TryAgain,
+            "LOADING" => ServerErrorKind::Loading,
+            "BUSY" => ServerErrorKind::Busy,
+            "NOSCRIPT" => ServerErrorKind::NoScript,
+            "READONLY" => ServerErrorKind::ReadOnly,
+            "NOAUTH" => ServerErrorKind::NoAuth,
+            "WRONGPASS" => ServerErrorKind::WrongPass,
+            "NOPERM" => ServerErrorKind::NoPerm,
+            "OOM" => ServerErrorKind::Oom,
+            "EXECABORT" => ServerErrorKind::ExecAbort,
+            "CROSSSLOT" => ServerErrorKind::CrossSlot,
+            "CLUSTERDOWN" => ServerErrorKind::ClusterDown,
+            _ => ServerErrorKind::Other(code),
+        };
+        ServerError { kind, message }
+    }
+
+    /// Error prefix
+    pub fn code(&self) -> &str {
+        match self.kind {
+            ServerErrorKind::Err => "ERR",
+            ServerErrorKind::WrongType => "WRONGTYPE",
+            ServerErrorKind::Moved { .. } => "MOVED",
+            ServerErrorKind::Ask { .. } => "ASK",
+            ServerErrorKind::TryAgain => "TRYAGAIN",
+            ServerErrorKind::Loading => "LOADING",
+            ServerErrorKind::Busy => "BUSY",
+            ServerErrorKind::NoScript => "NOSCRIPT",
+            ServerErrorKind::ReadOnly => "READONLY",
+            ServerErrorKind::NoAuth => "NOAUTH",
+            ServerErrorKind::WrongPass => "WRONGPASS",
+            ServerErrorKind::NoPerm => "NOPERM",
+            ServerErrorKind::Oom => "OOM",
+            ServerErrorKind::ExecAbort => "EXECABORT",
+            ServerErrorKind::CrossSlot => "CROSSSLOT",
+            ServerErrorKind::ClusterDown => "CLUSTERDOWN",
+            ServerErrorKind::Other(ref code) => code,
+        }
+    }
+
+    /// Command could succeed if retried later on the same connection
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self.kind,
+            ServerErrorKind::TryAgain
+                | ServerErrorKind::Loading
+                | ServerErrorKind::Busy
+                | ServerErrorKind::ClusterDown
+        )
+    }
+
+    /// Command must be sent to another cluster node
+    pub fn is_redirect(&self) -> bool {
+        matches!(
+            self.kind,
+            ServerErrorKind::Moved { .. } | ServerErrorKind::Ask { .. }
+        )
+    }
+
+    /// Connection could not be used for further commands
+    ///
+    /// Connection is not authenticated or, after failover, it is
+    /// connected to a read only replica.
+    pub fn is_connection_fatal(&self) -> bool {
+        matches!(
+            self.kind,
+            ServerErrorKind::NoAuth | ServerErrorKind::WrongPass | ServerErrorKind::ReadOnly
+        )
+    }
+}
+
+/// Parse `3999 127.0.0.1:6381` part of `MOVED` and `ASK` errors
+fn parse_redirect(message: &ByteString) -> Option<(u16, ByteString)> {
+    let idx = message.find(' ')?;
+    Some((message[..idx].parse().ok()?, message.slice(idx + 1..)))
+}
+
+impl From<ByteString> for ServerError {
+    fn from(err: ByteString) -> ServerError {
+        ServerError::parse(err)
+    }
+}
+
+impl fmt::Display for ServerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.message.is_empty() {
+            f.write_str(self.code())
+        } else {
+            write!(f, "{} {}", self.code(), self.message)
+        }
+    }
+}
+
+impl std::error::Error for ServerError {}
+
 #[derive(Debug, Display, From)]
 /// Redis command execution errors
 pub enum CommandError {
     /// A redis server error response
-    Error(ByteString),
+    Error(ServerError),
 
     /// A command response parse error
     #[display(fmt = "Command output parse error: {}", _0)]
     Output(&'static str, Response),
 
+    /// A command response decode error with detailed message
+    #[display(fmt = "Command output decode error: {}", _0)]
+    Decode(String, Response),
+
     /// Redis protocol level errors
     Protocol(Error),
 }
@@ -6,4 +6,16 @@ pub mod errors;
 pub mod bytes;
 pub mod connector;
 pub mod client;
-pub mod simple;
\ No newline at end of file
+pub mod simple;
+pub mod monitor;
+pub mod response;
+pub mod args;
+pub mod serialized;
+#[cfg(feature = "testing")]
+pub mod testing;
+
+pub use self::args::{ToArg, ToArgs};
+pub use self::response::FromResponse;
+
+#[cfg(feature = "derive")]
+pub use mco_redis_derive::{FromResponse, ToArgs};
@@ -0,0 +1,182 @@
+//! Redis `MONITOR` stream
+use std::io::Write;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use mco::net::TcpStream;
+
+use crate::bytes::{ByteString, Bytes, BytesMut, BytesVec};
+use crate::codec::Encoder;
+use crate::codec_redis::{Codec, Request, Response};
+use crate::errors::{CommandError, Error};
+use crate::simple::read_response;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Command processed by redis server
+pub struct MonitorEvent {
+    /// Time the command was processed
+    pub timestamp: SystemTime,
+    /// Database index
+    pub db: u32,
+    /// Client address, `lua` or `unix:path` for scripts and unix socket clients
+    pub client: String,
+    /// Command name and arguments
+    pub args: Vec<Bytes>,
+}
+
+impl MonitorEvent {
+    /// Parse single line of the `MONITOR` output
+    ///
+    /// Line format is `1339518083.107412 [0 127.0.0.1:60866] "keys" "*"`
+    pub fn parse(line: &str) -> Option<MonitorEvent> {
+        let (ts, rest) = line.split_once(' ')?;
+        let (secs, micros) = ts.split_once('.')?;
+        let timestamp = UNIX_EPOCH
+            + Duration::from_secs(secs.parse().ok()?)
+            + Duration::from_micros(micros.parse().ok()?);
+
+        // client address could contain brackets, e.g. `[0 [::1]:6379]`
+        let (source, args) = rest.strip_prefix('[')?.split_once("] ")?;
+        let (db, client) = source.split_once(' ')?;
+
+        Some(MonitorEvent {
+            timestamp,
+            db: db.parse().ok()?,
+            client: client.to_string(),
+            args: parse_args(args.as_bytes())?,
+        })
+    }
+}
+
+/// Parse quoted and escaped arguments
+fn parse_args(mut line: &[u8]) -> Option<Vec<Bytes>> {
+    let mut args = Vec::new();
+    while !line.is_empty() {
+        if line[0] != b'"' {
+            return None;
+        }
+        let mut arg = Vec::new();
+        let mut idx = 1;
+        loop {
+            match *line.get(idx)? {
+                b'"' => break,
+                b'\\' => {
+                    let (ch, len) = match *line.get(idx + 1)? {
+                        b'n' => (b'\n', 2),
+                        b'r' => (b'\r', 2),
+                        b't' => (b'\t', 2),
+                        b'a' => (0x07, 2),
+                        b'b' => (0x08, 2),
+                        b'x' => {
+                            let hex = std::str::from_utf8(line.get(idx + 2..idx + 4)?).ok()?;
+                            (u8::from_str_radix(hex, 16).ok()?, 4)
+                        }
+                        ch => (ch, 2),
+                    };
+                    arg.push(ch);
+                    idx += len;
+                }
+                ch => {
+                    arg.push(ch);
+                    idx += 1;
+                }
+            }
+        }
+        args.push(Bytes::from(arg));
+        line = line[idx + 1..]
+            .strip_prefix(b" ")
+            .unwrap_or(&line[idx + 1..]);
+    }
+    Some(args)
+}
+
+/// Stream of commands processed by redis server
+///
+/// Monitor occupies the connection, it is created with
+/// `SimpleClient::monitor()` and yields events until connection is closed.
+/// Stream ends after io or protocol error. Lines contain full command
+/// arguments, use `DecodeLimits::max_line_size()` to allow large values.
+///
+/// ```rust,no_run
+/// use mco_redis::connector::RedisConnector;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // requires real redis server, MONITOR is not implemented by `MockServer`
+///     let redis = RedisConnector::new("127.0.0.1:6379").connect_simple()?;
+///
+///     for event in redis.monitor()? {
+///         let event = event?;
+///         println!("db{} {} {:?}", event.db, event.client, event.args);
+///     }
+///     Ok(())
+/// }
+/// ```
+pub struct Monitor {
+    io: Option<TcpStream>,
+    codec: Codec,
+    buf: BytesVec,
+}
+
+impl Monitor {
+    pub(crate) fn start(
+        mut io: TcpStream,
+        codec: Codec,
+        buf: BytesVec,
+    ) -> Result<Self, CommandError> {
+        let mut req = BytesMut::new();
+        codec.encode(
+            Request::Array(vec![Request::from_static("MONITOR")]),
+            &mut req,
+        )?;
+        io.write_all(&req)?;
+
+        // stream could be idle for a long time
+        io.set_read_timeout(None)?;
+        let mut monitor = Monitor {
+            io: Some(io),
+            codec,
+            buf,
+        };
+        match monitor.read_line()? {
+            Some(line) if line == "OK" => Ok(monitor),
+            Some(line) => Err(CommandError::Output(
+                "Unexpected monitor output",
+                Response::String(line),
+            )),
+            None => Err(CommandError::Protocol(Error::PeerGone(None))),
+        }
+    }
+
+    fn read_line(&mut self) -> Result<Option<ByteString>, CommandError> {
+        let io = match self.io.as_mut() {
+            Some(io) => io,
+            None => return Ok(None),
+        };
+        match read_response(io, &self.codec, &mut self.buf) {
+            Ok(Response::String(line)) => Ok(Some(line)),
+            Ok(Response::Error(err)) => Err(CommandError::Error(err.into())),
+            Ok(val) => Err(CommandError::Output("Unexpected monitor output", val)),
+            Err(err) => {
+                // rest of the stream cannot be decoded
+                self.io = None;
+                match err {
+                    CommandError::Protocol(Error::PeerGone(None)) => Ok(None),
+                    err => Err(err),
+                }
+            }
+        }
+    }
+}
+
+impl Iterator for Monitor {
+    type Item = Result<MonitorEvent, CommandError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.read_line() {
+            Ok(Some(line)) => Some(MonitorEvent::parse(&line).ok_or_else(|| {
+                CommandError::Output("Cannot parse monitor line", Response::String(line))
+            })),
+            Ok(None) => None,
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
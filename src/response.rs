@@ -0,0 +1,229 @@
+//! Decoding of redis responses into rust types
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
+use std::hash::{BuildHasher, Hash};
+
+use crate::bytes::{ByteString, Bytes};
+use crate::codec_redis::Response;
+use crate::errors::CommandError;
+
+/// Trait implemented by types that can be decoded from a redis response
+///
+/// Generic commands, like `EVAL`, `FCALL` or `HGETALL` with
+/// `HGetAllCommand::output()`, decode their output with this trait.
+/// User structs could implement it with `#[derive(FromResponse)]`,
+/// available with `derive` feature.
+///
+/// Derived implementation decodes field/value arrays, i.e. output of
+/// `HGETALL` command, into named fields. Structs marked with
+/// `#[redis(positional)]` and tuple structs are decoded from arrays of
+/// values, i.e. output of `HMGET` command with `HMGetCommand::output()`.
+/// Field attributes:
+///
+/// * `#[redis(rename = "name")]` - use different field name
+/// * `#[redis(default)]` - use `Default::default()` if field is missing or nil
+/// * `#[redis(skip)]` - do not decode field, use `Default::default()`
+/// * `#[redis(with = "path")]` - decode field with
+///   `fn(Response) -> Result<T, CommandError>` function
+///
+/// ```rust
+/// use mco_redis::{cmd, FromResponse};
+/// use mco_redis::testing::MockServer;
+///
+/// #[derive(FromResponse)]
+/// struct User {
+///     name: String,
+///     #[redis(rename = "login_count", default)]
+///     logins: u64,
+/// }
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let server = MockServer::start()?;
+///     let redis = server.connector().connect()?;
+///
+///     redis.exec(cmd::HSet("user:1", "name", "alice"))?;
+///
+///     let user = redis.exec(cmd::HGetAll("user:1").output::<User>())?;
+///     assert_eq!(user.name, "alice");
+///     assert_eq!(user.logins, 0);
+///     Ok(())
+/// }
+/// ```
+pub trait FromResponse: Sized {
+    /// Decode value from a redis response
+    fn from_response(val: Response) -> Result<Self, CommandError>;
+}
+
+impl FromResponse for Response {
+    fn from_response(val: Response) -> Result<Self, CommandError> {
+        Ok(val)
+    }
+}
+
+impl FromResponse for Bytes {
+    fn from_response(val: Response) -> Result<Self, CommandError> {
+        match val {
+            Response::Bytes(val) => Ok(val),
+            Response::String(val) => Ok(val.into_bytes()),
+            _ => Err(CommandError::Output("Not a bytes object", val)),
+        }
+    }
+}
+
+macro_rules! impl_from_response {
+    ($($tp:ty),* $(,)*) => {
+        $(
+            impl FromResponse for $tp {
+                fn from_response(val: Response) -> Result<Self, CommandError> {
+                    Ok(<$tp>::try_from(val)?)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_response!(ByteString, String, f64, ());
+
+/// Integers are decoded from integer replies and from numeric strings,
+/// i.e. hash values
+macro_rules! impl_from_response_int {
+    ($($tp:ty),* $(,)*) => {
+        $(
+            impl FromResponse for $tp {
+                fn from_response(val: Response) -> Result<Self, CommandError> {
+                    let parsed = match val {
+                        Response::Integer(i) => <$tp>::try_from(i).ok(),
+                        Response::Bytes(ref bytes) => {
+                            std::str::from_utf8(bytes).ok().and_then(|s| s.parse().ok())
+                        }
+                        Response::String(ref s) => s.parse().ok(),
+                        _ => None,
+                    };
+                    parsed.ok_or(CommandError::Output(
+                        "Cannot be converted into an integer",
+                        val,
+                    ))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_response_int!(i64, isize, usize, i32, u32, u64);
+
+impl FromResponse for bool {
+    fn from_response(val: Response) -> Result<Self, CommandError> {
+        if let Response::Boolean(val) = val {
+            return Ok(val);
+        }
+        match i64::from_response(val)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            i => Err(CommandError::Output(
+                "Cannot be converted into a bool",
+                Response::Integer(i),
+            )),
+        }
+    }
+}
+
+impl<T: FromResponse> FromResponse for Option<T> {
+    fn from_response(val: Response) -> Result<Self, CommandError> {
+        match val {
+            Response::Nil => Ok(None),
+            val => T::from_response(val).map(Some),
+        }
+    }
+}
+
+impl<T: FromResponse> FromResponse for Vec<T> {
+    fn from_response(val: Response) -> Result<Self, CommandError> {
+        match val {
+            Response::Array(items) | Response::Set(items) => {
+                items.into_iter().map(T::from_response).collect()
+            }
+            _ => Err(CommandError::Output(
+                "Cannot be converted into a vector",
+                val,
+            )),
+        }
+    }
+}
+
+impl<A: FromResponse, B: FromResponse> FromResponse for (A, B) {
+    fn from_response(val: Response) -> Result<Self, CommandError> {
+        match val {
+            Response::Array(items) if items.len() == 2 => {
+                let mut items = items.into_iter();
+                Ok((
+                    A::from_response(items.next().unwrap())?,
+                    B::from_response(items.next().unwrap())?,
+                ))
+            }
+            _ => Err(CommandError::Output("Array needs to be 2 elements", val)),
+        }
+    }
+}
+
+impl<A: FromResponse, B: FromResponse, C: FromResponse> FromResponse for (A, B, C) {
+    fn from_response(val: Response) -> Result<Self, CommandError> {
+        match val {
+            Response::Array(items) if items.len() == 3 => {
+                let mut items = items.into_iter();
+                Ok((
+                    A::from_response(items.next().unwrap())?,
+                    B::from_response(items.next().unwrap())?,
+                    C::from_response(items.next().unwrap())?,
+                ))
+            }
+            _ => Err(CommandError::Output("Array needs to be 3 elements", val)),
+        }
+    }
+}
+
+impl<K, V, S> FromResponse for HashMap<K, V, S>
+where
+    K: FromResponse + Eq + Hash,
+    V: FromResponse,
+    S: BuildHasher + Default,
+{
+    fn from_response(val: Response) -> Result<Self, CommandError> {
+        let mut map = HashMap::with_hasher(S::default());
+        for (key, value) in entries(val)? {
+            map.insert(K::from_response(key)?, V::from_response(value)?);
+        }
+        Ok(map)
+    }
+}
+
+impl<K, V> FromResponse for BTreeMap<K, V>
+where
+    K: FromResponse + Ord,
+    V: FromResponse,
+{
+    fn from_response(val: Response) -> Result<Self, CommandError> {
+        let mut map = BTreeMap::new();
+        for (key, value) in entries(val)? {
+            map.insert(K::from_response(key)?, V::from_response(value)?);
+        }
+        Ok(map)
+    }
+}
+
+/// Split flat array of keys and values or RESP3 map to pairs
+///
+/// Used by maps and by `#[derive(FromResponse)]` implementations.
+pub fn entries(val: Response) -> Result<Vec<(Response, Response)>, CommandError> {
+    match val {
+        Response::Map(pairs) => Ok(pairs),
+        Response::Array(items) if items.len() % 2 == 0 => {
+            let mut result = Vec::with_capacity(items.len() / 2);
+            let mut items = items.into_iter();
+            while let (Some(key), Some(value)) = (items.next(), items.next()) {
+                result.push((key, value));
+            }
+            Ok(result)
+        }
+        _ => Err(CommandError::Output("Cannot be converted into a map", val)),
+    }
+}
@@ -0,0 +1,186 @@
+//! Serde based encoding of values
+//!
+//! Formats are enabled with `json`, `msgpack` and `bincode` features.
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::args::{ToArg, ToArgs};
+use crate::bytes::Bytes;
+use crate::codec_redis::{Request, Response};
+use crate::errors::CommandError;
+use crate::response::FromResponse;
+
+/// Serialization error
+pub type FormatError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Serialization format of `Serialized` values
+pub trait Format {
+    /// Serialize value to bytes
+    fn serialize<T: Serialize + ?Sized>(val: &T) -> Result<Vec<u8>, FormatError>;
+
+    /// Deserialize value from bytes
+    fn deserialize<T: DeserializeOwned>(data: &[u8]) -> Result<T, FormatError>;
+}
+
+#[cfg(feature = "json")]
+#[derive(Debug, Copy, Clone)]
+/// JSON format, requires `json` feature
+pub struct Json;
+
+#[cfg(feature = "json")]
+impl Format for Json {
+    fn serialize<T: Serialize + ?Sized>(val: &T) -> Result<Vec<u8>, FormatError> {
+        Ok(serde_json::to_vec(val)?)
+    }
+
+    fn deserialize<T: DeserializeOwned>(data: &[u8]) -> Result<T, FormatError> {
+        Ok(serde_json::from_slice(data)?)
+    }
+}
+
+#[cfg(feature = "msgpack")]
+#[derive(Debug, Copy, Clone)]
+/// MessagePack format, requires `msgpack` feature
+///
+/// Structs are serialized as maps with field names.
+pub struct MsgPack;
+
+#[cfg(feature = "msgpack")]
+impl Format for MsgPack {
+    fn serialize<T: Serialize + ?Sized>(val: &T) -> Result<Vec<u8>, FormatError> {
+        Ok(rmp_serde::to_vec_named(val)?)
+    }
+
+    fn deserialize<T: DeserializeOwned>(data: &[u8]) -> Result<T, FormatError> {
+        Ok(rmp_serde::from_slice(data)?)
+    }
+}
+
+#[cfg(feature = "bincode")]
+#[derive(Debug, Copy, Clone)]
+/// Bincode format, requires `bincode` feature
+pub struct Bincode;
+
+#[cfg(feature = "bincode")]
+impl Format for Bincode {
+    fn serialize<T: Serialize + ?Sized>(val: &T) -> Result<Vec<u8>, FormatError> {
+        Ok(bincode::serialize(val)?)
+    }
+
+    fn deserialize<T: DeserializeOwned>(data: &[u8]) -> Result<T, FormatError> {
+        Ok(bincode::deserialize(data)?)
+    }
+}
+
+/// Value serialized with serde
+///
+/// Value is serialized with format `F` by `Serialized::new()`, so
+/// serialization errors are returned before command is sent. As command
+/// output it is deserialized from a bulk string. Use `output()` method
+/// of commands like `GET` or `HGET` to decode their output.
+///
+/// ```rust
+/// use mco_redis::serialized::{Json, Serialized};
+/// use mco_redis::cmd;
+/// use mco_redis::testing::MockServer;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct User {
+///     name: String,
+///     logins: u64,
+/// }
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+///     let server = MockServer::start()?;
+///     let redis = server.connector().connect()?;
+///
+///     let user = User { name: "alice".to_string(), logins: 3 };
+///     redis.exec(cmd::Set("user:1", Serialized::<_, Json>::new(&user)?))?;
+///
+///     let user = redis.exec(cmd::Get("user:1").output::<Serialized<User, Json>>())?;
+///     assert_eq!(user.unwrap().into_inner().logins, 3);
+///     Ok(())
+/// }
+/// ```
+pub struct Serialized<T, F> {
+    value: T,
+    data: Bytes,
+    _f: PhantomData<F>,
+}
+
+impl<T: Serialize, F: Format> Serialized<T, F> {
+    /// Serialize value
+    pub fn new(value: T) -> Result<Self, FormatError> {
+        let data = Bytes::from(F::serialize(&value)?);
+        Ok(Serialized {
+            value,
+            data,
+            _f: PhantomData,
+        })
+    }
+}
+
+impl<T, F> Serialized<T, F> {
+    /// Get serialized representation of the value
+    pub fn as_bytes(&self) -> &Bytes {
+        &self.data
+    }
+
+    /// Get reference to the wrapped value
+    pub fn get_ref(&self) -> &T {
+        &self.value
+    }
+
+    /// Unwrap value
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T: Clone, F> Clone for Serialized<T, F> {
+    fn clone(&self) -> Self {
+        Serialized {
+            value: self.value.clone(),
+            data: self.data.clone(),
+            _f: PhantomData,
+        }
+    }
+}
+
+impl<T: fmt::Debug, F> fmt::Debug for Serialized<T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Serialized").field(&self.value).finish()
+    }
+}
+
+impl<T, F> ToArg for Serialized<T, F> {
+    fn to_arg(self) -> Request {
+        self.data.to_arg()
+    }
+}
+
+impl<T, F> ToArgs for Serialized<T, F> {
+    fn write_args(self, args: &mut Vec<Request>) {
+        args.push(self.to_arg());
+    }
+}
+
+impl<T: DeserializeOwned, F: Format> FromResponse for Serialized<T, F> {
+    fn from_response(val: Response) -> Result<Self, CommandError> {
+        let data = Bytes::from_response(val)?;
+        match F::deserialize(&data) {
+            Ok(value) => Ok(Serialized {
+                value,
+                data,
+                _f: PhantomData,
+            }),
+            Err(err) => Err(CommandError::Decode(
+                format!("Cannot deserialize value: {}", err),
+                Response::Bytes(data),
+            )),
+        }
+    }
+}
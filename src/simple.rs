@@ -1,19 +1,29 @@
 use std::cell::RefCell;
+use std::cmp;
 use std::io;
 use std::io::{Read, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 use mco::net::TcpStream;
-use crate::bytes::{BufMut, BytesMut, ByteString};
+use crate::bytes::{BufMut, BytesMut, BytesVec, ByteString, PoolRef};
 use crate::codec::{Decoder, Encoder};
-use crate::codec_redis::{Codec, Request, Response};
+use crate::codec_redis::{Codec, DecodeLimits, Request, RequestChunks, Response};
 use crate::errors::Error;
+use crate::monitor::Monitor;
+use crate::response::FromResponse;
 
-use super::cmd::Command;
-use super::errors::{CommandError};
+use super::cmd::{self, BlockingCommand, Command, NoReplyCommand};
+use super::cmd::commands::{CursorCommand, EvalCommand, Library, ScanIter, ScriptCall};
+use super::errors::{CommandError, ServerErrorKind};
 
 /// Redis client
 pub struct SimpleClient {
     pub codec: Codec,
     pub io: RefCell<Option<TcpStream>>,
+    /// Read buffer, keeps bytes received after the last response
+    read_buf: RefCell<BytesVec>,
+    /// Set when connection is closed, could be checked while io is in use
+    closed: AtomicBool,
 }
 
 unsafe impl Send for SimpleClient {}
@@ -23,7 +33,17 @@ unsafe impl Sync for SimpleClient {}
 impl SimpleClient {
     /// Create new simple client
     pub fn new(io: TcpStream) -> Self {
-        SimpleClient { codec: Codec {}, io: RefCell::new(Some(io)) }
+        SimpleClient::with_limits(io, DecodeLimits::default())
+    }
+
+    /// Create new simple client with custom response limits
+    pub fn with_limits(io: TcpStream, limits: DecodeLimits) -> Self {
+        SimpleClient {
+            codec: Codec::with_limits(limits),
+            io: RefCell::new(Some(io)),
+            read_buf: RefCell::new(PoolRef::default().get_read_buf()),
+            closed: AtomicBool::new(false),
+        }
     }
 
     /// Execute redis command
@@ -31,11 +51,136 @@ impl SimpleClient {
         where
             U: Command,
     {
-        let buf = self.encode(cmd)?;
-        let resp = self.send(&buf)?;
+        let req = self.encode_chunks(cmd.to_request())?;
+        let resp = self.send_chunks(req)?;
         self.decode::<U>(resp)
     }
 
+    /// Send redis command without waiting for the response
+    ///
+    /// Should be used for commands that are not acknowledged by server,
+    /// i.e. `CLIENT REPLY OFF` or commands sent after it.
+    pub fn exec_no_reply<U>(&self, cmd: U) -> Result<(), CommandError>
+        where
+            U: NoReplyCommand,
+    {
+        let req = self.encode_chunks(cmd.into_request())?;
+        let mut io = self.io.borrow_mut();
+        let result = match io.as_mut() {
+            Some(conn) => req.write_to(conn).map_err(CommandError::from),
+            None => return Err(CommandError::Protocol(Error::PeerGone(None))),
+        };
+        if result.is_err() {
+            self.close(&mut io);
+        }
+        result
+    }
+
+    /// Execute lua script
+    ///
+    /// Script is executed with `EVALSHA` command, if script is not loaded
+    /// yet, it is executed with `EVAL` command, which loads it to the cache.
+    pub fn exec_script<T>(&self, call: ScriptCall<T>) -> Result<T, CommandError>
+        where
+            T: FromResponse,
+    {
+        call.exec_with(|cmd| self.exec(cmd))
+    }
+
+    /// Load functions library
+    ///
+    /// Library is loaded if it is not loaded yet, or replaced if loaded
+    /// code differs.
+    pub fn ensure_library(&self, lib: &Library) -> Result<(), CommandError> {
+        if let Some(cmd) = lib.load(&self.exec(lib.list())?) {
+            self.exec(cmd)?;
+        }
+        Ok(())
+    }
+
+    /// Execute `FCALL` or `FCALL_RO` command
+    ///
+    /// If function is not found, library is loaded and command is retried.
+    pub fn exec_function<T>(&self, lib: &Library, cmd: EvalCommand<T>) -> Result<T, CommandError>
+        where
+            T: FromResponse,
+    {
+        let retry = cmd.clone();
+        match self.exec(cmd) {
+            Err(CommandError::Error(ref err))
+                if err.kind == ServerErrorKind::Err && err.message.starts_with("Function not found") =>
+            {
+                self.ensure_library(lib)?;
+                self.exec(retry)
+            }
+            res => res,
+        }
+    }
+
+    /// Iterate over all elements of the cursor based command
+    ///
+    /// Cursor is driven until redis returns 0, next batch of elements is
+    /// requested only when previous one is consumed.
+    pub fn scan<T>(&self, cmd: T) -> ScanIter<T, impl FnMut(T) -> Result<T::Output, CommandError> + '_>
+        where
+            T: CursorCommand,
+    {
+        ScanIter::new(cmd, move |cmd| self.exec(cmd))
+    }
+
+    /// Execute blocking redis command
+    ///
+    /// Socket read timeout is extended by the command's server-side timeout
+    /// for the duration of the call, so client does not give up before
+    /// redis server replies.
+    pub fn exec_blocking<U>(&self, cmd: U) -> Result<U::Output, CommandError>
+        where
+            U: BlockingCommand,
+    {
+        let timeout = cmd.timeout();
+        let req = self.encode_chunks(cmd.to_request())?;
+
+        let prev = self.read_timeout()?;
+        let extended = match prev {
+            Some(prev) if !timeout.is_zero() => Some(prev + cmd::utils::round_timeout(timeout)),
+            _ => None,
+        };
+        self.set_read_timeout(extended)?;
+        let resp = self.send_chunks(req);
+        // connection is closed on errors, its error is returned instead
+        if !self.is_closed() {
+            let _ = self.set_read_timeout(prev);
+        }
+        U::to_output(resp?.into_result().map_err(|err| CommandError::Error(err.into()))?)
+    }
+
+    /// Execute write command and wait for replicas acknowledgement
+    ///
+    /// Returns command output and the number of replicas that acknowledged
+    /// the write, it could be less than requested if timeout expires.
+    pub fn write_and_wait<U>(&self, cmd: U, replicas: usize, timeout: Duration) -> Result<(U::Output, usize), CommandError>
+        where
+            U: Command,
+    {
+        let output = self.exec(cmd)?;
+        let acked = self.exec_blocking(cmd::Wait(replicas, timeout))?;
+        Ok((output, acked))
+    }
+
+    fn read_timeout(&self) -> Result<Option<Duration>, CommandError> {
+        match self.io.borrow().as_ref() {
+            Some(io) => Ok(io.read_timeout()?),
+            None => Err(CommandError::Protocol(Error::PeerGone(None))),
+        }
+    }
+
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> Result<(), CommandError> {
+        match self.io.borrow().as_ref() {
+            Some(io) => Ok(io.set_read_timeout(timeout)?),
+            None => Err(CommandError::Protocol(Error::PeerGone(None))),
+        }
+    }
+
     pub fn encode<U: Command>(&self, cmd: U) -> Result<BytesMut, Error> {
         let mut buf_in = BytesMut::new();
         let mut req = cmd.to_request();
@@ -48,42 +193,121 @@ impl SimpleClient {
         Ok(())
     }
 
+    /// Encode request for vectored write, large values are not copied
+    pub fn encode_chunks(&self, req: Request) -> Result<RequestChunks, Error> {
+        let mut chunks = RequestChunks::new();
+        self.codec.encode_chunks(req, &mut chunks)?;
+        Ok(chunks)
+    }
+
     pub fn send(&self, arg: &BytesMut) -> Result<Response, CommandError> {
         let mut io = self.io.borrow_mut();
-        if io.is_none() {
-            return Err(CommandError::Protocol(Error::PeerGone(None)));
+        let result = match io.as_mut() {
+            Some(conn) => conn
+                .write_all(arg)
+                .and_then(|_| conn.flush())
+                .map_err(CommandError::from)
+                .and_then(|_| self.read(conn)),
+            None => return Err(CommandError::Protocol(Error::PeerGone(None))),
+        };
+        if result.is_err() {
+            self.close(&mut io);
         }
-        let io = io.as_mut().unwrap();
-        io.write_all(arg)?;
-        io.flush();
-        let mut buffer = BytesMut::with_capacity(64);
-        loop {
-            let mut buf = BytesMut::with_capacity(64);
-            buf.put(&[0; 64][..]);
-            io.read(&mut buf)?;
-            buffer.extend(buf);
-            match self.codec.decode(&mut buffer)? {
-                None => {
-                    continue;
-                }
-                Some(item) => {
-                    return Ok(item);
-                }
-            }
+        result
+    }
+
+    /// Send request encoded with `encode_chunks()` and read response
+    pub fn send_chunks(&self, req: RequestChunks) -> Result<Response, CommandError> {
+        let mut io = self.io.borrow_mut();
+        let result = match io.as_mut() {
+            Some(conn) => req
+                .write_to(conn)
+                .map_err(CommandError::from)
+                .and_then(|_| self.read(conn)),
+            None => return Err(CommandError::Protocol(Error::PeerGone(None))),
+        };
+        if result.is_err() {
+            self.close(&mut io);
+        }
+        result
+    }
+
+    /// Drop connection after io or protocol error
+    ///
+    /// Rest of the partially read response would be decoded as a response
+    /// to the next command otherwise.
+    fn close(&self, io: &mut Option<TcpStream>) {
+        io.take();
+        self.closed.store(true, Ordering::Release);
+        self.read_buf.borrow_mut().clear();
+        self.codec.reset();
+    }
+
+    fn read(&self, io: &mut TcpStream) -> Result<Response, CommandError> {
+        let mut buf = self.read_buf.borrow_mut();
+        let result = read_response(io, &self.codec, &mut buf);
+
+        // do not keep memory allocated for large responses
+        let (hw, _) = PoolRef::default().read_params().unpack();
+        if buf.is_empty() && buf.capacity() > hw {
+            *buf = PoolRef::default().get_read_buf();
         }
+        result
     }
 
     pub fn decode<U>(&self, resp: Response) -> Result<U::Output, CommandError>
         where
             U: Command, {
-        return U::to_output(resp.into_result().map_err(CommandError::Error)?);
+        return U::to_output(resp.into_result().map_err(|err| CommandError::Error(err.into()))?);
+    }
+
+    /// Start `MONITOR` stream
+    ///
+    /// Stream occupies the connection, so client is consumed.
+    pub fn monitor(self) -> Result<Monitor, CommandError> {
+        match self.io.into_inner() {
+            Some(io) => Monitor::start(io, self.codec, self.read_buf.into_inner()),
+            None => Err(CommandError::Protocol(Error::PeerGone(None))),
+        }
     }
 
     pub fn is_closed(&self) -> bool {
-        self.io.borrow().is_none()
+        self.closed.load(Ordering::Acquire)
     }
 }
 
+/// Read single response from the socket
+///
+/// Data is read directly to spare capacity of the buffer, bytes received
+/// after the response are kept in the buffer. Read window is limited
+/// by high watermark of the pool, it is zeroed before read.
+pub(crate) fn read_response(io: &mut TcpStream, codec: &Codec, buf: &mut BytesVec) -> Result<Response, CommandError> {
+    loop {
+        if let Some(item) = codec.decode_vec(buf)? {
+            return Ok(item);
+        }
 
+        // decoder reserves space for large bulk strings itself
+        let (hw, lw) = PoolRef::default().read_params().unpack();
+        if buf.remaining_mut() < lw {
+            buf.reserve(hw);
+        }
 
-
+        let result = {
+            let dst = buf.chunk_mut();
+            let len = cmp::min(dst.len(), hw);
+            // spare capacity is uninitialized, reader gets zeroed slice
+            let dst = unsafe {
+                std::ptr::write_bytes(dst.as_mut_ptr(), 0, len);
+                std::slice::from_raw_parts_mut(dst.as_mut_ptr(), len)
+            };
+            io.read(dst)
+        };
+        match result {
+            Ok(0) => return Err(CommandError::Protocol(Error::PeerGone(None))),
+            Ok(n) => unsafe { buf.advance_mut(n) },
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err.into()),
+        }
+    }
+}
@@ -0,0 +1,1035 @@
+//! In-process redis server for tests
+//!
+//! Requires `testing` feature. `MockServer` listens on a random local port
+//! and keeps data in memory. It implements a subset of redis commands:
+//! strings, keys with expiration, hashes, lists, sets and pub/sub.
+//! Any command could be scripted with canned responses or faults, see `Action`.
+//!
+//! ```rust
+//! use mco_redis::bytes::Bytes;
+//! use mco_redis::cmd;
+//! use mco_redis::codec_redis::Response;
+//! use mco_redis::testing::{Action, MockServer};
+//!
+//! let server = MockServer::start().unwrap();
+//! let redis = server.connector().connect_simple().unwrap();
+//!
+//! redis.exec(cmd::Set("key", "value").expire_secs(10)).unwrap();
+//! assert_eq!(redis.exec(cmd::Get("key")).unwrap(), Some(Bytes::from_static(b"value")));
+//!
+//! server.once("GET", Action::Reply(Response::Error("ERR injected".into())));
+//! assert!(redis.exec(cmd::Get("key")).is_err());
+//! assert!(redis.exec(cmd::Get("key")).is_ok());
+//! ```
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::io::{self, Read, Write};
+use std::net::{Shutdown, SocketAddr};
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use std::{cmp, str};
+
+use mco::coroutine::{sleep, spawn};
+use mco::net::{TcpListener, TcpStream};
+
+use crate::bytes::{ByteString, Bytes, BytesMut};
+use crate::codec::{Decoder, Encoder};
+use crate::codec_redis::{Request, Response, ServerCodec};
+use crate::connector::RedisConnector;
+
+const WRONGTYPE: &str = "WRONGTYPE Operation against a key holding the wrong kind of value";
+const NOT_INTEGER: &str = "ERR value is not an integer or out of range";
+const SYNTAX: &str = "ERR syntax error";
+
+#[derive(Debug, Clone)]
+/// Scripted server behaviour for a command
+pub enum Action {
+    /// Reply with canned response, command is not executed
+    Reply(Response),
+    /// Wait before executing command
+    Delay(Duration),
+    /// Close connection without reply
+    Disconnect,
+    /// Write raw bytes as reply, for example malformed frame
+    Raw(Bytes),
+}
+
+/// In-process redis server
+///
+/// Server stops and closes all connections on drop.
+pub struct MockServer {
+    addr: SocketAddr,
+    inner: Arc<Inner>,
+}
+
+impl MockServer {
+    /// Start server on random local port
+    pub fn start() -> io::Result<MockServer> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        let inner = Arc::new(Inner {
+            state: Mutex::new(State::default()),
+            stopped: AtomicBool::new(false),
+            next_id: AtomicI64::new(1),
+        });
+
+        let srv = inner.clone();
+        spawn(move || {
+            for io in listener.incoming() {
+                if srv.stopped.load(Ordering::Acquire) {
+                    break;
+                }
+                if let Ok(io) = io {
+                    let srv = srv.clone();
+                    spawn(move || srv.serve(io));
+                }
+            }
+        });
+        Ok(MockServer { addr, inner })
+    }
+
+    /// Server address
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Create connector for the server
+    pub fn connector(&self) -> RedisConnector<SocketAddr> {
+        RedisConnector::new(self.addr)
+    }
+
+    /// Queue action for the next call of the command
+    ///
+    /// Queued actions are used one per call, in order, before
+    /// the action set with `always()`.
+    pub fn once(&self, command: &str, action: Action) {
+        let mut state = self.inner.state.lock().unwrap();
+        state
+            .once
+            .entry(command.to_ascii_uppercase())
+            .or_default()
+            .push_back(action);
+    }
+
+    /// Use action for every call of the command
+    pub fn always(&self, command: &str, action: Action) {
+        let mut state = self.inner.state.lock().unwrap();
+        state.always.insert(command.to_ascii_uppercase(), action);
+    }
+
+    /// Remove all scripted actions
+    pub fn reset_actions(&self) {
+        let mut state = self.inner.state.lock().unwrap();
+        state.once.clear();
+        state.always.clear();
+    }
+
+    /// Remove all keys
+    pub fn flush(&self) {
+        self.inner.state.lock().unwrap().db.entries.clear();
+    }
+
+    /// Close all client connections
+    pub fn disconnect_all(&self) {
+        self.inner.disconnect_all();
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.inner.stopped.store(true, Ordering::Release);
+        // wake up accept loop
+        let _ = TcpStream::connect(self.addr);
+        self.inner.disconnect_all();
+    }
+}
+
+type Writer = Arc<mco::std::sync::Mutex<TcpStream>>;
+
+struct Inner {
+    state: Mutex<State>,
+    stopped: AtomicBool,
+    next_id: AtomicI64,
+}
+
+#[derive(Default)]
+struct State {
+    db: Db,
+    once: HashMap<String, VecDeque<Action>>,
+    always: HashMap<String, Action>,
+    conns: HashMap<i64, Writer>,
+    channels: HashMap<Bytes, HashMap<i64, Writer>>,
+}
+
+/// Client connection
+struct Conn {
+    id: i64,
+    name: Option<Bytes>,
+    channels: HashSet<Bytes>,
+    codec: ServerCodec,
+    writer: Writer,
+}
+
+impl Conn {
+    fn reply(&self, resp: Response) -> bool {
+        let mut buf = BytesMut::new();
+        let _ = self.codec.encode(resp, &mut buf);
+        write(&self.writer, &buf)
+    }
+}
+
+fn write(writer: &Writer, data: &[u8]) -> bool {
+    let mut io = writer.lock().unwrap();
+    io.write_all(data).and_then(|_| io.flush()).is_ok()
+}
+
+impl Inner {
+    fn serve(&self, mut io: TcpStream) {
+        let writer = match io.try_clone() {
+            Ok(io) => Arc::new(mco::std::sync::Mutex::new(io)),
+            Err(_) => return,
+        };
+        let mut conn = Conn {
+            id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            name: None,
+            channels: HashSet::new(),
+            codec: ServerCodec::new(),
+            writer,
+        };
+        self.state
+            .lock()
+            .unwrap()
+            .conns
+            .insert(conn.id, conn.writer.clone());
+
+        let mut buf = BytesMut::new();
+        let mut chunk = vec![0; 16 * 1024];
+        'conn: while !self.stopped.load(Ordering::Acquire) {
+            loop {
+                match conn.codec.decode(&mut buf) {
+                    Ok(Some(req)) => {
+                        if !self.request(&mut conn, req) {
+                            break 'conn;
+                        }
+                    }
+                    Ok(None) => break,
+                    Err(err) => {
+                        conn.reply(error(format!("ERR Protocol error: {}", err)));
+                        break 'conn;
+                    }
+                }
+            }
+            match io.read(&mut chunk) {
+                Ok(0) | Err(_) => break,
+                Ok(n) => buf.extend_from_slice(&chunk[..n]),
+            }
+        }
+
+        let _ = io.shutdown(Shutdown::Both);
+        let mut state = self.state.lock().unwrap();
+        state.conns.remove(&conn.id);
+        for channel in conn.channels {
+            unsubscribe(&mut state, &channel, conn.id);
+        }
+    }
+
+    /// Handle client request, returns false if connection must be closed
+    fn request(&self, conn: &mut Conn, req: Request) -> bool {
+        let args = match request_args(req) {
+            Some(args) if !args.is_empty() => args,
+            _ => return conn.reply(error("ERR Protocol error: invalid request")),
+        };
+        let name = String::from_utf8_lossy(&args[0]).to_ascii_uppercase();
+
+        let action = {
+            let mut state = self.state.lock().unwrap();
+            match state.once.get_mut(&name).and_then(|q| q.pop_front()) {
+                Some(action) => Some(action),
+                None => state.always.get(&name).cloned(),
+            }
+        };
+        match action {
+            Some(Action::Reply(resp)) => return conn.reply(resp),
+            Some(Action::Raw(data)) => return write(&conn.writer, &data),
+            Some(Action::Disconnect) => return false,
+            Some(Action::Delay(dur)) => sleep(dur),
+            None => (),
+        }
+
+        let args = &args[1..];
+        match name.as_str() {
+            "SUBSCRIBE" if !args.is_empty() => {
+                for channel in args {
+                    conn.channels.insert(channel.clone());
+                    self.state
+                        .lock()
+                        .unwrap()
+                        .channels
+                        .entry(channel.clone())
+                        .or_default()
+                        .insert(conn.id, conn.writer.clone());
+                    let resp = Response::Array(vec![
+                        Response::Bytes(Bytes::from_static(b"subscribe")),
+                        Response::Bytes(channel.clone()),
+                        Response::Integer(conn.channels.len() as i64),
+                    ]);
+                    if !conn.reply(resp) {
+                        return false;
+                    }
+                }
+                true
+            }
+            "UNSUBSCRIBE" => {
+                let channels: Vec<_> = if args.is_empty() {
+                    conn.channels.iter().cloned().collect()
+                } else {
+                    args.to_vec()
+                };
+                if channels.is_empty() {
+                    return conn.reply(Response::Array(vec![
+                        Response::Bytes(Bytes::from_static(b"unsubscribe")),
+                        Response::Nil,
+                        Response::Integer(0),
+                    ]));
+                }
+                for channel in channels {
+                    conn.channels.remove(&channel);
+                    unsubscribe(&mut self.state.lock().unwrap(), &channel, conn.id);
+                    let resp = Response::Array(vec![
+                        Response::Bytes(Bytes::from_static(b"unsubscribe")),
+                        Response::Bytes(channel),
+                        Response::Integer(conn.channels.len() as i64),
+                    ]);
+                    if !conn.reply(resp) {
+                        return false;
+                    }
+                }
+                true
+            }
+            "PUBLISH" if args.len() == 2 => {
+                let subscribers: Vec<_> = {
+                    let state = self.state.lock().unwrap();
+                    match state.channels.get(&args[0]) {
+                        Some(subs) => subs.values().cloned().collect(),
+                        None => Vec::new(),
+                    }
+                };
+                let mut msg = BytesMut::new();
+                let _ = conn.codec.encode(
+                    Response::Array(vec![
+                        Response::Bytes(Bytes::from_static(b"message")),
+                        Response::Bytes(args[0].clone()),
+                        Response::Bytes(args[1].clone()),
+                    ]),
+                    &mut msg,
+                );
+                for writer in &subscribers {
+                    write(writer, &msg);
+                }
+                conn.reply(Response::Integer(subscribers.len() as i64))
+            }
+            "CLIENT" if !args.is_empty() => {
+                let sub = String::from_utf8_lossy(&args[0]).to_ascii_uppercase();
+                match (sub.as_str(), args.len()) {
+                    ("ID", 1) => conn.reply(Response::Integer(conn.id)),
+                    ("SETNAME", 2) => {
+                        conn.name = Some(args[1].clone());
+                        conn.reply(ok())
+                    }
+                    ("GETNAME", 1) => conn.reply(
+                        conn.name
+                            .clone()
+                            .map(Response::Bytes)
+                            .unwrap_or(Response::Nil),
+                    ),
+                    _ => conn.reply(error(format!("ERR unknown subcommand '{}'", sub))),
+                }
+            }
+            "QUIT" => {
+                conn.reply(ok());
+                false
+            }
+            _ => {
+                let resp = self.state.lock().unwrap().db.execute(&name, args);
+                conn.reply(resp)
+            }
+        }
+    }
+
+    fn disconnect_all(&self) {
+        let conns: Vec<_> = self.state.lock().unwrap().conns.values().cloned().collect();
+        for writer in conns {
+            let _ = writer.lock().unwrap().shutdown(Shutdown::Both);
+        }
+    }
+}
+
+fn unsubscribe(state: &mut State, channel: &Bytes, id: i64) {
+    if let Some(subs) = state.channels.get_mut(channel) {
+        subs.remove(&id);
+        if subs.is_empty() {
+            state.channels.remove(channel);
+        }
+    }
+}
+
+fn request_args(req: Request) -> Option<Vec<Bytes>> {
+    match req {
+        Request::Array(items) => items
+            .into_iter()
+            .map(|item| match item {
+                Request::BulkString(s) => Some(s.into_bytes()),
+                Request::BulkStatic(s) => Some(Bytes::from_static(s)),
+                Request::BulkInteger(i) | Request::Integer(i) => Some(Bytes::from(i.to_string())),
+                Request::String(s) => Some(s.into_bytes()),
+                Request::Array(_) => None,
+            })
+            .collect(),
+        _ => None,
+    }
+}
+
+fn ok() -> Response {
+    Response::String(ByteString::from_static("OK"))
+}
+
+fn error<T: Into<String>>(msg: T) -> Response {
+    Response::Error(ByteString::from(msg.into()))
+}
+
+fn int(arg: &[u8]) -> Result<i64, String> {
+    btoi::btoi(arg).map_err(|_| NOT_INTEGER.to_string())
+}
+
+fn bulk_or_nil(val: Option<Bytes>) -> Response {
+    val.map(Response::Bytes).unwrap_or(Response::Nil)
+}
+
+/// Expiration deadline for time in milliseconds, relative or unix time
+fn deadline(ms: i64, absolute: bool) -> Instant {
+    let ms = if absolute {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as i64)
+            .unwrap_or(0);
+        ms.saturating_sub(now)
+    } else {
+        ms
+    };
+    Instant::now() + Duration::from_millis(cmp::max(ms, 0) as u64)
+}
+
+fn millis(val: &[u8], secs: bool, cmd: &str) -> Result<i64, String> {
+    let val = int(val)?;
+    let ms = if secs {
+        val.checked_mul(1000)
+    } else {
+        Some(val)
+    };
+    ms.ok_or_else(|| {
+        format!(
+            "ERR invalid expire time in '{}' command",
+            cmd.to_ascii_lowercase()
+        )
+    })
+}
+
+/// Redis glob-style pattern, only `*` and `?` are supported
+fn glob(pattern: &[u8], s: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => s.is_empty(),
+        Some((b'*', rest)) => (0..=s.len()).any(|i| glob(rest, &s[i..])),
+        Some((b'?', rest)) => !s.is_empty() && glob(rest, &s[1..]),
+        Some((c, rest)) => s.first() == Some(c) && glob(rest, &s[1..]),
+    }
+}
+
+/// Inclusive range of list items for redis `start` and `stop` indexes
+fn list_range(len: usize, start: i64, stop: i64) -> Option<(usize, usize)> {
+    let len = len as i64;
+    let start = if start < 0 {
+        cmp::max(len + start, 0)
+    } else {
+        start
+    };
+    let stop = if stop < 0 {
+        len + stop
+    } else {
+        cmp::min(stop, len - 1)
+    };
+    if start > stop || start >= len {
+        None
+    } else {
+        Some((start as usize, stop as usize))
+    }
+}
+
+/// Number of arguments, without command name, `None` for unknown commands
+fn arity(name: &str) -> Option<(usize, usize)> {
+    let many = usize::MAX;
+    Some(match name {
+        "PING" => (0, 1),
+        "ECHO" | "GET" | "GETDEL" | "INCR" | "DECR" | "STRLEN" | "TTL" | "PTTL" | "PERSIST"
+        | "TYPE" | "KEYS" | "HGETALL" | "HLEN" | "HKEYS" | "HVALS" | "LLEN" | "SMEMBERS"
+        | "SCARD" | "SELECT" => (1, 1),
+        "AUTH" | "LPOP" | "RPOP" => (1, 2),
+        "FLUSHDB" | "FLUSHALL" => (0, 1),
+        "DBSIZE" => (0, 0),
+        "SET" => (2, many),
+        "SETNX" | "GETSET" | "INCRBY" | "DECRBY" | "APPEND" | "EXPIRE" | "PEXPIRE" | "EXPIREAT"
+        | "PEXPIREAT" | "HGET" | "HEXISTS" | "LINDEX" | "SISMEMBER" => (2, 2),
+        "SETEX" | "PSETEX" | "HINCRBY" | "HSETNX" | "LRANGE" => (3, 3),
+        "MGET" | "DEL" | "EXISTS" => (1, many),
+        "MSET" | "MSETNX" | "HDEL" | "HMGET" | "LPUSH" | "RPUSH" | "SADD" | "SREM" => (2, many),
+        "HSET" => (3, many),
+        _ => return None,
+    })
+}
+
+#[derive(Default)]
+struct Db {
+    entries: HashMap<Bytes, Entry>,
+}
+
+struct Entry {
+    value: Value,
+    expires: Option<Instant>,
+}
+
+enum Value {
+    String(Bytes),
+    Hash(HashMap<Bytes, Bytes>),
+    List(VecDeque<Bytes>),
+    Set(HashSet<Bytes>),
+}
+
+impl Value {
+    fn is_empty(&self) -> bool {
+        match self {
+            Value::String(_) => false,
+            Value::Hash(h) => h.is_empty(),
+            Value::List(l) => l.is_empty(),
+            Value::Set(s) => s.is_empty(),
+        }
+    }
+}
+
+impl Db {
+    fn execute(&mut self, name: &str, args: &[Bytes]) -> Response {
+        match arity(name) {
+            None => error(format!(
+                "ERR unknown command '{}'",
+                name.to_ascii_lowercase()
+            )),
+            Some((min, max)) if args.len() < min || args.len() > max => error(format!(
+                "ERR wrong number of arguments for '{}' command",
+                name.to_ascii_lowercase()
+            )),
+            Some(_) => self.command(name, args).unwrap_or_else(error),
+        }
+    }
+
+    /// Entry that is not expired
+    fn live(&mut self, key: &Bytes) -> Option<&mut Entry> {
+        let now = Instant::now();
+        if let Some(Entry {
+            expires: Some(t), ..
+        }) = self.entries.get(key)
+        {
+            if *t <= now {
+                self.entries.remove(key);
+            }
+        }
+        self.entries.get_mut(key)
+    }
+
+    fn remove_if_empty(&mut self, key: &Bytes) {
+        if self
+            .entries
+            .get(key)
+            .map(|e| e.value.is_empty())
+            .unwrap_or(false)
+        {
+            self.entries.remove(key);
+        }
+    }
+
+    fn set(&mut self, key: &Bytes, value: Bytes, expires: Option<Instant>) {
+        let entry = Entry {
+            value: Value::String(value),
+            expires,
+        };
+        self.entries.insert(key.clone(), entry);
+    }
+
+    fn string(&mut self, key: &Bytes) -> Result<Option<Bytes>, String> {
+        match self.live(key).map(|e| &e.value) {
+            None => Ok(None),
+            Some(Value::String(s)) => Ok(Some(s.clone())),
+            Some(_) => Err(WRONGTYPE.to_string()),
+        }
+    }
+
+    fn hash(
+        &mut self,
+        key: &Bytes,
+        create: bool,
+    ) -> Result<Option<&mut HashMap<Bytes, Bytes>>, String> {
+        if create && self.live(key).is_none() {
+            let entry = Entry {
+                value: Value::Hash(HashMap::new()),
+                expires: None,
+            };
+            self.entries.insert(key.clone(), entry);
+        }
+        match self.live(key).map(|e| &mut e.value) {
+            None => Ok(None),
+            Some(Value::Hash(h)) => Ok(Some(h)),
+            Some(_) => Err(WRONGTYPE.to_string()),
+        }
+    }
+
+    fn list(&mut self, key: &Bytes, create: bool) -> Result<Option<&mut VecDeque<Bytes>>, String> {
+        if create && self.live(key).is_none() {
+            let entry = Entry {
+                value: Value::List(VecDeque::new()),
+                expires: None,
+            };
+            self.entries.insert(key.clone(), entry);
+        }
+        match self.live(key).map(|e| &mut e.value) {
+            None => Ok(None),
+            Some(Value::List(l)) => Ok(Some(l)),
+            Some(_) => Err(WRONGTYPE.to_string()),
+        }
+    }
+
+    fn set_value(
+        &mut self,
+        key: &Bytes,
+        create: bool,
+    ) -> Result<Option<&mut HashSet<Bytes>>, String> {
+        if create && self.live(key).is_none() {
+            let entry = Entry {
+                value: Value::Set(HashSet::new()),
+                expires: None,
+            };
+            self.entries.insert(key.clone(), entry);
+        }
+        match self.live(key).map(|e| &mut e.value) {
+            None => Ok(None),
+            Some(Value::Set(s)) => Ok(Some(s)),
+            Some(_) => Err(WRONGTYPE.to_string()),
+        }
+    }
+
+    fn incr_by(&mut self, key: &Bytes, delta: i64) -> Result<Response, String> {
+        let current = match self.string(key)? {
+            Some(val) => int(&val)?,
+            None => 0,
+        };
+        let val = current
+            .checked_add(delta)
+            .ok_or_else(|| "ERR increment or decrement would overflow".to_string())?;
+        let expires = self.live(key).and_then(|e| e.expires);
+        self.set(key, Bytes::from(val.to_string()), expires);
+        Ok(Response::Integer(val))
+    }
+
+    fn command(&mut self, name: &str, args: &[Bytes]) -> Result<Response, String> {
+        Ok(match name {
+            "PING" => match args.first() {
+                Some(msg) => Response::Bytes(msg.clone()),
+                None => Response::String(ByteString::from_static("PONG")),
+            },
+            "ECHO" => Response::Bytes(args[0].clone()),
+            "SELECT" | "AUTH" => ok(),
+            "FLUSHDB" | "FLUSHALL" => {
+                self.entries.clear();
+                ok()
+            }
+            "DBSIZE" => {
+                let now = Instant::now();
+                self.entries
+                    .retain(|_, e| e.expires.map(|t| t > now).unwrap_or(true));
+                Response::Integer(self.entries.len() as i64)
+            }
+
+            // strings
+            "GET" => bulk_or_nil(self.string(&args[0])?),
+            "SET" => return self.set_command(args),
+            "SETNX" => {
+                if self.live(&args[0]).is_some() {
+                    Response::Integer(0)
+                } else {
+                    self.set(&args[0], args[1].clone(), None);
+                    Response::Integer(1)
+                }
+            }
+            "SETEX" | "PSETEX" => {
+                let ms = millis(&args[1], name == "SETEX", name)?;
+                if ms <= 0 {
+                    return Err(format!(
+                        "ERR invalid expire time in '{}' command",
+                        name.to_ascii_lowercase()
+                    ));
+                }
+                self.set(&args[0], args[2].clone(), Some(deadline(ms, false)));
+                ok()
+            }
+            "GETSET" => {
+                let old = self.string(&args[0])?;
+                self.set(&args[0], args[1].clone(), None);
+                bulk_or_nil(old)
+            }
+            "GETDEL" => {
+                let old = self.string(&args[0])?;
+                self.entries.remove(&args[0]);
+                bulk_or_nil(old)
+            }
+            "MGET" => Response::Array(
+                args.iter()
+                    .map(|key| bulk_or_nil(self.string(key).unwrap_or(None)))
+                    .collect(),
+            ),
+            "MSET" | "MSETNX" => {
+                if args.len() % 2 == 1 {
+                    return Err(format!(
+                        "ERR wrong number of arguments for '{}' command",
+                        name.to_ascii_lowercase()
+                    ));
+                }
+                if name == "MSETNX" && args.chunks(2).any(|kv| self.live(&kv[0]).is_some()) {
+                    return Ok(Response::Integer(0));
+                }
+                for kv in args.chunks(2) {
+                    self.set(&kv[0], kv[1].clone(), None);
+                }
+                if name == "MSET" {
+                    ok()
+                } else {
+                    Response::Integer(1)
+                }
+            }
+            "INCR" => return self.incr_by(&args[0], 1),
+            "DECR" => return self.incr_by(&args[0], -1),
+            "INCRBY" => return self.incr_by(&args[0], int(&args[1])?),
+            "DECRBY" => {
+                let delta = int(&args[1])?
+                    .checked_neg()
+                    .ok_or_else(|| NOT_INTEGER.to_string())?;
+                return self.incr_by(&args[0], delta);
+            }
+            "APPEND" => {
+                let mut val = BytesMut::from(&self.string(&args[0])?.unwrap_or_default()[..]);
+                val.extend_from_slice(&args[1]);
+                let len = val.len();
+                let expires = self.live(&args[0]).and_then(|e| e.expires);
+                self.set(&args[0], val.freeze(), expires);
+                Response::Integer(len as i64)
+            }
+            "STRLEN" => {
+                Response::Integer(self.string(&args[0])?.map(|s| s.len()).unwrap_or(0) as i64)
+            }
+
+            // keys
+            "DEL" => {
+                let mut count = 0;
+                for key in args {
+                    if self.live(key).is_some() {
+                        self.entries.remove(key);
+                        count += 1;
+                    }
+                }
+                Response::Integer(count)
+            }
+            "EXISTS" => {
+                Response::Integer(args.iter().filter(|key| self.live(key).is_some()).count() as i64)
+            }
+            "EXPIRE" | "PEXPIRE" | "EXPIREAT" | "PEXPIREAT" => {
+                let ms = millis(&args[1], name == "EXPIRE" || name == "EXPIREAT", name)?;
+                let expires = deadline(ms, name.ends_with("AT"));
+                match self.live(&args[0]) {
+                    Some(entry) => {
+                        entry.expires = Some(expires);
+                        Response::Integer(1)
+                    }
+                    None => Response::Integer(0),
+                }
+            }
+            "TTL" | "PTTL" => match self.live(&args[0]) {
+                None => Response::Integer(-2),
+                Some(Entry { expires: None, .. }) => Response::Integer(-1),
+                Some(Entry {
+                    expires: Some(t), ..
+                }) => {
+                    let ms = t.saturating_duration_since(Instant::now()).as_millis() as i64;
+                    Response::Integer(if name == "TTL" { (ms + 500) / 1000 } else { ms })
+                }
+            },
+            "PERSIST" => match self.live(&args[0]) {
+                Some(entry) if entry.expires.is_some() => {
+                    entry.expires = None;
+                    Response::Integer(1)
+                }
+                _ => Response::Integer(0),
+            },
+            "TYPE" => Response::String(ByteString::from_static(
+                match self.live(&args[0]).map(|e| &e.value) {
+                    None => "none",
+                    Some(Value::String(_)) => "string",
+                    Some(Value::Hash(_)) => "hash",
+                    Some(Value::List(_)) => "list",
+                    Some(Value::Set(_)) => "set",
+                },
+            )),
+            "KEYS" => {
+                let now = Instant::now();
+                Response::Array(
+                    self.entries
+                        .iter()
+                        .filter(|(key, e)| {
+                            e.expires.map(|t| t > now).unwrap_or(true) && glob(&args[0], key)
+                        })
+                        .map(|(key, _)| Response::Bytes(key.clone()))
+                        .collect(),
+                )
+            }
+
+            // hashes
+            "HSET" => {
+                if args.len() % 2 != 1 {
+                    return Err("ERR wrong number of arguments for 'hset' command".to_string());
+                }
+                let hash = self.hash(&args[0], true)?.unwrap();
+                let mut added = 0;
+                for kv in args[1..].chunks(2) {
+                    if hash.insert(kv[0].clone(), kv[1].clone()).is_none() {
+                        added += 1;
+                    }
+                }
+                Response::Integer(added)
+            }
+            "HSETNX" => {
+                let hash = self.hash(&args[0], true)?.unwrap();
+                if hash.contains_key(&args[1]) {
+                    Response::Integer(0)
+                } else {
+                    hash.insert(args[1].clone(), args[2].clone());
+                    Response::Integer(1)
+                }
+            }
+            "HGET" => bulk_or_nil(
+                self.hash(&args[0], false)?
+                    .and_then(|h| h.get(&args[1]).cloned()),
+            ),
+            "HMGET" => {
+                let hash = self.hash(&args[0], false)?;
+                Response::Array(
+                    args[1..]
+                        .iter()
+                        .map(|field| bulk_or_nil(hash.as_ref().and_then(|h| h.get(field).cloned())))
+                        .collect(),
+                )
+            }
+            "HGETALL" | "HKEYS" | "HVALS" => {
+                let mut items = Vec::new();
+                for (field, value) in self.hash(&args[0], false)?.into_iter().flatten() {
+                    if name != "HVALS" {
+                        items.push(Response::Bytes(field.clone()));
+                    }
+                    if name != "HKEYS" {
+                        items.push(Response::Bytes(value.clone()));
+                    }
+                }
+                Response::Array(items)
+            }
+            "HDEL" => {
+                let removed = match self.hash(&args[0], false)? {
+                    Some(hash) => args[1..]
+                        .iter()
+                        .filter(|f| hash.remove(*f).is_some())
+                        .count(),
+                    None => 0,
+                };
+                self.remove_if_empty(&args[0]);
+                Response::Integer(removed as i64)
+            }
+            "HEXISTS" => Response::Integer(
+                self.hash(&args[0], false)?
+                    .map(|h| h.contains_key(&args[1]))
+                    .unwrap_or(false) as i64,
+            ),
+            "HLEN" => {
+                Response::Integer(self.hash(&args[0], false)?.map(|h| h.len()).unwrap_or(0) as i64)
+            }
+            "HINCRBY" => {
+                let delta = int(&args[2])?;
+                let hash = self.hash(&args[0], true)?.unwrap();
+                let current = match hash.get(&args[1]) {
+                    Some(val) => {
+                        int(val).map_err(|_| "ERR hash value is not an integer".to_string())?
+                    }
+                    None => 0,
+                };
+                let val = current
+                    .checked_add(delta)
+                    .ok_or_else(|| "ERR increment or decrement would overflow".to_string())?;
+                hash.insert(args[1].clone(), Bytes::from(val.to_string()));
+                Response::Integer(val)
+            }
+
+            // lists
+            "LPUSH" | "RPUSH" => {
+                let list = self.list(&args[0], true)?.unwrap();
+                for item in &args[1..] {
+                    if name == "LPUSH" {
+                        list.push_front(item.clone());
+                    } else {
+                        list.push_back(item.clone());
+                    }
+                }
+                Response::Integer(list.len() as i64)
+            }
+            "LPOP" | "RPOP" => {
+                let count = match args.get(1) {
+                    Some(count) => {
+                        Some(usize::try_from(int(count)?).map_err(|_| NOT_INTEGER.to_string())?)
+                    }
+                    None => None,
+                };
+                let resp = match self.list(&args[0], false)? {
+                    None => Response::Nil,
+                    Some(list) => {
+                        let mut items = Vec::new();
+                        for _ in 0..count.unwrap_or(1) {
+                            let item = if name == "LPOP" {
+                                list.pop_front()
+                            } else {
+                                list.pop_back()
+                            };
+                            match item {
+                                Some(item) => items.push(Response::Bytes(item)),
+                                None => break,
+                            }
+                        }
+                        if count.is_some() {
+                            Response::Array(items)
+                        } else {
+                            items.pop().unwrap_or(Response::Nil)
+                        }
+                    }
+                };
+                self.remove_if_empty(&args[0]);
+                resp
+            }
+            "LLEN" => {
+                Response::Integer(self.list(&args[0], false)?.map(|l| l.len()).unwrap_or(0) as i64)
+            }
+            "LRANGE" => {
+                let (start, stop) = (int(&args[1])?, int(&args[2])?);
+                let items = match self.list(&args[0], false)? {
+                    Some(list) => match list_range(list.len(), start, stop) {
+                        Some((start, stop)) => list
+                            .range(start..=stop)
+                            .map(|item| Response::Bytes(item.clone()))
+                            .collect(),
+                        None => Vec::new(),
+                    },
+                    None => Vec::new(),
+                };
+                Response::Array(items)
+            }
+            "LINDEX" => {
+                let idx = int(&args[1])?;
+                let item = self.list(&args[0], false)?.and_then(|list| {
+                    let idx = if idx < 0 {
+                        list.len() as i64 + idx
+                    } else {
+                        idx
+                    };
+                    usize::try_from(idx)
+                        .ok()
+                        .and_then(|idx| list.get(idx).cloned())
+                });
+                bulk_or_nil(item)
+            }
+
+            // sets
+            "SADD" => {
+                let set = self.set_value(&args[0], true)?.unwrap();
+                Response::Integer(
+                    args[1..]
+                        .iter()
+                        .filter(|m| set.insert((*m).clone()))
+                        .count() as i64,
+                )
+            }
+            "SREM" => {
+                let removed = match self.set_value(&args[0], false)? {
+                    Some(set) => args[1..].iter().filter(|m| set.remove(*m)).count(),
+                    None => 0,
+                };
+                self.remove_if_empty(&args[0]);
+                Response::Integer(removed as i64)
+            }
+            "SMEMBERS" => Response::Array(match self.set_value(&args[0], false)? {
+                Some(set) => set.iter().map(|m| Response::Bytes(m.clone())).collect(),
+                None => Vec::new(),
+            }),
+            "SISMEMBER" => Response::Integer(
+                self.set_value(&args[0], false)?
+                    .map(|s| s.contains(&args[1]))
+                    .unwrap_or(false) as i64,
+            ),
+            "SCARD" => Response::Integer(
+                self.set_value(&args[0], false)?
+                    .map(|s| s.len())
+                    .unwrap_or(0) as i64,
+            ),
+
+            _ => {
+                return Err(format!(
+                    "ERR unknown command '{}'",
+                    name.to_ascii_lowercase()
+                ))
+            }
+        })
+    }
+
+    fn set_command(&mut self, args: &[Bytes]) -> Result<Response, String> {
+        let (key, value) = (&args[0], &args[1]);
+        let (mut nx, mut xx, mut get, mut keep_ttl) = (false, false, false, false);
+        let mut expires = None;
+        let mut opts = args[2..].iter();
+        while let Some(opt) = opts.next() {
+            let opt = String::from_utf8_lossy(opt).to_ascii_uppercase();
+            match opt.as_str() {
+                "NX" if !xx => nx = true,
+                "XX" if !nx => xx = true,
+                "GET" => get = true,
+                "KEEPTTL" if expires.is_none() => keep_ttl = true,
+                "EX" | "PX" | "EXAT" | "PXAT" if expires.is_none() && !keep_ttl => {
+                    let val = opts.next().ok_or_else(|| SYNTAX.to_string())?;
+                    let ms = millis(val, opt == "EX" || opt == "EXAT", "set")?;
+                    if ms <= 0 {
+                        return Err("ERR invalid expire time in 'set' command".to_string());
+                    }
+                    expires = Some(deadline(ms, opt.ends_with("AT")));
+                }
+                _ => return Err(SYNTAX.to_string()),
+            }
+        }
+
+        let old = if get { self.string(key)? } else { None };
+        let exists = self.live(key).is_some();
+        if (nx && exists) || (xx && !exists) {
+            return Ok(if get { bulk_or_nil(old) } else { Response::Nil });
+        }
+        if keep_ttl {
+            expires = self.live(key).and_then(|e| e.expires);
+        }
+        self.set(key, value.clone(), expires);
+        Ok(if get { bulk_or_nil(old) } else { ok() })
+    }
+}
@@ -0,0 +1,40 @@
+mod common;
+
+use mco_redis::cmd::{self, commands::AclSelector};
+
+use common::{args, encode};
+
+#[test]
+fn test_set_user() {
+    let req = encode(
+        cmd::AclSetUser("user")
+            .on()
+            .key_pattern("user:*")
+            .allow_command("get"),
+    );
+    assert_eq!(
+        req,
+        args(&["ACL", "SETUSER", "user", "on", "~user:*", "+get"])
+    );
+}
+
+#[test]
+fn test_selector_quoting() {
+    let req = encode(
+        cmd::AclSetUser("user").selector(
+            AclSelector::new()
+                .read_key_pattern("my key")
+                .key_pattern(r#"a"b\c"#)
+                .allow_command("get"),
+        ),
+    );
+    assert_eq!(
+        req,
+        args(&[
+            "ACL",
+            "SETUSER",
+            "user",
+            r#"("%R~my key" "~a\"b\\c" "+get")"#
+        ])
+    );
+}
@@ -0,0 +1,45 @@
+mod common;
+
+use mco_redis::cmd;
+
+use common::{args, encode};
+
+#[test]
+fn test_single_values() {
+    assert_eq!(
+        encode(cmd::HSet("h", "a", 1).entry("b", 2.5).entry("c", true)),
+        args(&["HSET", "h", "a", "1", "b", "2.5", "c", "1"])
+    );
+    assert_eq!(
+        encode(cmd::Set("k", u64::MAX)),
+        args(&["SET", "k", "18446744073709551615"])
+    );
+    assert_eq!(
+        encode(cmd::MSet("a", 1).entry("b", "x")),
+        args(&["MSET", "a", "1", "b", "x"])
+    );
+    assert_eq!(
+        encode(cmd::LPush("l", -1).value("x")),
+        args(&["LPUSH", "l", "-1", "x"])
+    );
+}
+
+#[test]
+fn test_multiple_values() {
+    assert_eq!(
+        encode(cmd::HSetEntries("h", vec![("a", 1), ("b", 2)])),
+        args(&["HSET", "h", "a", "1", "b", "2"])
+    );
+    assert_eq!(
+        encode(
+            cmd::HSet("h", "a", "1")
+                .entries((Some("b"), Some("2")))
+                .entries(None::<(&str, &str)>)
+        ),
+        args(&["HSET", "h", "a", "1", "b", "2"])
+    );
+    assert_eq!(
+        encode(cmd::RPush("l", "a").extend(vec![vec!["b", "c"], vec![]])),
+        args(&["RPUSH", "l", "a", "b", "c"])
+    );
+}
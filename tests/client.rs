@@ -0,0 +1,152 @@
+use std::time::Duration;
+
+use mco_redis::bytes::Bytes;
+use mco_redis::cmd;
+use mco_redis::codec_redis::DecodeLimits;
+use mco_redis::errors::{CommandError, Error};
+use mco_redis::testing::{Action, MockServer};
+
+#[test]
+fn test_read_timeout_closes_connection() {
+    let server = MockServer::start().unwrap();
+    let redis = server
+        .connector()
+        .connect_simple_timeout(Duration::from_millis(100))
+        .unwrap();
+
+    server.once("GET", Action::Delay(Duration::from_millis(300)));
+    assert!(redis.exec(cmd::Get("key")).is_err());
+    assert!(redis.is_closed());
+    assert!(matches!(
+        redis.exec(cmd::Get("key")),
+        Err(CommandError::Protocol(Error::PeerGone(None)))
+    ));
+}
+
+#[test]
+fn test_disconnect_closes_connection() {
+    let server = MockServer::start().unwrap();
+    let redis = server.connector().connect_simple().unwrap();
+
+    redis.exec(cmd::Set("key", "value")).unwrap();
+    server.once("GET", Action::Disconnect);
+    assert!(redis.exec(cmd::Get("key")).is_err());
+    assert!(redis.is_closed());
+
+    let redis = server.connector().connect().unwrap();
+    assert_eq!(
+        redis.exec(cmd::Get("key")).unwrap(),
+        Some(Bytes::from_static(b"value"))
+    );
+    server.once("GET", Action::Disconnect);
+    assert!(redis.exec(cmd::Get("key")).is_err());
+    assert!(!redis.is_connected());
+}
+
+#[test]
+fn test_limit_error_closes_connection() {
+    let server = MockServer::start().unwrap();
+    let redis = server
+        .connector()
+        .limits(DecodeLimits::default().max_bulk_size(4))
+        .connect_simple()
+        .unwrap();
+
+    // value of the rejected bulk string must not be decoded as next response
+    redis.exec(cmd::Set("big", "+FORGED\r\n")).unwrap();
+    assert!(matches!(
+        redis.exec(cmd::Get("big")),
+        Err(CommandError::Protocol(Error::Limit(_)))
+    ));
+    assert!(redis.is_closed());
+    assert!(matches!(
+        redis.exec(cmd::Get("nonexistent")),
+        Err(CommandError::Protocol(Error::PeerGone(None)))
+    ));
+}
+
+#[test]
+fn test_blocking_limit_error() {
+    let server = MockServer::start().unwrap();
+    let redis = server
+        .connector()
+        .limits(DecodeLimits::default().max_bulk_size(4))
+        .connect_simple_timeout(Duration::from_secs(1))
+        .unwrap();
+
+    // error must not be replaced by failure to restore read timeout
+    server.once("BLPOP", Action::Raw(Bytes::from_static(b"$100\r\n")));
+    assert!(matches!(
+        redis.exec_blocking(cmd::BLPop("list", Duration::from_millis(100))),
+        Err(CommandError::Protocol(Error::Limit(_)))
+    ));
+    assert!(redis.is_closed());
+}
+
+#[test]
+fn test_line_limit() {
+    let server = MockServer::start().unwrap();
+    let redis = server
+        .connector()
+        .limits(DecodeLimits::default().max_line_size(16))
+        .connect_simple()
+        .unwrap();
+
+    server.once(
+        "PING",
+        Action::Raw(Bytes::from(format!("+{}\r\n", "a".repeat(32)))),
+    );
+    assert!(matches!(
+        redis.exec(cmd::Ping()),
+        Err(CommandError::Protocol(Error::Limit(_)))
+    ));
+    assert!(redis.is_closed());
+
+    let redis = server
+        .connector()
+        .limits(DecodeLimits::default().max_line_size(64))
+        .connect_simple()
+        .unwrap();
+    server.once(
+        "PING",
+        Action::Raw(Bytes::from(format!("+{}\r\n", "a".repeat(32)))),
+    );
+    assert_eq!(redis.exec(cmd::Ping()).unwrap(), "a".repeat(32));
+}
+
+#[test]
+fn test_client_id_and_name() {
+    let server = MockServer::start().unwrap();
+    let redis = server.connector().name("test").connect().unwrap();
+    assert!(redis.id().is_some());
+
+    // debug output must not wait for in-flight commands
+    server.once("GET", Action::Delay(Duration::from_millis(500)));
+    let client = redis.clone();
+    let handle = std::thread::spawn(move || client.exec(cmd::Get("key")));
+    std::thread::sleep(Duration::from_millis(50));
+    let start = std::time::Instant::now();
+    let debug = format!("{:?}", redis);
+    assert!(start.elapsed() < Duration::from_millis(250));
+    assert!(debug.contains(&format!("id: {:?}", redis.id())));
+    assert!(debug.contains("name: Some(\"test\")"));
+    handle.join().unwrap().unwrap();
+
+    redis.set_name("other").unwrap();
+    assert!(format!("{:?}", redis).contains("name: Some(\"other\")"));
+    assert_eq!(redis.name().unwrap().unwrap(), "other");
+}
+
+#[test]
+fn test_large_response() {
+    let server = MockServer::start().unwrap();
+    let redis = server.connector().connect_simple().unwrap();
+
+    // response is received with many reads
+    let value = "x".repeat(1024 * 1024 + 7);
+    redis.exec(cmd::Set("key", value.as_str())).unwrap();
+    let result = redis.exec(cmd::Get("key")).unwrap().unwrap();
+    assert_eq!(result.len(), value.len());
+    assert_eq!(result, value.as_bytes());
+    assert_eq!(redis.exec(cmd::StrLen("key")).unwrap(), value.len() as i64);
+}
@@ -0,0 +1,412 @@
+use std::fmt::Debug;
+use std::io;
+
+use mco_redis::bytes::{ByteString, Bytes, BytesMut};
+use mco_redis::codec::{Decoder, Encoder};
+use mco_redis::codec_redis::{
+    Codec, DecodeLimits, Request, RequestChunks, Response, ServerCodec, VECTORED_WRITE_THRESHOLD,
+};
+use mco_redis::errors::Error;
+
+/// Decode all frames from the buffer
+fn decode_all<D: Decoder<DecodeError = Error>>(
+    codec: &D,
+    buf: &mut BytesMut,
+) -> Vec<D::DecodeItem> {
+    let mut items = Vec::new();
+    while let Some(item) = codec.decode(buf).unwrap() {
+        items.push(item);
+    }
+    items
+}
+
+/// Feed data split at every offset and byte by byte, all splits must
+/// produce the same frames
+fn check_splits<D, F>(new: F, data: &[u8], expected: &[D::DecodeItem])
+where
+    D: Decoder<DecodeError = Error>,
+    D::DecodeItem: PartialEq + Debug,
+    F: Fn() -> D,
+{
+    for pos in 0..=data.len() {
+        let codec = new();
+        let mut buf = BytesMut::from(&data[..pos]);
+        let mut items = decode_all(&codec, &mut buf);
+        buf.extend_from_slice(&data[pos..]);
+        items.extend(decode_all(&codec, &mut buf));
+        assert_eq!(items, expected, "split at {}", pos);
+        assert!(buf.is_empty(), "split at {}", pos);
+    }
+
+    let codec = new();
+    let mut buf = BytesMut::new();
+    let mut items = Vec::new();
+    for byte in data {
+        buf.extend_from_slice(&[*byte]);
+        items.extend(decode_all(&codec, &mut buf));
+    }
+    assert_eq!(items, expected, "byte by byte");
+    assert!(buf.is_empty(), "byte by byte");
+}
+
+fn check_response(data: &[u8], expected: &[Response]) {
+    check_splits(Codec::new, data, expected);
+}
+
+fn bytes(val: &'static str) -> Response {
+    Response::Bytes(Bytes::from_static(val.as_bytes()))
+}
+
+fn string(val: &'static str) -> Response {
+    Response::String(ByteString::from_static(val))
+}
+
+#[test]
+fn test_simple_values() {
+    check_response(
+        b"+OK\r\n-ERR failed\r\n:-42\r\n",
+        &[
+            string("OK"),
+            Response::Error(ByteString::from_static("ERR failed")),
+            Response::Integer(-42),
+        ],
+    );
+}
+
+#[test]
+fn test_bulk_strings() {
+    check_response(b"$0\r\n\r\n", &[bytes("")]);
+    check_response(b"$5\r\nhello\r\n", &[bytes("hello")]);
+    // `\r\n` inside of the value
+    check_response(b"$4\r\n\r\n\r\n\r\n", &[bytes("\r\n\r\n")]);
+    check_response(b"$3\r\na\rb\r\n$1\r\n\r\r\n", &[bytes("a\rb"), bytes("\r")]);
+}
+
+#[test]
+fn test_nil() {
+    check_response(
+        b"$-1\r\n*-1\r\n_\r\n",
+        &[Response::Nil, Response::Nil, Response::Nil],
+    );
+}
+
+#[test]
+fn test_arrays() {
+    check_response(b"*0\r\n", &[Response::Array(vec![])]);
+    check_response(
+        b"*3\r\n*2\r\n:1\r\n$3\r\nfoo\r\n*0\r\n*1\r\n*1\r\n$-1\r\n+OK\r\n",
+        &[
+            Response::Array(vec![
+                Response::Array(vec![Response::Integer(1), bytes("foo")]),
+                Response::Array(vec![]),
+                Response::Array(vec![Response::Array(vec![Response::Nil])]),
+            ]),
+            string("OK"),
+        ],
+    );
+}
+
+fn decode_error(limits: DecodeLimits, data: &[u8]) -> Error {
+    let codec = Codec::with_limits(limits);
+    let mut buf = BytesMut::from(data);
+    loop {
+        match codec.decode(&mut buf) {
+            Ok(Some(_)) => continue,
+            Ok(None) => panic!("no error"),
+            Err(err) => return err,
+        }
+    }
+}
+
+#[test]
+fn test_limits() {
+    let limits = DecodeLimits::default()
+        .max_bulk_size(4)
+        .max_array_len(2)
+        .max_depth(2)
+        .max_line_size(8);
+
+    check_splits(
+        || Codec::with_limits(limits),
+        b"$4\r\nabcd\r\n*2\r\n*2\r\n:1\r\n:2\r\n:3\r\n+1234567\r\n",
+        &[
+            bytes("abcd"),
+            Response::Array(vec![
+                Response::Array(vec![Response::Integer(1), Response::Integer(2)]),
+                Response::Integer(3),
+            ]),
+            string("1234567"),
+        ],
+    );
+
+    assert!(matches!(decode_error(limits, b"$5\r\n"), Error::Limit(_)));
+    assert!(matches!(decode_error(limits, b"*3\r\n"), Error::Limit(_)));
+    assert!(matches!(
+        decode_error(limits, b"*1\r\n*1\r\n*1\r\n"),
+        Error::Limit(_)
+    ));
+    assert!(matches!(
+        decode_error(limits, b"+123456789\r\n"),
+        Error::Limit(_)
+    ));
+    assert!(matches!(
+        decode_error(limits, b"+123456789"),
+        Error::Limit(_)
+    ));
+}
+
+#[test]
+fn test_protocol_errors() {
+    let limits = DecodeLimits::default();
+    assert!(matches!(decode_error(limits, b"?\r\n"), Error::Parse(_)));
+    assert!(matches!(decode_error(limits, b"$-2\r\n"), Error::Parse(_)));
+    assert!(matches!(decode_error(limits, b":abc\r\n"), Error::Parse(_)));
+    assert!(matches!(
+        decode_error(limits, b"$3\r\nabcde\r\n"),
+        Error::Parse(_)
+    ));
+}
+
+fn req(args: &[&'static str]) -> Request {
+    Request::Array(
+        args.iter()
+            .map(|arg| Request::BulkString((*arg).into()))
+            .collect(),
+    )
+}
+
+#[test]
+fn test_multibulk_requests() {
+    check_splits(
+        ServerCodec::new,
+        b"*2\r\n$3\r\nGET\r\n$3\r\nkey\r\n*3\r\n$3\r\nSET\r\n$1\r\nk\r\n$0\r\n\r\n",
+        &[req(&["GET", "key"]), req(&["SET", "k", ""])],
+    );
+}
+
+#[test]
+fn test_inline_requests() {
+    check_splits(
+        ServerCodec::new,
+        b"PING\r\n\r\nGET  key\r\n*1\r\n$4\r\nPING\r\n",
+        &[req(&["PING"]), req(&["GET", "key"]), req(&["PING"])],
+    );
+}
+
+#[test]
+fn test_resp3_values() {
+    check_response(
+        b"%2\r\n+a\r\n:1\r\n$1\r\nb\r\n~2\r\n#t\r\n#f\r\n%0\r\n",
+        &[
+            Response::Map(vec![
+                (string("a"), Response::Integer(1)),
+                (
+                    bytes("b"),
+                    Response::Set(vec![Response::Boolean(true), Response::Boolean(false)]),
+                ),
+            ]),
+            Response::Map(vec![]),
+        ],
+    );
+    check_response(
+        b">3\r\n+message\r\n+chan\r\n,1.5\r\n,-inf\r\n,nan\r\n",
+        &[
+            Response::Push(vec![
+                string("message"),
+                string("chan"),
+                Response::Double(1.5),
+            ]),
+            Response::Double(f64::NEG_INFINITY),
+            Response::Double(f64::NAN),
+        ],
+    );
+}
+
+fn encode(codec: &ServerCodec, val: Response) -> BytesMut {
+    let mut buf = BytesMut::new();
+    codec.encode(val, &mut buf).unwrap();
+    buf
+}
+
+#[test]
+fn test_encode_resp3_values() {
+    let val = Response::Array(vec![
+        Response::Map(vec![(string("a"), Response::Boolean(true))]),
+        Response::Set(vec![Response::Double(2.5)]),
+        Response::Push(vec![Response::Nil]),
+    ]);
+
+    let codec = ServerCodec::new();
+    assert_eq!(
+        &encode(&codec, val.clone())[..],
+        &b"*3\r\n*2\r\n+a\r\n:1\r\n*1\r\n$3\r\n2.5\r\n*1\r\n$-1\r\n"[..]
+    );
+
+    codec.set_resp3(true);
+    let data = encode(&codec, val.clone());
+    assert_eq!(
+        &data[..],
+        &b"*3\r\n%1\r\n+a\r\n#t\r\n~1\r\n,2.5\r\n>1\r\n_\r\n"[..]
+    );
+    check_response(&data, &[val]);
+}
+
+#[test]
+fn test_quoted_inline_requests() {
+    check_splits(
+        ServerCodec::new,
+        b"SET \"a b\" 'c \\'d'\r\nSET k \"\\x41\\n\\\"\" \"\"\r\nGET a\"b c\"\r\n",
+        &[
+            req(&["SET", "a b", "c 'd"]),
+            req(&["SET", "k", "A\n\"", ""]),
+            req(&["GET", "ab c"]),
+        ],
+    );
+}
+
+fn request_error(data: &[u8]) -> Error {
+    let codec = ServerCodec::new();
+    let mut buf = BytesMut::from(data);
+    loop {
+        match codec.decode(&mut buf) {
+            Ok(Some(_)) => continue,
+            Ok(None) => panic!("no error"),
+            Err(err) => return err,
+        }
+    }
+}
+
+#[test]
+fn test_invalid_requests() {
+    // unbalanced quotes
+    assert!(matches!(request_error(b"GET \"key\r\n"), Error::Parse(_)));
+    assert!(matches!(
+        request_error(b"GET \"key\"x\r\n"),
+        Error::Parse(_)
+    ));
+    assert!(matches!(request_error(b"GET 'key\r\n"), Error::Parse(_)));
+    // multibulk items must be bulk strings
+    assert!(matches!(
+        request_error(b"*2\r\n$3\r\nGET\r\n*1\r\n"),
+        Error::Parse(_)
+    ));
+    assert!(matches!(
+        request_error(b"*2\r\n$3\r\nGET\r\n:1\r\n"),
+        Error::Parse(_)
+    ));
+    assert!(matches!(
+        request_error(b"*2\r\n$3\r\nGET\r\n$-1\r\n"),
+        Error::Parse(_)
+    ));
+}
+
+#[test]
+fn test_empty_requests() {
+    check_splits(
+        ServerCodec::new,
+        b"*0\r\n*-1\r\n  \r\n*1\r\n$4\r\nPING\r\n",
+        &[req(&["PING"])],
+    );
+}
+
+/// Writer that accepts from 1 to `max` bytes per call
+struct PartialWriter {
+    data: Vec<u8>,
+    max: usize,
+    seed: u64,
+    max_slices: usize,
+}
+
+impl PartialWriter {
+    fn new(max: usize) -> Self {
+        PartialWriter {
+            data: Vec::new(),
+            max,
+            seed: 0x2545_f491_4f6c_dd1d,
+            max_slices: 0,
+        }
+    }
+
+    fn limit(&mut self) -> usize {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 7;
+        self.seed ^= self.seed << 17;
+        // mostly large writes with frequent few-byte writes
+        match self.seed % 4 {
+            0 => 1 + (self.seed >> 8) as usize % 7,
+            _ => 1 + (self.seed >> 8) as usize % self.max,
+        }
+    }
+}
+
+impl io::Write for PartialWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.write_vectored(&[io::IoSlice::new(buf)])
+    }
+
+    fn write_vectored(&mut self, bufs: &[io::IoSlice<'_>]) -> io::Result<usize> {
+        self.max_slices = self.max_slices.max(bufs.len());
+        let mut limit = self.limit();
+        let mut written = 0;
+        for buf in bufs {
+            let n = limit.min(buf.len());
+            self.data.extend_from_slice(&buf[..n]);
+            written += n;
+            limit -= n;
+            if limit == 0 {
+                break;
+            }
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Returns max number of slices passed to a single write
+fn check_chunked_write(req: Request, max: usize) -> usize {
+    let codec = Codec::default();
+    let mut expected = BytesMut::new();
+    codec.encode(req.clone(), &mut expected).unwrap();
+
+    let mut chunks = RequestChunks::new();
+    codec.encode_chunks(req, &mut chunks).unwrap();
+    assert_eq!(chunks.len(), expected.len());
+
+    let mut writer = PartialWriter::new(max);
+    chunks.write_to(&mut writer).unwrap();
+    assert_eq!(writer.data, &expected[..]);
+    assert!(writer.max_slices <= 64);
+    writer.max_slices
+}
+
+#[test]
+fn test_chunked_write() {
+    let value = |size: usize, fill: u8| Request::BulkString(Bytes::from(vec![fill; size]).into());
+
+    // values straddling the threshold
+    let req = Request::Array(vec![
+        Request::from_static("MSET"),
+        Request::from_static("a"),
+        value(VECTORED_WRITE_THRESHOLD - 1, b'a'),
+        Request::from_static("b"),
+        value(VECTORED_WRITE_THRESHOLD, b'b'),
+        Request::from_static("c"),
+        value(VECTORED_WRITE_THRESHOLD + 1, b'c'),
+    ]);
+    check_chunked_write(req.clone(), 100);
+    check_chunked_write(req, 200_000);
+
+    // more chunks than a single vectored write accepts
+    let mut items = vec![Request::from_static("RPUSH"), Request::from_static("list")];
+    for idx in 0..40 {
+        items.push(value(
+            VECTORED_WRITE_THRESHOLD + idx,
+            b'0' + (idx % 10) as u8,
+        ));
+        items.push(Request::BulkInteger(idx as i64));
+    }
+    assert_eq!(check_chunked_write(Request::Array(items), 300_000), 64);
+}
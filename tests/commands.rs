@@ -0,0 +1,161 @@
+use std::collections::HashMap;
+
+use mco_redis::bytes::Bytes;
+use mco_redis::cmd::{self, commands::KeyType, TtlResult};
+use mco_redis::errors::CommandError;
+use mco_redis::testing::MockServer;
+
+fn bytes(val: &'static str) -> Bytes {
+    Bytes::from_static(val.as_bytes())
+}
+
+#[test]
+fn test_strings() {
+    let server = MockServer::start().unwrap();
+    let redis = server.connector().connect().unwrap();
+
+    assert!(redis.exec(cmd::Set("key", "value")).unwrap());
+    assert_eq!(redis.exec(cmd::Get("key")).unwrap(), Some(bytes("value")));
+    assert_eq!(redis.exec(cmd::Get("missing")).unwrap(), None);
+    assert!(!redis.exec(cmd::SetNx("key", "other")).unwrap());
+    assert_eq!(redis.exec(cmd::Append("key", "-1")).unwrap(), 7);
+    assert_eq!(redis.exec(cmd::StrLen("key")).unwrap(), 7);
+    assert_eq!(
+        redis.exec(cmd::GetDel("key")).unwrap(),
+        Some(bytes("value-1"))
+    );
+    assert_eq!(redis.exec(cmd::Get("key")).unwrap(), None);
+
+    assert_eq!(redis.exec(cmd::Incr("counter")).unwrap(), 1);
+    assert_eq!(redis.exec(cmd::IncrBy("counter", 10)).unwrap(), 11);
+    redis.exec(cmd::Set("text", "value")).unwrap();
+    assert!(matches!(
+        redis.exec(cmd::Incr("text")),
+        Err(CommandError::Error(_))
+    ));
+
+    redis.exec(cmd::MSet("k1", "v1").entry("k2", "v2")).unwrap();
+    assert_eq!(
+        redis.exec(cmd::MGet("k1").key("k2").key("k3")).unwrap(),
+        vec![Some(bytes("v1")), Some(bytes("v2")), None]
+    );
+}
+
+#[test]
+fn test_keys() {
+    let server = MockServer::start().unwrap();
+    let redis = server.connector().connect_simple().unwrap();
+
+    redis.exec(cmd::Set("key", "value")).unwrap();
+    redis.exec(cmd::RPush("list", "item")).unwrap();
+    assert_eq!(
+        redis
+            .exec(cmd::Exists("key").key("list").key("missing"))
+            .unwrap(),
+        2
+    );
+    assert_eq!(redis.exec(cmd::Type("key")).unwrap(), KeyType::String);
+    assert_eq!(redis.exec(cmd::Type("list")).unwrap(), KeyType::List);
+    assert_eq!(redis.exec(cmd::Type("missing")).unwrap(), KeyType::None);
+
+    assert_eq!(redis.exec(cmd::Ttl("key")).unwrap(), TtlResult::NoExpire);
+    assert_eq!(
+        redis.exec(cmd::Ttl("missing")).unwrap(),
+        TtlResult::NotFound
+    );
+    assert!(redis.exec(cmd::Expire("key", 100)).unwrap());
+    assert!(matches!(
+        redis.exec(cmd::Ttl("key")).unwrap(),
+        TtlResult::Seconds(secs) if secs > 0 && secs <= 100
+    ));
+    assert!(redis.exec(cmd::Persist("key")).unwrap());
+    assert_eq!(redis.exec(cmd::Ttl("key")).unwrap(), TtlResult::NoExpire);
+
+    assert_eq!(
+        redis
+            .exec(cmd::Del("key").key("list").key("missing"))
+            .unwrap(),
+        2
+    );
+    assert_eq!(redis.exec(cmd::Exists("key").key("list")).unwrap(), 0);
+}
+
+#[test]
+fn test_hashes() {
+    let server = MockServer::start().unwrap();
+    let redis = server.connector().connect().unwrap();
+
+    assert_eq!(
+        redis
+            .exec(cmd::HSet("hash", "f1", "v1").entry("f2", "v2"))
+            .unwrap(),
+        2
+    );
+    assert_eq!(
+        redis.exec(cmd::HGet("hash", "f1")).unwrap(),
+        Some(bytes("v1"))
+    );
+    assert_eq!(redis.exec(cmd::HLen("hash")).unwrap(), 2);
+    assert!(redis.exec(cmd::HExists("hash", "f2")).unwrap());
+    assert_eq!(
+        redis
+            .exec(cmd::HMGet("hash", "f1").field("missing"))
+            .unwrap(),
+        vec![Some(bytes("v1")), None]
+    );
+
+    let mut expected = HashMap::new();
+    expected.insert(bytes("f1"), bytes("v1"));
+    expected.insert(bytes("f2"), bytes("v2"));
+    assert_eq!(redis.exec(cmd::HGetAll("hash")).unwrap(), expected);
+
+    assert_eq!(redis.exec(cmd::HIncrBy("hash", "count", 5)).unwrap(), 5);
+    assert_eq!(
+        redis.exec(cmd::HDel("hash", "f1").remove("count")).unwrap(),
+        2
+    );
+    assert_eq!(redis.exec(cmd::HKeys("hash")).unwrap(), vec![bytes("f2")]);
+
+    redis.exec(cmd::Set("key", "value")).unwrap();
+    assert!(matches!(
+        redis.exec(cmd::HGet("key", "f1")),
+        Err(CommandError::Error(_))
+    ));
+}
+
+#[test]
+fn test_lists() {
+    let server = MockServer::start().unwrap();
+    let redis = server.connector().connect_simple().unwrap();
+
+    assert_eq!(redis.exec(cmd::RPush("list", "b").value("c")).unwrap(), 2);
+    assert_eq!(redis.exec(cmd::LPush("list", "a")).unwrap(), 3);
+    assert_eq!(redis.exec(cmd::LLen("list")).unwrap(), 3);
+    assert_eq!(
+        redis.exec(cmd::LIndex("list", -1)).unwrap(),
+        Some(bytes("c"))
+    );
+    assert_eq!(
+        redis.exec(cmd::LRange("list", 0, -1)).unwrap(),
+        vec![bytes("a"), bytes("b"), bytes("c")]
+    );
+
+    assert_eq!(redis.exec(cmd::LPop("list")).unwrap(), Some(bytes("a")));
+    assert_eq!(redis.exec(cmd::RPop("list")).unwrap(), Some(bytes("c")));
+    assert_eq!(redis.exec(cmd::RPop("list")).unwrap(), Some(bytes("b")));
+    assert_eq!(redis.exec(cmd::RPop("list")).unwrap(), None);
+}
+
+#[test]
+fn test_flush() {
+    let server = MockServer::start().unwrap();
+    let redis = server.connector().connect().unwrap();
+
+    // mock server keeps single database
+    redis.exec(cmd::Select(1)).unwrap();
+    redis.exec(cmd::Set("key", "value")).unwrap();
+    assert_eq!(redis.exec(cmd::Get("key")).unwrap(), Some(bytes("value")));
+
+    server.flush();
+    assert_eq!(redis.exec(cmd::Get("key")).unwrap(), None);
+}
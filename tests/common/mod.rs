@@ -0,0 +1,20 @@
+#![allow(dead_code)]
+use mco_redis::bytes::BytesMut;
+use mco_redis::cmd::Command;
+use mco_redis::codec::Encoder;
+use mco_redis::codec_redis::{Codec, Request};
+
+/// Encode command request
+pub fn encode<T: Command>(cmd: T) -> BytesMut {
+    let mut buf = BytesMut::new();
+    Codec::default().encode(cmd.to_request(), &mut buf).unwrap();
+    buf
+}
+
+/// Encode expected request arguments
+pub fn args(args: &[&'static str]) -> BytesMut {
+    let mut buf = BytesMut::new();
+    let req = Request::Array(args.iter().map(|arg| Request::from_static(arg)).collect());
+    Codec::default().encode(req, &mut buf).unwrap();
+    buf
+}
@@ -0,0 +1,85 @@
+mod common;
+
+use std::time::Duration;
+
+use mco_redis::cmd::{self, commands::ConnectionInfo};
+use mco_redis::codec_redis::Response;
+use mco_redis::errors::CommandError;
+use mco_redis::testing::{Action, MockServer};
+
+use common::{args, encode};
+
+const CLIENT_LIST: &str = "id=3 addr=127.0.0.1:52555 laddr=127.0.0.1:6379 fd=8 name=worker \
+age=12 idle=0 flags=N db=1 sub=0 psub=0 multi=-1 cmd=client|list user=default \
+resp=2 lib-name=mco-redis lib-ver=0.1 tot-mem=22426\n\
+id=4 addr=[::1]:52556 laddr=[::1]:6379 fd=9 name= age=1 idle=1 flags=P db=0 sub=2 \
+psub=1 multi=-1 cmd=subscribe user=default resp=3\n";
+
+#[test]
+fn test_connection_info_parse() {
+    let info = ConnectionInfo::parse(CLIENT_LIST.lines().next().unwrap()).unwrap();
+    assert_eq!(info.id, 3);
+    assert_eq!(info.addr, "127.0.0.1:52555");
+    assert_eq!(info.laddr, "127.0.0.1:6379");
+    assert_eq!(info.fd, 8);
+    assert_eq!(info.name, "worker");
+    assert_eq!(info.age, 12);
+    assert_eq!(info.flags, "N");
+    assert_eq!(info.db, 1);
+    assert_eq!(info.multi, -1);
+    assert_eq!(info.cmd, "client|list");
+    assert_eq!(info.user, "default");
+    assert_eq!(info.resp, 2);
+    assert_eq!(info.lib_name, "mco-redis");
+    assert_eq!(info.lib_ver, "0.1");
+    // fields without struct member
+    assert_eq!(info.get("tot-mem"), Some("22426"));
+    assert_eq!(info.get("missing"), None);
+
+    match ConnectionInfo::parse("id=3 db=x") {
+        Err(CommandError::Decode(msg, _)) => assert!(msg.contains("`db`")),
+        res => panic!("unexpected result: {:?}", res),
+    }
+}
+
+#[test]
+fn test_client_list() {
+    let server = MockServer::start().unwrap();
+    let redis = server.connector().connect_simple().unwrap();
+
+    server.once("CLIENT", Action::Reply(Response::Bytes(CLIENT_LIST.into())));
+    let list = redis.exec(cmd::ClientList()).unwrap();
+    assert_eq!(list.len(), 2);
+    assert_eq!(list[1].addr, "[::1]:52556");
+    assert_eq!(list[1].name, "");
+    assert_eq!((list[1].sub, list[1].psub, list[1].resp), (2, 1, 3));
+
+    server.once(
+        "CLIENT",
+        Action::Reply(Response::Bytes("id=1 age=-1\n".into())),
+    );
+    assert!(matches!(
+        redis.exec(cmd::ClientInfo()),
+        Err(CommandError::Decode(_, _))
+    ));
+}
+
+#[test]
+fn test_timeouts_round_up() {
+    assert_eq!(
+        encode(cmd::ClientPause(Duration::from_micros(1500))),
+        args(&["CLIENT", "PAUSE", "2"])
+    );
+    assert_eq!(
+        encode(cmd::ClientPause(Duration::from_nanos(1))),
+        args(&["CLIENT", "PAUSE", "1"])
+    );
+    assert_eq!(
+        encode(cmd::ClientKill().max_age(Duration::from_millis(1500))),
+        args(&["CLIENT", "KILL", "MAXAGE", "2"])
+    );
+    assert_eq!(
+        encode(cmd::ClientKill().max_age(Duration::from_secs(3))),
+        args(&["CLIENT", "KILL", "MAXAGE", "3"])
+    );
+}
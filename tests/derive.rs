@@ -0,0 +1,217 @@
+#![cfg(feature = "derive")]
+// generated code must not trigger warnings in user crates
+#![deny(unused_mut)]
+mod common;
+
+use mco_redis::bytes::Bytes;
+use mco_redis::cmd;
+use mco_redis::codec_redis::Response;
+use mco_redis::errors::CommandError;
+use mco_redis::{FromResponse, ToArgs};
+
+use common::{args, encode};
+
+#[derive(FromResponse, Debug, PartialEq)]
+struct User {
+    name: String,
+    #[redis(rename = "login_count", default)]
+    logins: u64,
+    active: bool,
+}
+
+#[derive(FromResponse, Debug, PartialEq)]
+#[redis(positional)]
+struct Point {
+    x: i32,
+    y: Option<i64>,
+}
+
+fn bulk(val: &'static str) -> Response {
+    Response::Bytes(Bytes::from_static(val.as_bytes()))
+}
+
+#[test]
+fn test_named_fields() {
+    let val = Response::Array(vec![
+        bulk("name"),
+        bulk("alice"),
+        bulk("login_count"),
+        bulk("3"),
+        bulk("active"),
+        bulk("1"),
+    ]);
+    assert_eq!(
+        User::from_response(val).unwrap(),
+        User {
+            name: "alice".to_string(),
+            logins: 3,
+            active: true,
+        }
+    );
+
+    let val = Response::Array(vec![bulk("name"), bulk("bob"), bulk("active"), bulk("0")]);
+    assert_eq!(
+        User::from_response(val).unwrap(),
+        User {
+            name: "bob".to_string(),
+            logins: 0,
+            active: false,
+        }
+    );
+}
+
+#[test]
+fn test_positional() {
+    let val = Response::Array(vec![bulk("-5"), Response::Nil]);
+    assert_eq!(Point::from_response(val).unwrap(), Point { x: -5, y: None });
+}
+
+fn parse_csv(val: Response) -> Result<Vec<String>, CommandError> {
+    let text = String::from_response(val)?;
+    Ok(text.split(',').map(|s| s.to_string()).collect())
+}
+
+#[derive(FromResponse, Debug, PartialEq)]
+struct Tagged {
+    #[redis(with = "parse_csv")]
+    tags: Vec<String>,
+    #[redis(skip)]
+    cache: Option<u32>,
+    #[redis(skip)]
+    hits: u64,
+}
+
+#[derive(FromResponse, Debug, PartialEq)]
+struct Entry(String, #[redis(default)] u64, #[redis(skip)] bool);
+
+#[test]
+fn test_with_and_skip() {
+    let val = Response::Array(vec![
+        bulk("tags"),
+        bulk("a,b"),
+        // skipped fields are not decoded even if present
+        bulk("hits"),
+        bulk("invalid"),
+    ]);
+    assert_eq!(
+        Tagged::from_response(val).unwrap(),
+        Tagged {
+            tags: vec!["a".to_string(), "b".to_string()],
+            cache: None,
+            hits: 0,
+        }
+    );
+}
+
+#[test]
+fn test_tuple_struct() {
+    let val = Response::Array(vec![bulk("key"), Response::Nil]);
+    assert_eq!(
+        Entry::from_response(val).unwrap(),
+        Entry("key".to_string(), 0, false)
+    );
+
+    let val = Response::Array(vec![bulk("key"), bulk("5"), bulk("1")]);
+    assert_eq!(
+        Entry::from_response(val).unwrap(),
+        Entry("key".to_string(), 5, false)
+    );
+
+    assert!(Entry::from_response(bulk("key")).is_err());
+}
+
+#[test]
+fn test_missing_field() {
+    let val = Response::Array(vec![bulk("login_count"), bulk("3")]);
+    match User::from_response(val) {
+        Err(CommandError::Output(msg, Response::Nil)) => assert_eq!(msg, "Missing field `name`"),
+        res => panic!("unexpected result: {:?}", res),
+    }
+
+    let val = Response::Array(vec![bulk("name"), bulk("carol")]);
+    assert!(matches!(
+        User::from_response(val),
+        Err(CommandError::Output("Missing field `active`", _))
+    ));
+
+    // positional struct with missing required value
+    assert!(Point::from_response(Response::Array(vec![])).is_err());
+}
+
+#[test]
+fn test_invalid_values() {
+    assert!(u64::from_response(bulk("-1")).is_err());
+    assert!(i32::from_response(Response::Integer(i64::MAX)).is_err());
+    assert!(bool::from_response(bulk("2")).is_err());
+    assert!(i64::from_response(bulk("1.5")).is_err());
+}
+
+#[derive(ToArgs)]
+struct Profile {
+    name: &'static str,
+    #[redis(rename = "login_count")]
+    logins: u64,
+    email: Option<&'static str>,
+    #[redis(skip)]
+    #[allow(dead_code)]
+    cache: u32,
+}
+
+#[derive(ToArgs)]
+struct Pair(&'static str, i64);
+
+#[derive(ToArgs)]
+#[redis(positional)]
+struct Coords {
+    lon: f64,
+    lat: f64,
+}
+
+#[test]
+fn test_to_args_named() {
+    let profile = Profile {
+        name: "alice",
+        logins: 3,
+        email: None,
+        cache: 1,
+    };
+    assert_eq!(
+        encode(cmd::HSetEntries("user", profile)),
+        args(&["HSET", "user", "name", "alice", "login_count", "3"])
+    );
+
+    let profile = Profile {
+        name: "bob",
+        logins: 0,
+        email: Some("bob@example.com"),
+        cache: 1,
+    };
+    assert_eq!(
+        encode(cmd::HSetEntries("user", profile)),
+        args(&[
+            "HSET",
+            "user",
+            "name",
+            "bob",
+            "login_count",
+            "0",
+            "email",
+            "bob@example.com",
+        ])
+    );
+}
+
+#[test]
+fn test_to_args_positional() {
+    assert_eq!(
+        encode(cmd::RPush("list", "a").extend([Pair("b", 1), Pair("c", -2)])),
+        args(&["RPUSH", "list", "a", "b", "1", "c", "-2"])
+    );
+    assert_eq!(
+        encode(cmd::RPush("list", "a").extend([Coords {
+            lon: 1.5,
+            lat: -2.0
+        }])),
+        args(&["RPUSH", "list", "a", "1.5", "-2"])
+    );
+}
@@ -0,0 +1,105 @@
+use mco_redis::bytes::ByteString;
+use mco_redis::cmd;
+use mco_redis::codec_redis::Response;
+use mco_redis::errors::{CommandError, ServerError, ServerErrorKind};
+use mco_redis::testing::{Action, MockServer};
+
+fn parse(err: &'static str) -> ServerError {
+    ServerError::parse(ByteString::from_static(err))
+}
+
+#[test]
+fn test_prefix() {
+    let err = parse("WRONGTYPE Operation against a key holding the wrong kind of value");
+    assert_eq!(err.kind, ServerErrorKind::WrongType);
+    assert_eq!(
+        err.message,
+        "Operation against a key holding the wrong kind of value"
+    );
+
+    let err = parse("ERR Function not found");
+    assert_eq!(err.kind, ServerErrorKind::Err);
+    assert_eq!(err.message, "Function not found");
+
+    let err = parse("NOSCRIPT No matching script. Please use EVAL.");
+    assert_eq!(err.kind, ServerErrorKind::NoScript);
+    assert_eq!(err.message, "No matching script. Please use EVAL.");
+
+    let err = parse("CUSTOM error from module");
+    assert_eq!(
+        err.kind,
+        ServerErrorKind::Other(ByteString::from_static("CUSTOM"))
+    );
+    assert_eq!(err.code(), "CUSTOM");
+    assert_eq!(err.message, "error from module");
+    assert_eq!(err.to_string(), "CUSTOM error from module");
+}
+
+#[test]
+fn test_without_message() {
+    let err = parse("LOADING");
+    assert_eq!(err.kind, ServerErrorKind::Loading);
+    assert_eq!(err.message, "");
+    assert!(err.is_retryable());
+    assert_eq!(err.to_string(), "LOADING");
+
+    let err = parse("");
+    assert_eq!(err.kind, ServerErrorKind::Other(ByteString::new()));
+    assert_eq!(err.message, "");
+}
+
+#[test]
+fn test_redirect() {
+    let err = parse("MOVED 3999 127.0.0.1:6381");
+    assert_eq!(
+        err.kind,
+        ServerErrorKind::Moved {
+            slot: 3999,
+            addr: ByteString::from_static("127.0.0.1:6381")
+        }
+    );
+    assert!(err.is_redirect());
+    assert_eq!(err.to_string(), "MOVED 3999 127.0.0.1:6381");
+
+    let err = parse("ASK 0 [::1]:7000");
+    assert_eq!(
+        err.kind,
+        ServerErrorKind::Ask {
+            slot: 0,
+            addr: ByteString::from_static("[::1]:7000")
+        }
+    );
+
+    // malformed redirects are kept as is
+    for text in [
+        "MOVED 70000 127.0.0.1:6381",
+        "MOVED x 127.0.0.1:6381",
+        "ASK 12",
+    ] {
+        let err = parse(text);
+        assert!(!err.is_redirect());
+        assert!(matches!(err.kind, ServerErrorKind::Other(_)));
+        assert_eq!(err.to_string(), text);
+    }
+}
+
+#[test]
+fn test_command_error() {
+    let server = MockServer::start().unwrap();
+    let redis = server.connector().connect_simple().unwrap();
+
+    server.once(
+        "GET",
+        Action::Reply(Response::Error(
+            "READONLY You can't write against a read only replica.".into(),
+        )),
+    );
+    match redis.exec(cmd::Get("key")) {
+        Err(CommandError::Error(err)) => {
+            assert_eq!(err.kind, ServerErrorKind::ReadOnly);
+            assert!(err.is_connection_fatal());
+            assert_eq!(err.message, "You can't write against a read only replica.");
+        }
+        res => panic!("unexpected result: {:?}", res),
+    }
+}
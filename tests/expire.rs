@@ -0,0 +1,55 @@
+mod common;
+
+use std::time::{Duration, UNIX_EPOCH};
+
+use mco_redis::cmd::{self, TtlResult};
+use mco_redis::testing::MockServer;
+
+use common::{args, encode};
+
+#[test]
+fn test_duration_timeouts() {
+    assert_eq!(
+        encode(cmd::ExpireIn("key", Duration::from_micros(1500)).unwrap()),
+        args(&["PEXPIRE", "key", "2"])
+    );
+    assert_eq!(
+        encode(
+            cmd::ExpireAtTime("key", UNIX_EPOCH + Duration::from_secs(10))
+                .unwrap()
+                .if_greater()
+        ),
+        args(&["PEXPIREAT", "key", "10000", "GT"])
+    );
+    assert_eq!(
+        encode(
+            cmd::HExpireIn("key", Duration::from_secs(5), "f1")
+                .unwrap()
+                .field("f2")
+        ),
+        args(&["HPEXPIRE", "key", "5000", "FIELDS", "2", "f1", "f2"])
+    );
+
+    assert!(cmd::ExpireIn("key", Duration::MAX).is_err());
+    assert!(cmd::HExpireIn("key", Duration::MAX, "f1").is_err());
+}
+
+#[test]
+fn test_expire_in() {
+    let server = MockServer::start().unwrap();
+    let redis = server.connector().connect().unwrap();
+
+    redis.exec(cmd::Set("key", "value")).unwrap();
+    assert!(redis
+        .exec(cmd::ExpireIn("key", Duration::from_secs(100)).unwrap())
+        .unwrap());
+    assert!(matches!(
+        redis.exec(cmd::Ttl("key")).unwrap(),
+        TtlResult::Seconds(secs) if secs > 90 && secs <= 100
+    ));
+
+    assert!(redis
+        .exec(cmd::ExpireAtTime("key", UNIX_EPOCH).unwrap())
+        .unwrap());
+    assert_eq!(redis.exec(cmd::Get("key")).unwrap(), None);
+}
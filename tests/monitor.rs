@@ -0,0 +1,83 @@
+use std::time::{Duration, UNIX_EPOCH};
+
+use mco_redis::bytes::Bytes;
+use mco_redis::errors::{CommandError, Error};
+use mco_redis::monitor::MonitorEvent;
+use mco_redis::testing::{Action, MockServer};
+
+#[test]
+fn test_parse_ipv4() {
+    let event = MonitorEvent::parse(r#"1339518083.107412 [0 127.0.0.1:60866] "keys" "*""#).unwrap();
+    assert_eq!(
+        event.timestamp,
+        UNIX_EPOCH + Duration::from_secs(1339518083) + Duration::from_micros(107412)
+    );
+    assert_eq!(event.db, 0);
+    assert_eq!(event.client, "127.0.0.1:60866");
+    assert_eq!(
+        event.args,
+        vec![Bytes::from_static(b"keys"), Bytes::from_static(b"*")]
+    );
+}
+
+#[test]
+fn test_parse_ipv6() {
+    let event = MonitorEvent::parse(r#"1339518083.107412 [3 [::1]:6379] "get" "key""#).unwrap();
+    assert_eq!(event.db, 3);
+    assert_eq!(event.client, "[::1]:6379");
+    assert_eq!(
+        event.args,
+        vec![Bytes::from_static(b"get"), Bytes::from_static(b"key")]
+    );
+}
+
+#[test]
+fn test_parse_lua() {
+    let event = MonitorEvent::parse(r#"1339518083.107412 [0 lua] "set" "a\"b" "\x00\n""#).unwrap();
+    assert_eq!(event.client, "lua");
+    assert_eq!(
+        event.args,
+        vec![
+            Bytes::from_static(b"set"),
+            Bytes::from_static(b"a\"b"),
+            Bytes::from_static(b"\x00\n")
+        ]
+    );
+}
+
+#[test]
+fn test_parse_unix() {
+    let event =
+        MonitorEvent::parse(r#"1339518083.107412 [1 unix:/tmp/redis.sock] "ping""#).unwrap();
+    assert_eq!(event.db, 1);
+    assert_eq!(event.client, "unix:/tmp/redis.sock");
+    assert_eq!(event.args, vec![Bytes::from_static(b"ping")]);
+}
+
+#[test]
+fn test_parse_invalid() {
+    assert!(MonitorEvent::parse("OK").is_none());
+    assert!(MonitorEvent::parse(r#"1339518083.107412 [0 127.0.0.1:60866 "keys""#).is_none());
+    assert!(MonitorEvent::parse(r#"1339518083.107412 [0 lua] keys"#).is_none());
+}
+
+#[test]
+fn test_monitor_stops_after_protocol_error() {
+    let server = MockServer::start().unwrap();
+    let redis = server.connector().connect_simple().unwrap();
+
+    server.once(
+        "MONITOR",
+        Action::Raw(Bytes::from_static(
+            b"+OK\r\n+1339518083.107412 [0 lua] \"ping\"\r\n$-5\r\n+1339518083.107412 [0 lua] \"ping\"\r\n",
+        )),
+    );
+    let mut monitor = redis.monitor().unwrap();
+    let event = monitor.next().unwrap().unwrap();
+    assert_eq!(event.args, vec![Bytes::from_static(b"ping")]);
+    assert!(matches!(
+        monitor.next(),
+        Some(Err(CommandError::Protocol(Error::Parse(_))))
+    ));
+    assert!(monitor.next().is_none());
+}
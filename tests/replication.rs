@@ -0,0 +1,164 @@
+mod common;
+
+use std::time::Duration;
+
+use mco_redis::bytes::{ByteString, Bytes};
+use mco_redis::cmd::{self, commands::RoleReplica, commands::ServerRole};
+use mco_redis::codec_redis::Response;
+use mco_redis::errors::{CommandError, Error};
+use mco_redis::testing::{Action, MockServer};
+
+use common::{args, encode};
+
+fn bulk(val: &'static str) -> Response {
+    Response::Bytes(Bytes::from_static(val.as_bytes()))
+}
+
+#[test]
+fn test_role() {
+    let server = MockServer::start().unwrap();
+    let redis = server.connector().connect_simple().unwrap();
+
+    server.once(
+        "ROLE",
+        Action::Reply(Response::Array(vec![
+            bulk("master"),
+            Response::Integer(3129659),
+            Response::Array(vec![
+                Response::Array(vec![bulk("127.0.0.1"), bulk("9001"), bulk("3129242")]),
+                Response::Array(vec![bulk("127.0.0.1"), bulk("9002"), bulk("3129543")]),
+            ]),
+        ])),
+    );
+    assert_eq!(
+        redis.exec(cmd::Role()).unwrap(),
+        ServerRole::Master {
+            offset: 3129659,
+            replicas: vec![
+                RoleReplica {
+                    ip: ByteString::from_static("127.0.0.1"),
+                    port: 9001,
+                    offset: 3129242,
+                },
+                RoleReplica {
+                    ip: ByteString::from_static("127.0.0.1"),
+                    port: 9002,
+                    offset: 3129543,
+                },
+            ],
+        }
+    );
+
+    server.once(
+        "ROLE",
+        Action::Reply(Response::Array(vec![
+            bulk("slave"),
+            bulk("127.0.0.1"),
+            Response::Integer(9000),
+            bulk("connected"),
+            Response::Integer(3167038),
+        ])),
+    );
+    assert_eq!(
+        redis.exec(cmd::Role()).unwrap(),
+        ServerRole::Replica {
+            master_ip: ByteString::from_static("127.0.0.1"),
+            master_port: 9000,
+            state: ByteString::from_static("connected"),
+            offset: 3167038,
+        }
+    );
+
+    server.once(
+        "ROLE",
+        Action::Reply(Response::Array(vec![
+            bulk("sentinel"),
+            Response::Array(vec![bulk("resque-master"), bulk("html-fragments-master")]),
+        ])),
+    );
+    assert_eq!(
+        redis.exec(cmd::Role()).unwrap(),
+        ServerRole::Sentinel {
+            masters: vec![
+                ByteString::from_static("resque-master"),
+                ByteString::from_static("html-fragments-master"),
+            ],
+        }
+    );
+
+    for resp in [
+        Response::Array(vec![bulk("unknown")]),
+        Response::Array(vec![
+            bulk("slave"),
+            bulk("127.0.0.1"),
+            Response::Integer(70000),
+        ]),
+        Response::Array(vec![]),
+    ] {
+        server.once("ROLE", Action::Reply(resp));
+        assert!(matches!(
+            redis.exec(cmd::Role()),
+            Err(CommandError::Output(_, _))
+        ));
+    }
+}
+
+#[test]
+fn test_timeouts_round_up() {
+    assert_eq!(
+        encode(cmd::Failover().timeout(Duration::from_micros(1500))),
+        args(&["FAILOVER", "TIMEOUT", "2"])
+    );
+    assert_eq!(
+        encode(cmd::Failover().timeout(Duration::from_nanos(1))),
+        args(&["FAILOVER", "TIMEOUT", "1"])
+    );
+    assert_eq!(
+        encode(cmd::Failover().timeout(Duration::MAX)),
+        args(&["FAILOVER", "TIMEOUT", "9223372036854775807"])
+    );
+}
+
+#[test]
+fn test_write_and_wait() {
+    let server = MockServer::start().unwrap();
+    let redis = server
+        .connector()
+        .connect_timeout(Duration::from_millis(500))
+        .unwrap();
+
+    for timeout in [
+        Duration::ZERO,
+        Duration::from_millis(500),
+        Duration::from_micros(499_001),
+    ] {
+        assert!(matches!(
+            redis.write_and_wait(cmd::Set("key", "value"), 1, timeout),
+            Err(CommandError::Protocol(Error::Command(_)))
+        ));
+    }
+    // command is not sent if validation fails
+    assert_eq!(redis.exec(cmd::Get("key")).unwrap(), None);
+
+    server.once("WAIT", Action::Reply(Response::Integer(1)));
+    assert_eq!(
+        redis
+            .write_and_wait(cmd::Set("key", "value"), 1, Duration::from_millis(100))
+            .unwrap(),
+        (true, 1)
+    );
+    assert_eq!(
+        redis.exec(cmd::Get("key")).unwrap(),
+        Some(Bytes::from_static(b"value"))
+    );
+
+    // without read timeout any non-zero timeout is allowed
+    let redis = server.connector().connect().unwrap();
+    server.once("WAIT", Action::Reply(Response::Integer(0)));
+    assert_eq!(
+        redis
+            .write_and_wait(cmd::Incr("counter"), 1, Duration::from_secs(3600))
+            .unwrap(),
+        (1, 0)
+    );
+}
@@ -0,0 +1,40 @@
+mod common;
+
+use mco_redis::cmd;
+use mco_redis::codec_redis::Response;
+use mco_redis::testing::{Action, MockServer};
+
+use common::{args, encode};
+
+#[test]
+fn test_cursor_encoding() {
+    assert_eq!(
+        encode(cmd::Scan(u64::MAX - 1).count(10)),
+        args(&["SCAN", "18446744073709551614", "COUNT", "10"])
+    );
+    assert_eq!(
+        encode(cmd::SScan("set", 5).pattern("a*")),
+        args(&["SSCAN", "set", "5", "MATCH", "a*"])
+    );
+    assert_eq!(
+        encode(cmd::Scan(0).key_type("hash")),
+        args(&["SCAN", "0", "TYPE", "hash"])
+    );
+}
+
+#[test]
+fn test_large_cursor() {
+    let server = MockServer::start().unwrap();
+    let redis = server.connector().connect_simple().unwrap();
+
+    server.once(
+        "SSCAN",
+        Action::Reply(Response::Array(vec![
+            Response::Bytes("18446744073709551614".into()),
+            Response::Array(vec![Response::Bytes("a".into())]),
+        ])),
+    );
+    let (cursor, items) = redis.exec(cmd::SScan("set", 0)).unwrap();
+    assert_eq!(cursor, u64::MAX - 1);
+    assert_eq!(items, vec!["a"]);
+}
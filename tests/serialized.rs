@@ -0,0 +1,92 @@
+#![cfg(any(feature = "json", feature = "msgpack", feature = "bincode"))]
+use std::fmt::Debug;
+
+use mco_redis::bytes::Bytes;
+use mco_redis::codec_redis::{Request, Response};
+use mco_redis::errors::CommandError;
+use mco_redis::serialized::{Format, Serialized};
+use mco_redis::{FromResponse, ToArg};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+fn roundtrip<T, F>(value: T) -> T
+where
+    T: Serialize + DeserializeOwned,
+    F: Format,
+{
+    let value = Serialized::<_, F>::new(value).unwrap();
+    let data = match value.to_arg() {
+        Request::BulkString(data) => data,
+        req => panic!("unexpected request: {:?}", req),
+    };
+    Serialized::<T, F>::from_response(Response::Bytes(data.into_bytes()))
+        .unwrap()
+        .into_inner()
+}
+
+fn check_roundtrip<F: Format>() {
+    let value = ("alice".to_string(), 3u64, vec![Some(1.5f64), None]);
+    assert_eq!(roundtrip::<_, F>(value.clone()), value);
+}
+
+fn check_decode_error<T: DeserializeOwned + Debug, F: Format>(data: &'static [u8]) {
+    match Serialized::<T, F>::from_response(Response::Bytes(Bytes::from_static(data))) {
+        Err(CommandError::Decode(msg, Response::Bytes(bytes))) => {
+            // serde error is kept in the message
+            let detail = msg.strip_prefix("Cannot deserialize value: ").unwrap();
+            assert!(!detail.is_empty());
+            assert_eq!(&bytes[..], data);
+        }
+        res => panic!("unexpected result: {:?}", res.map(|v| v.into_inner())),
+    }
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json() {
+    use mco_redis::serialized::Json;
+
+    let value = Serialized::<_, Json>::new(vec![1, 2, 3]).unwrap();
+    assert_eq!(&value.as_bytes()[..], b"[1,2,3]");
+    check_roundtrip::<Json>();
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_errors() {
+    use mco_redis::serialized::Json;
+    use std::collections::HashMap;
+
+    // json object keys must be strings
+    let map: HashMap<(i32, i32), i32> = vec![((1, 2), 3)].into_iter().collect();
+    assert!(Serialized::<_, Json>::new(map).is_err());
+
+    check_decode_error::<Vec<i32>, Json>(b"{");
+}
+
+#[cfg(feature = "msgpack")]
+#[test]
+fn test_msgpack() {
+    use mco_redis::serialized::MsgPack;
+
+    let value = Serialized::<_, MsgPack>::new(vec![1, 2, 3]).unwrap();
+    assert_eq!(&value.as_bytes()[..], &[0x93, 1, 2, 3]);
+    check_roundtrip::<MsgPack>();
+
+    // array of three elements without elements
+    check_decode_error::<Vec<i32>, MsgPack>(&[0x93]);
+    check_decode_error::<String, MsgPack>(&[0x01]);
+}
+
+#[cfg(feature = "bincode")]
+#[test]
+fn test_bincode() {
+    use mco_redis::serialized::Bincode;
+
+    let value = Serialized::<_, Bincode>::new(7u32).unwrap();
+    assert_eq!(&value.as_bytes()[..], &[7, 0, 0, 0]);
+    check_roundtrip::<Bincode>();
+
+    // truncated length prefix
+    check_decode_error::<Vec<i32>, Bincode>(&[1, 0]);
+}
@@ -0,0 +1,75 @@
+use mco_redis::cmd::{self, commands::KeyspaceInfo, commands::ServerInfo};
+use mco_redis::codec_redis::Response;
+use mco_redis::errors::CommandError;
+use mco_redis::testing::{Action, MockServer};
+
+const INFO: &str = "# Server\r\n\
+redis_version:7.2.4\r\n\
+redis_mode:standalone\r\n\
+tcp_port:6379\r\n\
+\r\n\
+# Persistence\r\n\
+loading:0\r\n\
+aof_enabled:1\r\n\
+\r\n\
+# Replication\r\n\
+role:master\r\n\
+connected_slaves:0\r\n\
+\r\n\
+# Keyspace\r\n\
+db0:keys=10,expires=2,avg_ttl=100\r\n";
+
+#[test]
+fn test_info_parse() {
+    let info = ServerInfo::parse(INFO).unwrap();
+    assert_eq!(info.get("redis_mode"), Some("standalone"));
+    let server = info.server.unwrap();
+    assert_eq!(server.redis_version, "7.2.4");
+    assert_eq!(server.tcp_port, 6379);
+    let persistence = info.persistence.unwrap();
+    assert!(!persistence.loading);
+    assert!(persistence.aof_enabled);
+    let replication = info.replication.unwrap();
+    assert_eq!(replication.role, "master");
+    assert_eq!(replication.master_port, None);
+    assert!(info.memory.is_none());
+    assert_eq!(
+        info.keyspace[&0],
+        KeyspaceInfo {
+            keys: 10,
+            expires: 2,
+            avg_ttl: 100
+        }
+    );
+}
+
+#[test]
+fn test_info_malformed_value() {
+    for text in [
+        "# Server\r\ntcp_port:70000\r\n",
+        "# Persistence\r\nloading:yes\r\n",
+        "# Keyspace\r\ndb0:keys=10,expires=-1\r\n",
+    ] {
+        match ServerInfo::parse(text) {
+            Err(CommandError::Decode(msg, _)) => assert!(msg.starts_with("Invalid value")),
+            res => panic!("unexpected result: {:?}", res),
+        }
+    }
+
+    // unknown fields are kept as is
+    let info = ServerInfo::parse("# Server\r\nunknown:value\r\n").unwrap();
+    assert_eq!(info.get("unknown"), Some("value"));
+
+    let server = MockServer::start().unwrap();
+    let redis = server.connector().connect_simple().unwrap();
+    server.once(
+        "INFO",
+        Action::Reply(Response::Bytes(
+            "# Clients\r\nconnected_clients:x\r\n".into(),
+        )),
+    );
+    assert!(matches!(
+        redis.exec(cmd::Info()),
+        Err(CommandError::Decode(_, Response::Bytes(_)))
+    ));
+}